@@ -0,0 +1,546 @@
+use rowan::ast::AstNode;
+
+use crate::{
+    ast::{
+        CalendarDateStyle, Delay, DelayType, DiaryError, Headline, TimeUnit, Timestamp,
+        TimestampDate, TimestampTime, TimestampValue,
+    },
+    syntax::SyntaxKind,
+    Org,
+};
+
+/// Priority of headlines without a priority cookie, same as
+/// `org-priority-default`
+const DEFAULT_PRIORITY: char = 'B';
+
+/// Options of building an agenda
+#[derive(Debug, Clone)]
+pub struct AgendaOptions {
+    /// First day of the agenda
+    pub start: TimestampDate,
+    /// Last day of the agenda, inclusive
+    pub end: TimestampDate,
+    /// Current date, overdue items and deadline warnings are shown on this
+    /// day if the agenda covers it
+    pub today: TimestampDate,
+    /// Days before a deadline to start warning about it, if the deadline
+    /// doesn't have its own `-Nd` warning period, same as
+    /// `org-deadline-warning-days`
+    pub deadline_warning_days: u32,
+    /// Same as `org-agenda-skip-scheduled-if-done`
+    pub skip_scheduled_if_done: bool,
+    /// Same as `org-agenda-skip-deadline-if-done`
+    pub skip_deadline_if_done: bool,
+    /// Same as `org-agenda-skip-timestamp-if-done`
+    pub skip_timestamp_if_done: bool,
+    /// Skips subtrees tagged `ARCHIVE`, same as
+    /// `org-agenda-skip-archived-trees`
+    pub skip_archived: bool,
+    /// Skips commented subtrees, same as `org-agenda-skip-comment-trees`
+    pub skip_commented: bool,
+    /// Style of dates in diary sexps
+    pub date_style: CalendarDateStyle,
+}
+
+impl AgendaOptions {
+    /// Agenda of a single day
+    ///
+    /// ```rust
+    /// use orgize::{agenda::AgendaOptions, ast::TimestampDate};
+    ///
+    /// let options = AgendaOptions::day(TimestampDate::new(2024, 5, 1));
+    /// assert_eq!(options.start, options.end);
+    /// assert_eq!(options.deadline_warning_days, 14);
+    /// ```
+    pub fn day(today: TimestampDate) -> AgendaOptions {
+        AgendaOptions {
+            start: today,
+            end: today,
+            today,
+            deadline_warning_days: 14,
+            skip_scheduled_if_done: false,
+            skip_deadline_if_done: false,
+            skip_timestamp_if_done: false,
+            skip_archived: true,
+            skip_commented: true,
+            date_style: CalendarDateStyle::default(),
+        }
+    }
+
+    /// Agenda of the week containing given day, starting on Monday
+    ///
+    /// ```rust
+    /// use orgize::{agenda::AgendaOptions, ast::TimestampDate};
+    ///
+    /// let options = AgendaOptions::week(TimestampDate::new(2024, 5, 1));
+    /// assert_eq!(options.start, TimestampDate::new(2024, 4, 29));
+    /// assert_eq!(options.end, TimestampDate::new(2024, 5, 5));
+    /// ```
+    pub fn week(today: TimestampDate) -> AgendaOptions {
+        let start = today.add_days(-(((today.weekday() + 6) % 7) as i64));
+        AgendaOptions {
+            start,
+            end: start.add_days(6),
+            ..AgendaOptions::day(today)
+        }
+    }
+
+    fn contains(&self, date: TimestampDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// Why an entry shows up in the agenda
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AgendaEntryKind {
+    /// Scheduled on this day
+    Scheduled,
+    /// Scheduled given days ago and not done yet
+    ScheduledPast { days: u32 },
+    /// Due on this day
+    Deadline,
+    /// Due in given days
+    DeadlineUpcoming { days: u32 },
+    /// Due given days ago and not done yet
+    DeadlinePast { days: u32 },
+    /// Active timestamp on this day
+    Timestamp,
+    /// Given day of a range spanning `days` days, starting from 1
+    Range { day: u32, days: u32 },
+    /// Diary sexp matching this day
+    Diary,
+}
+
+/// An entry of agenda
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgendaEntry {
+    /// Day this entry is listed on
+    pub date: TimestampDate,
+    pub kind: AgendaEntryKind,
+    pub time: Option<TimestampTime>,
+    pub end_time: Option<TimestampTime>,
+    pub headline: Headline,
+    /// Timestamp this entry comes from
+    pub timestamp: Timestamp,
+    /// Index of the document this entry comes from
+    pub source: usize,
+}
+
+impl AgendaEntry {
+    /// Priority of the headline, `B` if it has no priority cookie
+    pub fn priority(&self) -> char {
+        self.headline
+            .priority()
+            .and_then(|priority| priority.chars().next())
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+}
+
+/// Agenda entries collected from one or more documents
+#[derive(Debug, Clone, Default)]
+pub struct Agenda {
+    /// Entries sorted by date, then time, then priority
+    ///
+    /// Entries with a time come before the ones without.
+    pub entries: Vec<AgendaEntry>,
+    /// Diary sexps that can't be evaluated, with index of their document
+    pub errors: Vec<(usize, Timestamp, DiaryError)>,
+}
+
+impl Agenda {
+    /// Collects agenda entries from given documents
+    ///
+    /// Entries come from `SCHEDULED` and `DEADLINE` planning, and from
+    /// active and diary timestamps in headline titles and sections.
+    /// Repeating timestamps show up on every day they repeat on. Not done
+    /// items scheduled or due in the past, and deadlines within their warning
+    /// period, are listed on [`AgendaOptions::today`].
+    ///
+    /// ```rust
+    /// use orgize::{
+    ///     agenda::{Agenda, AgendaEntryKind, AgendaOptions},
+    ///     ast::TimestampDate,
+    ///     Org,
+    /// };
+    ///
+    /// let work = Org::parse(r#"* TODO Write report
+    /// SCHEDULED: <2024-04-29 Mon>
+    /// * TODO [#A] Submit taxes
+    /// DEADLINE: <2024-05-03 Fri>
+    /// * DONE Book flights
+    /// DEADLINE: <2024-04-20 Sat>
+    /// * Standup <2024-04-29 Mon 09:30 +1d>
+    /// "#);
+    /// let home = Org::parse("* TODO Water plants\nSCHEDULED: <2024-05-01 Wed 18:00>\n");
+    ///
+    /// let mut options = AgendaOptions::day(TimestampDate::new(2024, 5, 1));
+    /// let agenda = Agenda::build([&work, &home], &options);
+    /// let entries: Vec<_> = agenda
+    ///     .entries
+    ///     .iter()
+    ///     .map(|e| (e.headline.title_raw(), e.kind, e.source))
+    ///     .collect();
+    /// assert_eq!(
+    ///     entries,
+    ///     [
+    ///         ("Standup <2024-04-29 Mon 09:30 +1d>".into(), AgendaEntryKind::Timestamp, 0),
+    ///         ("Water plants".into(), AgendaEntryKind::Scheduled, 1),
+    ///         ("Submit taxes".into(), AgendaEntryKind::DeadlineUpcoming { days: 2 }, 0),
+    ///         ("Write report".into(), AgendaEntryKind::ScheduledPast { days: 2 }, 0),
+    ///     ]
+    /// );
+    ///
+    /// // done items are still shown on their own day, unless skipped
+    /// options.start = TimestampDate::new(2024, 4, 20);
+    /// let agenda = Agenda::build([&work], &options);
+    /// assert_eq!(agenda.entries[0].kind, AgendaEntryKind::Deadline);
+    /// assert_eq!(agenda.entries[0].headline.title_raw(), "Book flights");
+    ///
+    /// options.skip_deadline_if_done = true;
+    /// let agenda = Agenda::build([&work], &options);
+    /// assert!(agenda.entries.iter().all(|e| e.headline.title_raw() != "Book flights"));
+    /// ```
+    pub fn build<'a>(orgs: impl IntoIterator<Item = &'a Org>, options: &AgendaOptions) -> Agenda {
+        let mut agenda = Agenda::default();
+
+        for (source, org) in orgs.into_iter().enumerate() {
+            for headline in org
+                .document()
+                .syntax
+                .descendants()
+                .filter_map(Headline::cast)
+            {
+                let skipped =
+                    in_skipped_subtree(&headline, options.skip_archived, options.skip_commented);
+                if skipped {
+                    continue;
+                }
+
+                let mut collector = Collector {
+                    options,
+                    source,
+                    done: headline.is_done(),
+                    headline: &headline,
+                    agenda: &mut agenda,
+                };
+                if let Some(deadline) = headline.deadline() {
+                    collector.deadline(deadline);
+                }
+                if let Some(scheduled) = headline.scheduled() {
+                    collector.scheduled(scheduled);
+                }
+                collector.timestamps();
+            }
+        }
+
+        // stable sort, so entries with same key keep document order
+        agenda.entries.sort_by_key(|entry| {
+            (
+                entry.date,
+                entry.time.is_none(),
+                entry.time,
+                entry.priority(),
+            )
+        });
+
+        agenda
+    }
+}
+
+impl Org {
+    /// Collects agenda entries from this document, see [`Agenda::build`]
+    ///
+    /// ```rust
+    /// use orgize::{agenda::AgendaOptions, ast::TimestampDate, Org};
+    ///
+    /// let org = Org::parse("* Meeting <2024-05-02 Thu 10:00-11:00>");
+    /// let agenda = org.agenda(&AgendaOptions::week(TimestampDate::new(2024, 5, 1)));
+    /// assert_eq!(agenda.entries.len(), 1);
+    /// assert_eq!(agenda.entries[0].date, TimestampDate::new(2024, 5, 2));
+    /// assert_eq!(agenda.entries[0].end_time.unwrap().to_string(), "11:00");
+    /// ```
+    pub fn agenda(&self, options: &AgendaOptions) -> Agenda {
+        Agenda::build([self], options)
+    }
+}
+
+struct Collector<'a> {
+    options: &'a AgendaOptions,
+    source: usize,
+    done: bool,
+    headline: &'a Headline,
+    agenda: &'a mut Agenda,
+}
+
+impl Collector<'_> {
+    fn push(
+        &mut self,
+        date: TimestampDate,
+        kind: AgendaEntryKind,
+        timestamp: &Timestamp,
+        time: Option<TimestampTime>,
+        end_time: Option<TimestampTime>,
+    ) {
+        self.agenda.entries.push(AgendaEntry {
+            date,
+            kind,
+            time,
+            end_time,
+            headline: self.headline.clone(),
+            timestamp: timestamp.clone(),
+            source: self.source,
+        });
+    }
+
+    /// Whether overdue items and warnings should be listed on today
+    fn shows_today(&self) -> bool {
+        !self.done && self.options.contains(self.options.today)
+    }
+
+    fn deadline(&mut self, timestamp: Timestamp) {
+        let Some(value) = timestamp.value().filter(|value| value.active) else {
+            return;
+        };
+        if self.done && self.options.skip_deadline_if_done {
+            return;
+        }
+
+        let today = self.options.today;
+        let mut due_today = false;
+        for occurrence in value.occurrences(self.options.start, self.options.end) {
+            if self.options.contains(occurrence.start_date) {
+                due_today |= occurrence.start_date == today;
+                self.push(
+                    occurrence.start_date,
+                    AgendaEntryKind::Deadline,
+                    &timestamp,
+                    occurrence.start_time,
+                    occurrence.end_time,
+                );
+            }
+        }
+
+        if due_today || !self.shows_today() {
+            return;
+        }
+
+        let days = value.start_date.to_days() - today.to_days();
+        let warning = value
+            .warning
+            .map_or(self.options.deadline_warning_days as i64, |delay| {
+                value.start_date.to_days() - shift(value.start_date, delay, -1).to_days()
+            });
+        if days < 0 {
+            let kind = AgendaEntryKind::DeadlinePast {
+                days: (-days) as u32,
+            };
+            self.push(today, kind, &timestamp, None, None);
+        } else if days > 0 && days <= warning {
+            let kind = AgendaEntryKind::DeadlineUpcoming { days: days as u32 };
+            self.push(today, kind, &timestamp, None, None);
+        }
+    }
+
+    fn scheduled(&mut self, timestamp: Timestamp) {
+        let Some(value) = timestamp.value().filter(|value| value.active) else {
+            return;
+        };
+        if self.done && self.options.skip_scheduled_if_done {
+            return;
+        }
+
+        // a `-Nd` delay hides the entry until given days after the scheduled
+        // date, `--Nd` only delays the first repeat
+        let shown = |date: TimestampDate| match value.warning {
+            Some(delay) if delay.kind == DelayType::All || date == value.start_date => {
+                shift(date, delay, 1)
+            }
+            _ => date,
+        };
+
+        let today = self.options.today;
+        let from = match value.warning {
+            Some(delay) => shift(self.options.start, delay, -1),
+            None => self.options.start,
+        };
+        let mut shown_today = false;
+        for occurrence in value.occurrences(from, self.options.end) {
+            let date = shown(occurrence.start_date);
+            if self.options.contains(date) {
+                shown_today |= date == today;
+                self.push(
+                    date,
+                    AgendaEntryKind::Scheduled,
+                    &timestamp,
+                    occurrence.start_time,
+                    occurrence.end_time,
+                );
+            }
+        }
+
+        if !shown_today && self.shows_today() && shown(value.start_date) < today {
+            let kind = AgendaEntryKind::ScheduledPast {
+                days: (today.to_days() - value.start_date.to_days()) as u32,
+            };
+            self.push(today, kind, &timestamp, None, None);
+        }
+    }
+
+    fn timestamps(&mut self) {
+        if self.done && self.options.skip_timestamp_if_done {
+            return;
+        }
+
+        let title = self
+            .headline
+            .syntax
+            .children()
+            .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE);
+        let section = self.headline.section().map(|section| section.syntax);
+        let timestamps: Vec<_> = title
+            .into_iter()
+            .chain(section)
+            .flat_map(|node| node.descendants())
+            .filter_map(Timestamp::cast)
+            .collect();
+
+        for timestamp in timestamps {
+            if timestamp.is_diary() {
+                self.diary(timestamp);
+            } else if timestamp.is_active() {
+                if let Some(value) = timestamp.value() {
+                    self.timestamp(&timestamp, value);
+                }
+            }
+        }
+    }
+
+    fn timestamp(&mut self, timestamp: &Timestamp, value: TimestampValue) {
+        for occurrence in value.occurrences(self.options.start, self.options.end) {
+            let start = occurrence.start_date;
+            let Some(end) = occurrence.end_date.filter(|end| *end != start) else {
+                if self.options.contains(start) {
+                    self.push(
+                        start,
+                        AgendaEntryKind::Timestamp,
+                        timestamp,
+                        occurrence.start_time,
+                        occurrence.end_time,
+                    );
+                }
+                continue;
+            };
+
+            let days = (end.to_days() - start.to_days() + 1) as u32;
+            let mut date = start.max(self.options.start);
+            while date <= end.min(self.options.end) {
+                let kind = AgendaEntryKind::Range {
+                    day: (date.to_days() - start.to_days() + 1) as u32,
+                    days,
+                };
+                let time = occurrence.start_time.filter(|_| date == start);
+                let end_time = occurrence.end_time.filter(|_| date == end);
+                self.push(date, kind, timestamp, time, end_time);
+                date = date.add_days(1);
+            }
+        }
+    }
+
+    fn diary(&mut self, timestamp: Timestamp) {
+        match timestamp.diary_sexp(self.options.date_style) {
+            Some(Ok(sexp)) => {
+                let mut date = self.options.start;
+                while date <= self.options.end {
+                    if sexp.matches(date) {
+                        self.push(date, AgendaEntryKind::Diary, &timestamp, None, None);
+                    }
+                    date = date.add_days(1);
+                }
+            }
+            Some(Err(err)) => self.agenda.errors.push((self.source, timestamp, err)),
+            None => {}
+        }
+    }
+}
+
+/// Whether the headline is in an archived or commented subtree
+pub(super) fn in_skipped_subtree(headline: &Headline, archived: bool, commented: bool) -> bool {
+    headline
+        .syntax
+        .ancestors()
+        .filter_map(Headline::cast)
+        .any(|h| (archived && h.is_archived()) || (commented && h.is_commented()))
+}
+
+/// Shifts date by a delay, forward if `sign` is positive
+fn shift(date: TimestampDate, delay: Delay, sign: i64) -> TimestampDate {
+    let value = delay.value as i64 * sign;
+    match delay.unit {
+        TimeUnit::Hour => date,
+        TimeUnit::Day => date.add_days(value),
+        TimeUnit::Week => date.add_days(value * 7),
+        TimeUnit::Month => date.add_months(value),
+        TimeUnit::Year => date.add_months(value * 12),
+    }
+}
+
+#[test]
+fn agenda() {
+    let kinds = |input: &str, options: &AgendaOptions| -> Vec<(u32, AgendaEntryKind)> {
+        Org::parse(input)
+            .agenda(options)
+            .entries
+            .into_iter()
+            .map(|entry| (entry.date.day, entry.kind))
+            .collect()
+    };
+
+    let today = TimestampDate::new(2024, 5, 1);
+    let week = AgendaOptions::week(today);
+
+    // deadline with its own warning period
+    assert_eq!(
+        kinds("* TODO a\nDEADLINE: <2024-05-04 Sat -2d>", &week),
+        [(4, AgendaEntryKind::Deadline)]
+    );
+    assert_eq!(
+        kinds("* TODO a\nDEADLINE: <2024-05-03 Fri -2d>", &week),
+        [
+            (1, AgendaEntryKind::DeadlineUpcoming { days: 2 }),
+            (3, AgendaEntryKind::Deadline)
+        ]
+    );
+
+    // overdue deadline with repeater is carried over to today only
+    assert_eq!(
+        kinds("* TODO a\nDEADLINE: <2024-04-20 Sat +1m>", &week),
+        [(1, AgendaEntryKind::DeadlinePast { days: 11 })]
+    );
+
+    // delayed scheduled item
+    assert_eq!(
+        kinds("* TODO a\nSCHEDULED: <2024-04-29 Mon -3d>", &week),
+        [(2, AgendaEntryKind::Scheduled)]
+    );
+
+    // ranges and diary sexps
+    assert_eq!(
+        kinds(
+            "* a <2024-05-04 Sat 10:00>--<2024-05-06 Mon 12:00>\n<%%(diary-float t 3 1)>",
+            &week
+        ),
+        [
+            (1, AgendaEntryKind::Diary),
+            (4, AgendaEntryKind::Range { day: 1, days: 3 }),
+            (5, AgendaEntryKind::Range { day: 2, days: 3 }),
+        ]
+    );
+
+    // archived and commented subtrees
+    assert!(kinds("* a :ARCHIVE:\n** b <2024-05-01>", &week).is_empty());
+    assert!(kinds("* COMMENT a <2024-05-01>", &week).is_empty());
+
+    let agenda = Org::parse("* a <%%(diary-foo)>").agenda(&week);
+    assert!(agenda.entries.is_empty());
+    assert_eq!(agenda.errors.len(), 1);
+}
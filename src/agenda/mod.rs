@@ -0,0 +1,7 @@
+//! Agenda views built from planning lines and active timestamps
+
+mod collect;
+mod render;
+
+pub use collect::{Agenda, AgendaEntry, AgendaEntryKind, AgendaOptions};
+pub use render::{AgendaRenderer, TagsSearchError};
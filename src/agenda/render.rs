@@ -0,0 +1,531 @@
+use std::{fmt, path::Path};
+
+use rowan::ast::AstNode;
+use unicode_width::UnicodeWidthStr;
+
+use super::{collect::in_skipped_subtree, Agenda, AgendaEntry, AgendaEntryKind, AgendaOptions};
+use crate::{
+    ast::{Document, Headline, TimestampDate, TimestampTime},
+    Org,
+};
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Category of entries without `CATEGORY` nor file name
+const UNKNOWN_CATEGORY: &str = "???";
+
+/// Renders agenda views as plain text, like `org-agenda` buffers
+///
+/// Lines are formatted like the default `org-agenda-prefix-format`:
+/// category, time and a leader such as `Scheduled:` or `In   3 d.:`,
+/// followed by the headline with its tags aligned to the right.
+#[derive(Debug, Clone)]
+pub struct AgendaRenderer {
+    /// File name of each document, in the order given to [`Agenda::build`]
+    ///
+    /// File name without extension is used as category of entries not
+    /// having a `CATEGORY` property or keyword.
+    pub file_names: Vec<String>,
+    /// Shows time grid on today, or on every day of a single day agenda,
+    /// if the day has timed entries
+    pub time_grid: bool,
+    /// Times of the time grid, from 8:00 to 20:00 every two hours by default
+    pub grid_times: Vec<TimestampTime>,
+    /// Column tags end at, same as `org-agenda-tags-column`
+    pub tags_column: usize,
+}
+
+impl Default for AgendaRenderer {
+    fn default() -> Self {
+        AgendaRenderer {
+            file_names: vec![],
+            time_grid: true,
+            grid_times: (8..=20)
+                .step_by(2)
+                .map(|hour| TimestampTime::new(hour, 0))
+                .collect(),
+            tags_column: 80,
+        }
+    }
+}
+
+impl AgendaRenderer {
+    /// Renders agenda of the days covered by given options
+    ///
+    /// ```rust
+    /// use orgize::{
+    ///     agenda::{Agenda, AgendaOptions, AgendaRenderer},
+    ///     ast::TimestampDate,
+    ///     Org,
+    /// };
+    ///
+    /// let work = Org::parse(r#"* TODO Write report :writing:
+    /// SCHEDULED: <2024-04-29 Mon>
+    /// * TODO [#A] Submit taxes
+    /// DEADLINE: <2024-05-03 Fri>
+    /// * Standup <2024-05-01 Wed 09:30-09:45>
+    /// "#);
+    /// let home = Org::parse(r#"#+CATEGORY: chores
+    /// * Garden :outdoor:
+    /// ** TODO Water plants
+    /// SCHEDULED: <2024-05-01 Wed 18:00>
+    /// "#);
+    ///
+    /// let mut options = AgendaOptions::week(TimestampDate::new(2024, 5, 1));
+    /// options.end = TimestampDate::new(2024, 5, 1);
+    /// let agenda = Agenda::build([&work, &home], &options);
+    ///
+    /// let renderer = AgendaRenderer {
+    ///     file_names: vec!["work.org".into(), "home.org".into()],
+    ///     tags_column: 60,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     renderer.agenda(&agenda, &options),
+    ///     r#"3-days-agenda (W18):
+    /// Monday     29 April 2024 W18
+    ///   work:       Scheduled:  TODO Write report        :writing:
+    /// Tuesday    30 April 2024
+    /// Wednesday   1 May 2024
+    ///                8:00...... ----------------
+    ///   work:        9:30-9:45  Standup <2024-05-01 Wed 09:30-09:45>
+    ///               10:00...... ----------------
+    ///               12:00...... ----------------
+    ///               14:00...... ----------------
+    ///               16:00...... ----------------
+    ///   chores:     18:00...... Scheduled:  TODO Water plants :outdoor:
+    ///               20:00...... ----------------
+    ///   work:       In   2 d.:  TODO [#A] Submit taxes
+    ///   work:       Sched. 2x:  TODO Write report        :writing:
+    /// "#
+    /// );
+    /// ```
+    pub fn agenda(&self, agenda: &Agenda, options: &AgendaOptions) -> String {
+        let span = options.end.to_days() - options.start.to_days() + 1;
+        let name = match span {
+            1 => "Day".to_string(),
+            7 => "Week".to_string(),
+            14 => "Fortnight".to_string(),
+            _ => format!("{span}-days"),
+        };
+        let (first, last) = (options.start.iso_week(), options.end.iso_week());
+        let mut output = if first == last {
+            format!("{name}-agenda (W{first:02}):\n")
+        } else {
+            format!("{name}-agenda (W{first:02}-W{last:02}):\n")
+        };
+
+        let mut date = options.start;
+        while date <= options.end {
+            output += &day_header(date);
+            output += "\n";
+
+            let entries: Vec<_> = agenda.entries.iter().filter(|e| e.date == date).collect();
+            let has_timed = entries.iter().any(|e| e.time.is_some());
+            let mut grid: &[TimestampTime] = &[];
+            if self.time_grid && has_timed && (span == 1 || date == options.today) {
+                grid = &self.grid_times;
+            }
+            // grid lines at the same time as an entry are hidden
+            let mut grid = grid
+                .iter()
+                .filter(|time| entries.iter().all(|e| e.time != Some(**time)))
+                .peekable();
+
+            for entry in &entries {
+                if let Some(time) = entry.time {
+                    while let Some(grid_time) = grid.next_if(|t| **t < time) {
+                        output += &grid_line(*grid_time);
+                    }
+                } else {
+                    for grid_time in grid.by_ref() {
+                        output += &grid_line(*grid_time);
+                    }
+                }
+                output += &self.entry_line(entry);
+            }
+            for grid_time in grid {
+                output += &grid_line(*grid_time);
+            }
+
+            date = date.add_days(1);
+        }
+
+        output
+    }
+
+    /// Renders all not done TODO entries, like `org-todo-list`
+    ///
+    /// ```rust
+    /// use orgize::{agenda::AgendaRenderer, Org};
+    ///
+    /// let org = Org::parse("* TODO a\n* DONE b\n* c\n** TODO d\n:PROPERTIES:\n:CATEGORY: misc\n:END:\n");
+    /// let renderer = AgendaRenderer {
+    ///     file_names: vec!["notes.org".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     renderer.todo_list([&org]),
+    ///     "Global list of TODO items of type: ALL\n  notes:      TODO a\n  misc:       TODO d\n"
+    /// );
+    /// ```
+    pub fn todo_list<'a>(&self, orgs: impl IntoIterator<Item = &'a Org>) -> String {
+        let mut output = "Global list of TODO items of type: ALL\n".to_string();
+        for (source, headline) in headlines(orgs) {
+            if headline.todo_keyword().is_some() && !headline.is_done() {
+                output += &self.line(&headline, source, "", "");
+            }
+        }
+        output
+    }
+
+    /// Renders entries matching a tags search, like `org-tags-view`
+    ///
+    /// Tags are inherited from parent headlines and `#+FILETAGS`. A search
+    /// is made of alternatives separated by `|`, each of them is a list of
+    /// tags required with `+` or `&`, or forbidden with `-`. It can end with
+    /// `/` and TODO keywords separated by `|` to only match these keywords.
+    ///
+    /// Property, level and regexp matches aren't supported, searches using
+    /// them are rejected with [`TagsSearchError`].
+    ///
+    /// ```rust
+    /// use orgize::{agenda::AgendaRenderer, Org};
+    ///
+    /// let org = Org::parse(r#"#+FILETAGS: :team:
+    /// * Project :work:
+    /// ** TODO Review :urgent:
+    /// ** TODO Deploy
+    /// * TODO Groceries :home:urgent:
+    /// "#);
+    /// let renderer = AgendaRenderer {
+    ///     tags_column: 40,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     renderer.tags_match([&org], "work-urgent|home").unwrap(),
+    ///     r#"Headlines with TAGS match: work-urgent|home
+    ///   ???:        Project        :team:work:
+    ///   ???:        TODO Deploy    :team:work:
+    ///   ???:        TODO Groceries :team:home:urgent:
+    /// "#
+    /// );
+    /// assert_eq!(
+    ///     renderer.tags_match([&org], "urgent/TODO").unwrap(),
+    ///     r#"Headlines with TAGS match: urgent/TODO
+    ///   ???:        TODO Review :team:work:urgent:
+    ///   ???:        TODO Groceries :team:home:urgent:
+    /// "#
+    /// );
+    ///
+    /// let err = renderer.tags_match([&org], "work+PRIORITY=\"A\"").unwrap_err();
+    /// assert_eq!(err.to_string(), r#"unsupported tags search PRIORITY="A""#);
+    /// assert!(renderer.tags_match([&org], "LEVEL>2").is_err());
+    /// assert!(renderer.tags_match([&org], "{^w}").is_err());
+    /// assert!(renderer.tags_match([&org], "work/!DONE").is_err());
+    /// ```
+    pub fn tags_match<'a>(
+        &self,
+        orgs: impl IntoIterator<Item = &'a Org>,
+        search: &str,
+    ) -> Result<String, TagsSearchError> {
+        let parsed = TagsSearch::parse(search)?;
+        let mut output = format!("Headlines with TAGS match: {search}\n");
+        for (source, headline) in headlines(orgs) {
+            let keyword = headline.todo_keyword();
+            if parsed.matches(&tags(&headline), keyword.as_deref()) {
+                output += &self.line(&headline, source, "", "");
+            }
+        }
+        Ok(output)
+    }
+
+    fn entry_line(&self, entry: &AgendaEntry) -> String {
+        let time = match (entry.time, entry.end_time) {
+            (Some(start), Some(end)) => format!("{}-{}:{:02}", clock(start), end.hour, end.minute),
+            (Some(start), None) => format!("{}......", clock(start)),
+            _ => String::new(),
+        };
+        let leader = match entry.kind {
+            AgendaEntryKind::Scheduled => "Scheduled: ".to_string(),
+            AgendaEntryKind::ScheduledPast { days } => format!("Sched.{days:>2}x: "),
+            AgendaEntryKind::Deadline => "Deadline:  ".to_string(),
+            AgendaEntryKind::DeadlineUpcoming { days } => format!("In {days:>3} d.: "),
+            AgendaEntryKind::DeadlinePast { days } => format!("{days:>2} d. ago: "),
+            AgendaEntryKind::Range { day, days } => format!("({day}/{days}): "),
+            AgendaEntryKind::Timestamp | AgendaEntryKind::Diary => String::new(),
+        };
+        self.line(&entry.headline, entry.source, &time, &leader)
+    }
+
+    fn line(&self, headline: &Headline, source: usize, time: &str, leader: &str) -> String {
+        let mut line = format!(
+            "  {}",
+            pad(&format!("{}:", self.category(headline, source)))
+        );
+        if !time.is_empty() {
+            line += &pad(time);
+        }
+        if !leader.is_empty() {
+            line += leader;
+            line += " ";
+        }
+        if let Some(keyword) = headline.todo_keyword() {
+            line += &keyword;
+            line += " ";
+        }
+        if let Some(priority) = headline.priority() {
+            line += &format!("[#{}] ", &*priority);
+        }
+        line += headline.title_raw().trim();
+
+        let tags = tags(headline);
+        if !tags.is_empty() {
+            let tags = format!(":{}:", tags.join(":"));
+            let fill = self
+                .tags_column
+                .saturating_sub(line.width() + tags.width())
+                .max(1);
+            line += &" ".repeat(fill);
+            line += &tags;
+        }
+
+        line + "\n"
+    }
+
+    /// Category from the `CATEGORY` property of headline or its ancestors,
+    /// then from the document, then from the file name
+    fn category(&self, headline: &Headline, source: usize) -> String {
+        let from_headlines = headline
+            .syntax
+            .ancestors()
+            .filter_map(Headline::cast)
+            .find_map(|h| h.properties()?.get("CATEGORY"));
+        if let Some(category) = from_headlines {
+            return category.trim().to_string();
+        }
+
+        let document = headline.syntax.ancestors().find_map(Document::cast);
+        let from_document = document.and_then(|document| {
+            document
+                .properties()
+                .and_then(|drawer| drawer.get("CATEGORY"))
+                .map(|value| value.trim().to_string())
+                .or_else(|| {
+                    document
+                        .keywords()
+                        .filter(|keyword| keyword.key().eq_ignore_ascii_case("CATEGORY"))
+                        .last()
+                        .map(|keyword| keyword.value().trim().to_string())
+                })
+        });
+        if let Some(category) = from_document {
+            return category;
+        }
+
+        self.file_names
+            .get(source)
+            .and_then(|name| Path::new(name).file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| UNKNOWN_CATEGORY.to_string())
+    }
+}
+
+/// Headlines outside archived or commented subtrees, with index of their
+/// document
+fn headlines<'a>(orgs: impl IntoIterator<Item = &'a Org>) -> Vec<(usize, Headline)> {
+    orgs.into_iter()
+        .enumerate()
+        .flat_map(|(source, org)| {
+            org.document()
+                .syntax
+                .descendants()
+                .filter_map(Headline::cast)
+                .filter(|headline| !in_skipped_subtree(headline, true, true))
+                .map(move |headline| (source, headline))
+        })
+        .collect()
+}
+
+/// Tags of headline, including the inherited ones and `#+FILETAGS`
+fn tags(headline: &Headline) -> Vec<String> {
+    let mut tags: Vec<String> = headline
+        .syntax
+        .ancestors()
+        .find_map(Document::cast)
+        .into_iter()
+        .flat_map(|document| document.keywords())
+        .filter(|keyword| keyword.key().eq_ignore_ascii_case("FILETAGS"))
+        .flat_map(|keyword| {
+            keyword
+                .value()
+                .split(':')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut ancestors: Vec<_> = headline
+        .syntax
+        .ancestors()
+        .filter_map(Headline::cast)
+        .collect();
+    ancestors.reverse();
+    for tag in ancestors.iter().flat_map(|h| h.tags()) {
+        if !tags.iter().any(|t| *t == *tag) {
+            tags.push(tag.to_string());
+        }
+    }
+
+    tags
+}
+
+/// Error returned by [`AgendaRenderer::tags_match`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagsSearchError {
+    /// Part of search which isn't a tag or TODO keyword, e.g. a property,
+    /// level or regexp match
+    Unsupported(String),
+}
+
+impl fmt::Display for TagsSearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagsSearchError::Unsupported(part) => write!(f, "unsupported tags search {part}"),
+        }
+    }
+}
+
+impl std::error::Error for TagsSearchError {}
+
+/// Parsed tags search, e.g. `work+urgent-home|errand/TODO`
+struct TagsSearch {
+    /// Alternatives separated by `|`, each of them is a list of tags and
+    /// whether they're required
+    alternatives: Vec<Vec<(String, bool)>>,
+    /// TODO keywords after `/`, matches any keyword if empty
+    keywords: Vec<String>,
+}
+
+impl TagsSearch {
+    fn parse(search: &str) -> Result<TagsSearch, TagsSearchError> {
+        let (tags, keywords) = search.split_once('/').unwrap_or((search, ""));
+
+        let keywords = keywords
+            .split('|')
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(|k| {
+                if k.chars().all(is_tag_char) {
+                    Ok(k.to_string())
+                } else {
+                    Err(TagsSearchError::Unsupported(k.to_string()))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        let alternatives = tags
+            .split('|')
+            .map(|alternative| {
+                let mut terms = vec![];
+                let mut required = true;
+                let mut rest = alternative.trim();
+                while let Some(c) = rest.chars().next() {
+                    if matches!(c, '+' | '&' | '-') {
+                        required = c != '-';
+                        rest = &rest[1..];
+                        continue;
+                    }
+                    let len = rest.find(|c| !is_tag_char(c)).unwrap_or(rest.len());
+                    if len == 0 {
+                        return Err(TagsSearchError::Unsupported(rest.to_string()));
+                    }
+                    let name = &rest[..len];
+                    rest = &rest[len..];
+                    if rest.starts_with(|c| !matches!(c, '+' | '&' | '-')) {
+                        return Err(TagsSearchError::Unsupported(format!("{name}{rest}")));
+                    }
+                    terms.push((name.to_string(), required));
+                    required = true;
+                }
+                Ok(terms)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(TagsSearch {
+            alternatives,
+            keywords,
+        })
+    }
+
+    /// Whether tags and TODO keyword match this search
+    fn matches(&self, tags: &[String], keyword: Option<&str>) -> bool {
+        if !self.keywords.is_empty() && !self.keywords.iter().any(|k| keyword == Some(k)) {
+            return false;
+        }
+
+        self.alternatives.iter().any(|terms| {
+            terms
+                .iter()
+                .all(|(name, required)| tags.contains(name) == *required)
+        })
+    }
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%')
+}
+
+/// Day header, like `org-agenda-format-date-aligned`
+fn day_header(date: TimestampDate) -> String {
+    let mut header = format!(
+        "{:<10} {:>2} {} {}",
+        WEEKDAY_NAMES[date.weekday() as usize],
+        date.day,
+        MONTH_NAMES[(date.month as usize).clamp(1, 12) - 1],
+        date.year
+    );
+    if date.weekday() == 1 {
+        header += &format!(" W{:02}", date.iso_week());
+    }
+    header
+}
+
+fn grid_line(time: TimestampTime) -> String {
+    format!("{:14}{}...... ----------------\n", "", clock(time))
+}
+
+/// Time with hour padded to two columns, e.g. ` 8:00`
+fn clock(time: TimestampTime) -> String {
+    format!("{:>2}:{:02}", time.hour, time.minute)
+}
+
+/// Pads text to the width of the category and time columns
+fn pad(text: &str) -> String {
+    format!("{text}{}", " ".repeat(12usize.saturating_sub(text.width())))
+}
@@ -97,6 +97,111 @@ impl SourceBlock {
             .filter_map(filter_token(SyntaxKind::TEXT))
             .fold(String::new(), |acc, value| acc + &value)
     }
+
+    /// Parsed switches, see [`SrcSwitches`]
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{LineNumbering, SourceBlock}};
+    ///
+    /// let block = Org::parse("#+begin_src c -n 10 -r\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// let switches = block.src_switches();
+    /// assert_eq!(switches.numbering, Some(LineNumbering::New(10)));
+    /// assert!(switches.remove_labels);
+    ///
+    /// let block = Org::parse("#+begin_src c\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.src_switches().numbering, None);
+    /// ````
+    pub fn src_switches(&self) -> SrcSwitches {
+        self.switches()
+            .map(|s| SrcSwitches::parse(&s))
+            .unwrap_or_default()
+    }
+
+    /// Returns source code lines, with line numbers and coderef labels
+    ///
+    /// `last_number` is the last line number of the previous numbered block
+    /// in the document, and is only used by continued numbering (`+n`).
+    ///
+    /// Common indentation is removed unless `-i` is given. Coderef labels are
+    /// removed from line content if `-r` is given.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::SourceBlock};
+    ///
+    /// let block = Org::parse(r#"
+    /// #+begin_src c +n 2 -r
+    ///   int x; (ref:x)
+    ///   return x;
+    /// #+end_src
+    /// "#).first_node::<SourceBlock>().unwrap();
+    /// let lines = block.lines(5);
+    /// assert_eq!(lines[0].number, Some(7));
+    /// assert_eq!(lines[0].content, "int x;");
+    /// assert_eq!(lines[0].label.as_deref(), Some("x"));
+    /// assert_eq!(lines[1].number, Some(8));
+    /// assert_eq!(lines[1].content, "return x;");
+    /// assert_eq!(lines[1].label, None);
+    ///
+    /// let block = Org::parse(r#"
+    /// #+begin_src c -i -l "[%s]"
+    ///   int x; [x]
+    /// #+end_src
+    /// "#).first_node::<SourceBlock>().unwrap();
+    /// let lines = block.lines(0);
+    /// assert_eq!(lines[0].number, None);
+    /// assert_eq!(lines[0].content, "  int x; [x]");
+    /// assert_eq!(lines[0].label.as_deref(), Some("x"));
+    /// ````
+    pub fn lines(&self, last_number: u32) -> Vec<SourceLine> {
+        let switches = self.src_switches();
+        let value = self.value();
+
+        let indent = if switches.preserve_indentation {
+            0
+        } else {
+            value
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.len() - line.trim_start().len())
+                .min()
+                .unwrap_or_default()
+        };
+
+        let first = match switches.numbering {
+            Some(LineNumbering::New(start)) => Some(start),
+            Some(LineNumbering::Continued(offset)) => Some(last_number.saturating_add(offset)),
+            None => None,
+        };
+
+        value
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let line = if line.trim().is_empty() {
+                    line.trim_start()
+                } else {
+                    line.get(indent..).unwrap_or(line)
+                };
+                let number = first.map(|first| first.saturating_add(idx as u32));
+                match coderef(line, switches.label_format()) {
+                    Some((start, label)) => SourceLine {
+                        number,
+                        content: if switches.remove_labels {
+                            line[..start].to_string()
+                        } else {
+                            line.to_string()
+                        },
+                        label: Some(label.to_string()),
+                    },
+                    None => SourceLine {
+                        number,
+                        content: line.to_string(),
+                        label: None,
+                    },
+                }
+            })
+            .collect()
+    }
 }
 
 impl ExportBlock {
@@ -192,3 +297,124 @@ impl_content_border!(QuoteBlock);
 impl_content_border!(SpecialBlock);
 impl_content_border!(VerseBlock);
 impl_content_border!(DynBlock);
+
+/// Line numbering of source block
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineNumbering {
+    /// `-n [start]`, numbering starts from `start`, defaults to `1`
+    New(u32),
+    /// `+n [offset]`, numbering continues from the previous numbered block,
+    /// the first line is numbered `last + offset`, `offset` defaults to `1`
+    Continued(u32),
+}
+
+/// Parsed source block switches
+///
+/// ```rust
+/// use orgize::ast::{LineNumbering, SrcSwitches};
+///
+/// let switches = SrcSwitches::parse(r#"+n 10 -r -i -l "[%s]""#);
+/// assert_eq!(switches.numbering, Some(LineNumbering::Continued(10)));
+/// assert_eq!(switches.label_format(), "[%s]");
+/// assert!(switches.remove_labels);
+/// assert!(switches.preserve_indentation);
+///
+/// let switches = SrcSwitches::parse("-n");
+/// assert_eq!(switches.numbering, Some(LineNumbering::New(1)));
+/// assert_eq!(switches.label_format(), "(ref:%s)");
+/// assert!(!switches.remove_labels);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SrcSwitches {
+    /// `-n` or `+n`
+    pub numbering: Option<LineNumbering>,
+    /// `-l "format"`, coderef label format containing `%s`
+    pub label_format: Option<String>,
+    /// `-r`, removes coderef labels from code
+    pub remove_labels: bool,
+    /// `-i`, preserves indentation of code
+    pub preserve_indentation: bool,
+}
+
+impl SrcSwitches {
+    pub fn parse(s: &str) -> SrcSwitches {
+        let mut switches = SrcSwitches::default();
+        let mut words = split_switches(s).into_iter().peekable();
+
+        while let Some(word) = words.next() {
+            match word {
+                "-n" | "+n" => {
+                    let number = words.next_if(|w| w.bytes().all(|b| b.is_ascii_digit()));
+                    let number = number.and_then(|n| n.parse().ok());
+                    switches.numbering = Some(if word == "-n" {
+                        LineNumbering::New(number.unwrap_or(1))
+                    } else {
+                        LineNumbering::Continued(number.unwrap_or(1))
+                    });
+                }
+                "-l" => {
+                    if let Some(format) = words.next() {
+                        let format = format
+                            .strip_prefix('"')
+                            .and_then(|f| f.strip_suffix('"'))
+                            .unwrap_or(format);
+                        switches.label_format = Some(format.to_string());
+                    }
+                }
+                "-r" => switches.remove_labels = true,
+                "-i" => switches.preserve_indentation = true,
+                _ => {}
+            }
+        }
+
+        switches
+    }
+
+    /// Coderef label format, defaults to `(ref:%s)`
+    pub fn label_format(&self) -> &str {
+        self.label_format.as_deref().unwrap_or("(ref:%s)")
+    }
+}
+
+/// Source code line, returned by [`SourceBlock::lines`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLine {
+    /// Line number, `None` if numbering is disabled
+    pub number: Option<u32>,
+    /// Line content, without trailing newline
+    pub content: String,
+    /// Coderef label of this line
+    pub label: Option<String>,
+}
+
+/// Splits switches by whitespaces, double quoted strings are kept together
+fn split_switches(s: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let end = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.find('"').map(|i| i + 2).unwrap_or(rest.len())
+        } else {
+            rest.find(char::is_whitespace).unwrap_or(rest.len())
+        };
+        words.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    words
+}
+
+/// Finds coderef label at the end of line, returns the position where
+/// the label (including preceding whitespaces) starts and the label itself
+fn coderef<'a>(line: &'a str, format: &str) -> Option<(usize, &'a str)> {
+    let (before, after) = format.split_once("%s")?;
+    let rest = line.trim_end().strip_suffix(after)?;
+    let start = rest.rfind(before)?;
+    let label = &rest[start + before.len()..];
+
+    let valid = label.starts_with(|c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' '));
+
+    valid.then(|| (line[..start].trim_end().len(), label))
+}
@@ -0,0 +1,113 @@
+use crate::{SyntaxElement, SyntaxKind, SyntaxNode};
+
+use super::{filter_token, Citation, CitationReference, Token};
+
+impl Citation {
+    /// Citation style, including its variant
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Citation};
+    ///
+    /// let cite = Org::parse("[cite/t/b:@doe]").first_node::<Citation>().unwrap();
+    /// assert_eq!(cite.style().unwrap(), "t/b");
+    /// let cite = Org::parse("[cite:@doe]").first_node::<Citation>().unwrap();
+    /// assert!(cite.style().is_none());
+    /// ```
+    pub fn style(&self) -> Option<Token> {
+        self.syntax
+            .children_with_tokens()
+            .skip_while(|e| e.kind() != SyntaxKind::SLASH)
+            .find_map(filter_token(SyntaxKind::TEXT))
+    }
+
+    /// Global prefix
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Citation, SyntaxKind};
+    ///
+    /// let cite = Org::parse("[cite:see *also*; @doe]").first_node::<Citation>().unwrap();
+    /// let prefix = cite.prefix().collect::<Vec<_>>();
+    /// assert_eq!(prefix[1].kind(), SyntaxKind::BOLD);
+    /// assert_eq!(cite.prefix_raw(), "see *also*");
+    /// ```
+    pub fn prefix(&self) -> impl Iterator<Item = SyntaxElement> {
+        affix(&self.syntax, SyntaxKind::CITATION_PREFIX)
+    }
+
+    /// Global prefix raw string
+    pub fn prefix_raw(&self) -> String {
+        affix(&self.syntax, SyntaxKind::CITATION_PREFIX)
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    /// Global suffix
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Citation};
+    ///
+    /// let cite = Org::parse("[cite:@doe; @smith; for details]").first_node::<Citation>().unwrap();
+    /// assert_eq!(cite.suffix_raw(), "for details");
+    /// assert_eq!(cite.references().count(), 2);
+    /// ```
+    pub fn suffix(&self) -> impl Iterator<Item = SyntaxElement> {
+        affix(&self.syntax, SyntaxKind::CITATION_SUFFIX)
+    }
+
+    /// Global suffix raw string
+    pub fn suffix_raw(&self) -> String {
+        affix(&self.syntax, SyntaxKind::CITATION_SUFFIX)
+            .map(|e| e.to_string())
+            .collect()
+    }
+}
+
+impl CitationReference {
+    /// Citation key, without leading `@`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::CitationReference};
+    ///
+    /// let reference = Org::parse("[cite:see @doe2020 p. 3]").first_node::<CitationReference>().unwrap();
+    /// assert_eq!(reference.key(), "doe2020");
+    /// assert_eq!(reference.prefix_raw(), "see ");
+    /// assert_eq!(reference.suffix_raw(), " p. 3");
+    /// ```
+    pub fn key(&self) -> Token {
+        self.syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::TEXT))
+            .expect("citation reference must contains TEXT")
+    }
+
+    /// Key prefix
+    pub fn prefix(&self) -> impl Iterator<Item = SyntaxElement> {
+        affix(&self.syntax, SyntaxKind::CITATION_PREFIX)
+    }
+
+    /// Key prefix raw string
+    pub fn prefix_raw(&self) -> String {
+        affix(&self.syntax, SyntaxKind::CITATION_PREFIX)
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    /// Key suffix
+    pub fn suffix(&self) -> impl Iterator<Item = SyntaxElement> {
+        affix(&self.syntax, SyntaxKind::CITATION_SUFFIX)
+    }
+
+    /// Key suffix raw string
+    pub fn suffix_raw(&self) -> String {
+        affix(&self.syntax, SyntaxKind::CITATION_SUFFIX)
+            .map(|e| e.to_string())
+            .collect()
+    }
+}
+
+fn affix(node: &SyntaxNode, kind: SyntaxKind) -> impl Iterator<Item = SyntaxElement> {
+    node.children()
+        .find(|n| n.kind() == kind)
+        .into_iter()
+        .flat_map(|n| n.children_with_tokens())
+}
@@ -1,8 +1,60 @@
-use rowan::ast::support;
+use rowan::ast::{support, AstNode};
 
 use crate::{ast::Token, SyntaxKind};
 
-use super::{Clock, Timestamp};
+use super::{Clock, Headline, Timestamp, TimestampDate, TimestampTime};
+
+/// Time window to sum clocked time within
+///
+/// Clocks straddling the boundaries only count the part inside the window.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ClockWindow {
+    /// Start of the window, unbounded if `None`
+    pub start: Option<(TimestampDate, TimestampTime)>,
+    /// End of the window, exclusive, unbounded if `None`
+    pub end: Option<(TimestampDate, TimestampTime)>,
+}
+
+impl ClockWindow {
+    /// Whole days from `start` to `end`, inclusive
+    pub fn days(start: TimestampDate, end: TimestampDate) -> ClockWindow {
+        let midnight = TimestampTime::new(0, 0);
+        ClockWindow {
+            start: Some((start, midnight)),
+            end: Some((end.add_days(1), midnight)),
+        }
+    }
+
+    /// The given day
+    pub fn today(today: TimestampDate) -> ClockWindow {
+        ClockWindow::days(today, today)
+    }
+
+    /// The week containing given day, starting on Monday
+    pub fn week(today: TimestampDate) -> ClockWindow {
+        let monday = today.add_days(-(((today.weekday() + 6) % 7) as i64));
+        ClockWindow::days(monday, monday.add_days(6))
+    }
+
+    /// Minutes of given interval inside this window
+    fn clip(&self, start: i64, end: i64) -> i64 {
+        let start = self.start.map_or(start, |(d, t)| start.max(minutes(d, t)));
+        let end = self.end.map_or(end, |(d, t)| end.min(minutes(d, t)));
+        (end - start).max(0)
+    }
+}
+
+/// Time clocked in a headline and its subtree, in minutes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClockedTime {
+    /// Minutes clocked in the headline itself
+    pub own: u64,
+    /// Minutes clocked in the headline and all its descendants
+    pub total: u64,
+    /// Running clocks of the headline and its descendants started before
+    /// the end of window, they are not counted in `own` or `total`
+    pub running: Vec<Clock>,
+}
 
 impl Clock {
     pub fn value(&self) -> Option<Timestamp> {
@@ -55,4 +107,109 @@ impl Clock {
     pub fn is_running(&self) -> bool {
         !self.is_closed()
     }
+
+    /// Minutes between start and end of this clock, computed from its
+    /// timestamps rather than the duration text
+    ///
+    /// Returns `None` if this clock has no end.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Clock};
+    ///
+    /// let clock = Org::parse("CLOCK: [2024-05-01 Wed 23:10]--[2024-05-02 Thu 00:40] =>  0:10")
+    ///     .first_node::<Clock>()
+    ///     .unwrap();
+    /// assert_eq!(clock.minutes(), Some(90));
+    /// let clock = Org::parse("CLOCK: [2024-05-01 Wed 09:39]").first_node::<Clock>().unwrap();
+    /// assert_eq!(clock.minutes(), None);
+    /// ```
+    pub fn minutes(&self) -> Option<u64> {
+        let (start, end) = self.interval()?;
+        Some((end? - start).max(0) as u64)
+    }
+
+    /// Start and end of this clock in minutes since epoch, end is `None`
+    /// if this clock is still running
+    fn interval(&self) -> Option<(i64, Option<i64>)> {
+        let value = self.value()?.value()?;
+        let midnight = TimestampTime::new(0, 0);
+        let start = minutes(value.start_date, value.start_time.unwrap_or(midnight));
+        let end = match (value.end_date, value.end_time) {
+            (None, None) => None,
+            (date, time) => Some(minutes(
+                date.unwrap_or(value.start_date),
+                time.unwrap_or(midnight),
+            )),
+        };
+        Some((start, end))
+    }
+}
+
+impl Headline {
+    /// Sums time clocked in this headline and its subtree within a window
+    ///
+    /// Durations are computed from clock timestamps, so stale `=> H:MM`
+    /// texts are ignored.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{ClockWindow, Headline, TimestampDate}};
+    ///
+    /// let org = Org::parse(r#"* Project
+    /// :LOGBOOK:
+    /// CLOCK: [2024-05-01 Wed 09:00]--[2024-05-01 Wed 10:30] =>  0:15
+    /// :END:
+    /// ** Task
+    /// :LOGBOOK:
+    /// CLOCK: [2024-05-01 Wed 23:00]--[2024-05-02 Thu 01:00] =>  2:00
+    /// CLOCK: [2024-05-02 Thu 14:00]
+    /// :END:
+    /// "#);
+    /// let project = org.first_node::<Headline>().unwrap();
+    ///
+    /// let time = project.clocked_time(&ClockWindow::default());
+    /// assert_eq!((time.own, time.total, time.running.len()), (90, 210, 1));
+    ///
+    /// // the clock crossing midnight is clipped
+    /// let time = project.clocked_time(&ClockWindow::today(TimestampDate::new(2024, 5, 2)));
+    /// assert_eq!((time.own, time.total, time.running.len()), (0, 60, 1));
+    ///
+    /// // running clocks are reported in windows after their start
+    /// let time = project.clocked_time(&ClockWindow::week(TimestampDate::new(2024, 5, 8)));
+    /// assert_eq!((time.own, time.total, time.running.len()), (0, 0, 1));
+    /// let time = project.clocked_time(&ClockWindow::week(TimestampDate::new(2024, 4, 24)));
+    /// assert_eq!((time.own, time.total, time.running.len()), (0, 0, 0));
+    /// ```
+    pub fn clocked_time(&self, window: &ClockWindow) -> ClockedTime {
+        let mut time = ClockedTime::default();
+
+        for headline in self.syntax.descendants().filter_map(Headline::cast) {
+            let is_self = headline == *self;
+            for clock in headline.clocks() {
+                let Some((start, end)) = clock.interval() else {
+                    continue;
+                };
+                match end {
+                    Some(end) => {
+                        let minutes = window.clip(start, end) as u64;
+                        time.total += minutes;
+                        if is_self {
+                            time.own += minutes;
+                        }
+                    }
+                    None => {
+                        if window.end.is_none_or(|(d, t)| start < minutes(d, t)) {
+                            time.running.push(clock);
+                        }
+                    }
+                }
+            }
+        }
+
+        time
+    }
+}
+
+/// Minutes since epoch
+fn minutes(date: TimestampDate, time: TimestampTime) -> i64 {
+    date.to_days() * 1440 + time.minutes()
 }
@@ -0,0 +1,273 @@
+use rowan::ast::AstNode;
+
+use super::{CheckboxState, Cookie, Headline, ListItem};
+use crate::{
+    syntax::{SyntaxKind, SyntaxNode},
+    Org,
+};
+
+/// Number of done and total entries counted by a statistics cookie
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct CookieStatistics {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl CookieStatistics {
+    /// Percentage of done entries, rounded down like org-mode does
+    pub fn percent(&self) -> usize {
+        self.done * 100 / self.total.max(1)
+    }
+}
+
+impl Cookie {
+    /// Returns `true` if this cookie is a percent cookie, e.g. `[40%]`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Cookie};
+    ///
+    /// assert!(Org::parse("- a [%]").first_node::<Cookie>().unwrap().is_percent());
+    /// assert!(!Org::parse("- a [1/2]").first_node::<Cookie>().unwrap().is_percent());
+    /// ```
+    pub fn is_percent(&self) -> bool {
+        self.syntax
+            .children_with_tokens()
+            .any(|e| e.kind() == SyntaxKind::PERCENT)
+    }
+
+    /// Computes the statistics this cookie should display
+    ///
+    /// A cookie in a list item counts the checkboxes of its child items. A
+    /// cookie in a headline title counts the TODO/DONE child headlines, or the
+    /// checkboxes of its section if it contains any. The `COOKIE_DATA` property
+    /// of the headline can force either with `todo` or `checkbox`, and includes
+    /// all descendants with `recursive`.
+    ///
+    /// Returns `None` if the cookie is neither in a list item nor in a headline
+    /// title.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Cookie, CookieStatistics}, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse(r#"* TODO Tasks [/]
+    /// ** DONE a
+    /// ** TODO b
+    /// ** c
+    /// * Shopping [%]
+    /// - [X] milk
+    /// - [ ] eggs [1/2]
+    ///   - [X] white
+    ///   - [ ] brown
+    /// "#);
+    /// let cookies: Vec<_> = org
+    ///     .document()
+    ///     .syntax()
+    ///     .descendants()
+    ///     .filter_map(Cookie::cast)
+    ///     .map(|c| c.statistics().unwrap())
+    ///     .collect();
+    /// assert_eq!(
+    ///     cookies,
+    ///     [
+    ///         CookieStatistics { done: 1, total: 2 },
+    ///         CookieStatistics { done: 1, total: 2 },
+    ///         CookieStatistics { done: 1, total: 2 },
+    ///     ]
+    /// );
+    ///
+    /// let org = Org::parse(r#"* Shopping [/]
+    /// :PROPERTIES:
+    /// :COOKIE_DATA: checkbox recursive
+    /// :END:
+    /// - [X] milk
+    /// - eggs
+    ///   - [X] white
+    ///   - [ ] brown
+    /// "#);
+    /// let cookie = org.first_node::<Cookie>().unwrap();
+    /// assert_eq!(cookie.statistics(), Some(CookieStatistics { done: 2, total: 3 }));
+    /// ```
+    pub fn statistics(&self) -> Option<CookieStatistics> {
+        let owner = self.owner()?;
+        let data = cookie_data(&owner);
+        let recursive = data.contains(&"recursive");
+
+        if owner.kind() == SyntaxKind::LIST_ITEM {
+            return Some(checkbox_statistics(
+                owner
+                    .children()
+                    .filter(|n| n.kind() == SyntaxKind::LIST_ITEM_CONTENT),
+                recursive,
+            ));
+        }
+
+        let headline = Headline::cast(owner)?;
+        let section = headline.section();
+        let has_checkbox = || {
+            section.iter().any(|s| {
+                s.syntax
+                    .descendants()
+                    .filter_map(ListItem::cast)
+                    .any(|item| item.checkbox().is_some())
+            })
+        };
+        if !data.contains(&"todo") && (data.contains(&"checkbox") || has_checkbox()) {
+            return Some(checkbox_statistics(
+                section.map(|s| s.syntax).into_iter(),
+                recursive,
+            ));
+        }
+
+        Some(todo_statistics(&headline, recursive))
+    }
+
+    /// List item or headline whose entries this cookie counts
+    pub(crate) fn owner(&self) -> Option<SyntaxNode> {
+        for ancestor in self.syntax.ancestors().skip(1) {
+            match ancestor.kind() {
+                SyntaxKind::LIST_ITEM => return Some(ancestor),
+                SyntaxKind::HEADLINE_TITLE => return ancestor.parent(),
+                SyntaxKind::SECTION | SyntaxKind::INLINETASK | SyntaxKind::DOCUMENT => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Returns the text this cookie should have, keeping its style
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Cookie};
+    ///
+    /// let org = Org::parse("- [ ] a [0%]\n  - [X] b\n  - [ ] c\n  - [X] d");
+    /// let cookie = org.first_node::<Cookie>().unwrap();
+    /// assert_eq!(cookie.updated().unwrap(), "[66%]");
+    /// ```
+    pub fn updated(&self) -> Option<String> {
+        let statistics = self.statistics()?;
+        Some(if self.is_percent() {
+            format!("[{}%]", statistics.percent())
+        } else {
+            format!("[{}/{}]", statistics.done, statistics.total)
+        })
+    }
+}
+
+impl Org {
+    /// Updates every stale statistics cookie in the document
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("* TODO a [3/3]\n** DONE b\n** TODO c\n- [X] d [50%]\n  - [X] e\n");
+    /// org.update_cookies();
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* TODO a [1/2]\n** DONE b\n** TODO c\n- [X] d [100%]\n  - [X] e\n"
+    /// );
+    /// ```
+    pub fn update_cookies(&mut self) {
+        self.update_cookies_where(|_| true);
+    }
+
+    /// Updates stale statistics cookies matching given predicate
+    pub(crate) fn update_cookies_where(&mut self, predicate: impl Fn(&Cookie) -> bool) {
+        let edits: Vec<_> = self
+            .document()
+            .syntax
+            .descendants()
+            .filter_map(Cookie::cast)
+            .filter(|cookie| predicate(cookie))
+            .filter_map(|cookie| {
+                let updated = cookie.updated()?;
+                (updated != cookie.raw()).then(|| (cookie.text_range(), updated))
+            })
+            .collect();
+
+        // replaces from the end, so ranges of remaining edits are still valid
+        for (range, updated) in edits.into_iter().rev() {
+            self.replace_range(range, updated);
+        }
+    }
+}
+
+/// Words of the `COOKIE_DATA` property of the headline containing given node
+fn cookie_data(node: &SyntaxNode) -> Vec<&'static str> {
+    let Some(headline) = node.ancestors().find_map(Headline::cast) else {
+        return vec![];
+    };
+    let Some(value) = headline
+        .properties()
+        .and_then(|drawer| drawer.get("COOKIE_DATA"))
+    else {
+        return vec![];
+    };
+    ["todo", "checkbox", "recursive"]
+        .into_iter()
+        .filter(|word| value.split_whitespace().any(|w| w == *word))
+        .collect()
+}
+
+/// Counts checkboxes of the list items under given nodes
+///
+/// Only items of the lists directly under nodes are counted, unless
+/// `recursive` is true.
+fn checkbox_statistics(
+    parents: impl Iterator<Item = SyntaxNode>,
+    recursive: bool,
+) -> CookieStatistics {
+    let mut statistics = CookieStatistics::default();
+
+    let mut count = |item: ListItem| {
+        if let Some(state) = item.checkbox_state() {
+            statistics.total += 1;
+            if state == CheckboxState::On {
+                statistics.done += 1;
+            }
+        }
+    };
+
+    for parent in parents {
+        if recursive {
+            parent
+                .descendants()
+                .filter_map(ListItem::cast)
+                .for_each(&mut count);
+        } else {
+            parent
+                .children()
+                .filter(|n| n.kind() == SyntaxKind::LIST)
+                .flat_map(|list| list.children().filter_map(ListItem::cast))
+                .for_each(&mut count);
+        }
+    }
+
+    statistics
+}
+
+/// Counts child headlines with a TODO keyword
+fn todo_statistics(headline: &Headline, recursive: bool) -> CookieStatistics {
+    let mut statistics = CookieStatistics::default();
+
+    let headlines: Vec<Headline> = if recursive {
+        headline
+            .syntax
+            .descendants()
+            .skip(1)
+            .filter_map(Headline::cast)
+            .collect()
+    } else {
+        headline.headlines().collect()
+    };
+
+    for headline in headlines {
+        if headline.todo_keyword().is_some() {
+            statistics.total += 1;
+            if headline.is_done() {
+                statistics.done += 1;
+            }
+        }
+    }
+
+    statistics
+}
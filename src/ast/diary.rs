@@ -0,0 +1,363 @@
+use std::fmt;
+
+use super::{filter_token, Timestamp, TimestampDate};
+use crate::syntax::SyntaxKind;
+
+/// Order of month, day and year arguments in diary sexps
+///
+/// Equivalent to `calendar-date-style`
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum CalendarDateStyle {
+    /// month, day, year
+    #[default]
+    American,
+    /// day, month, year
+    European,
+    /// year, month, day
+    Iso,
+}
+
+/// A diary sexp, like `(diary-float t 4 2)`
+///
+/// Month, day or year set to `None` matches any value, e.g. `t` in
+/// `(diary-date t 1 t)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiarySexp {
+    /// `(diary-anniversary month day &optional year)`
+    Anniversary {
+        month: u32,
+        day: u32,
+        year: Option<i32>,
+    },
+    /// `(diary-block m1 d1 y1 m2 d2 y2)`
+    Block {
+        start: TimestampDate,
+        end: TimestampDate,
+    },
+    /// `(diary-float month dayname n &optional day)`
+    Float {
+        month: Option<u32>,
+        /// 0 being Sunday
+        weekday: u32,
+        /// Counts from the end of month if negative
+        n: i32,
+        day: Option<u32>,
+    },
+    /// `(diary-cyclic n month day year)`
+    Cyclic { interval: u32, start: TimestampDate },
+    /// `(diary-date month day year)`
+    Date {
+        month: Option<u32>,
+        day: Option<u32>,
+        year: Option<i32>,
+    },
+    /// `(org-class y1 m1 d1 y2 m2 d2 dayname &rest skip-weeks)`
+    Class {
+        start: TimestampDate,
+        end: TimestampDate,
+        weekday: u32,
+        /// ISO week numbers without class
+        skip_weeks: Vec<u32>,
+    },
+}
+
+/// Error returned by [`DiarySexp::parse`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiaryError {
+    /// Not a single function call with atom arguments
+    Syntax(String),
+    /// Function isn't one of the supported diary functions
+    UnsupportedFunction(String),
+    /// Function is called with wrong number or kind of arguments
+    InvalidArgument(String),
+}
+
+impl fmt::Display for DiaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiaryError::Syntax(sexp) => write!(f, "invalid diary sexp {sexp:?}"),
+            DiaryError::UnsupportedFunction(name) => {
+                write!(f, "unsupported diary function {name}")
+            }
+            DiaryError::InvalidArgument(name) => write!(f, "invalid arguments to {name}"),
+        }
+    }
+}
+
+impl std::error::Error for DiaryError {}
+
+/// Argument of diary function
+#[derive(Debug, Clone, Copy)]
+enum Arg {
+    Int(i64),
+    True,
+}
+
+impl DiarySexp {
+    /// Parses a diary sexp, with or without the surrounding parentheses
+    ///
+    /// ```rust
+    /// use orgize::ast::{CalendarDateStyle, DiaryError, DiarySexp};
+    ///
+    /// let sexp = DiarySexp::parse("(diary-float t 4 2)", CalendarDateStyle::American).unwrap();
+    /// assert_eq!(sexp, DiarySexp::Float { month: None, weekday: 4, n: 2, day: None });
+    ///
+    /// let sexp = DiarySexp::parse("diary-anniversary 31 10 1948", CalendarDateStyle::European).unwrap();
+    /// assert_eq!(sexp, DiarySexp::Anniversary { month: 10, day: 31, year: Some(1948) });
+    ///
+    /// assert_eq!(
+    ///     DiarySexp::parse("(diary-lunar-phases)", CalendarDateStyle::American),
+    ///     Err(DiaryError::UnsupportedFunction("diary-lunar-phases".into()))
+    /// );
+    /// ```
+    pub fn parse(sexp: &str, style: CalendarDateStyle) -> Result<DiarySexp, DiaryError> {
+        let trimmed = sexp.trim();
+        let inner = trimmed
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(trimmed);
+
+        if inner.contains(['(', ')', '\'', '"']) {
+            return Err(DiaryError::Syntax(sexp.into()));
+        }
+
+        let mut words = inner.split_whitespace();
+        let name = words
+            .next()
+            .ok_or_else(|| DiaryError::Syntax(sexp.into()))?;
+        let invalid = || DiaryError::InvalidArgument(name.into());
+
+        let args = words
+            .map(|word| match word {
+                "t" => Ok(Arg::True),
+                _ => word.parse().map(Arg::Int).map_err(|_| invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let int = |idx: usize| match args.get(idx) {
+            Some(Arg::Int(i)) => Ok(*i),
+            _ => Err(invalid()),
+        };
+        let date_at = |idx: usize| -> Result<TimestampDate, DiaryError> {
+            let (month, day, year) = style.order(int(idx)?, int(idx + 1)?, int(idx + 2)?);
+            date(year, month, day).ok_or_else(invalid)
+        };
+
+        let sexp = match (name, args.len()) {
+            ("diary-anniversary", 2) if style != CalendarDateStyle::Iso => {
+                let (month, day, _) = style.order(int(0)?, int(1)?, 0);
+                DiarySexp::Anniversary {
+                    month: month_of(month).ok_or_else(invalid)?,
+                    day: day_of(day).ok_or_else(invalid)?,
+                    year: None,
+                }
+            }
+            ("diary-anniversary", 3) => {
+                let date = date_at(0)?;
+                DiarySexp::Anniversary {
+                    month: date.month,
+                    day: date.day,
+                    year: Some(date.year),
+                }
+            }
+            ("diary-block", 6) => DiarySexp::Block {
+                start: date_at(0)?,
+                end: date_at(3)?,
+            },
+            ("diary-float", 3 | 4) => DiarySexp::Float {
+                month: match args[0] {
+                    Arg::True => None,
+                    Arg::Int(month) => Some(month_of(month).ok_or_else(invalid)?),
+                },
+                weekday: weekday_of(int(1)?).ok_or_else(invalid)?,
+                n: i32::try_from(int(2)?)
+                    .ok()
+                    .filter(|n| *n != 0)
+                    .ok_or_else(invalid)?,
+                day: match args.get(3) {
+                    None => None,
+                    Some(_) => Some(day_of(int(3)?).ok_or_else(invalid)?),
+                },
+            },
+            ("diary-cyclic", 4) => DiarySexp::Cyclic {
+                interval: u32::try_from(int(0)?)
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(invalid)?,
+                start: date_at(1)?,
+            },
+            ("diary-date", 3) => {
+                let any = |arg: Arg| match arg {
+                    Arg::True => None,
+                    Arg::Int(i) => Some(i),
+                };
+                let (month, day, year) = style.order(any(args[0]), any(args[1]), any(args[2]));
+                DiarySexp::Date {
+                    month: month.map(|m| month_of(m).ok_or_else(invalid)).transpose()?,
+                    day: day.map(|d| day_of(d).ok_or_else(invalid)).transpose()?,
+                    year: year
+                        .map(|y| i32::try_from(y).map_err(|_| invalid()))
+                        .transpose()?,
+                }
+            }
+            // org-class always takes year, month and day
+            ("org-class", 7..) => DiarySexp::Class {
+                start: date(int(0)?, int(1)?, int(2)?).ok_or_else(invalid)?,
+                end: date(int(3)?, int(4)?, int(5)?).ok_or_else(invalid)?,
+                weekday: weekday_of(int(6)?).ok_or_else(invalid)?,
+                skip_weeks: (7..args.len())
+                    .map(|idx| u32::try_from(int(idx)?).map_err(|_| invalid()))
+                    .collect::<Result<_, _>>()?,
+            },
+            (
+                "diary-anniversary" | "diary-block" | "diary-float" | "diary-cyclic" | "diary-date"
+                | "org-class",
+                _,
+            ) => return Err(invalid()),
+            _ => return Err(DiaryError::UnsupportedFunction(name.into())),
+        };
+
+        Ok(sexp)
+    }
+
+    /// Returns `true` if this sexp matches given date
+    ///
+    /// ```rust
+    /// use orgize::ast::{CalendarDateStyle, DiarySexp, TimestampDate};
+    ///
+    /// let parse = |s| DiarySexp::parse(s, CalendarDateStyle::American).unwrap();
+    ///
+    /// // second Thursday of every month
+    /// let sexp = parse("diary-float t 4 2");
+    /// assert!(sexp.matches(TimestampDate::new(2024, 5, 9)));
+    /// assert!(!sexp.matches(TimestampDate::new(2024, 5, 2)));
+    ///
+    /// // last Monday of May
+    /// let sexp = parse("diary-float 5 1 -1");
+    /// assert!(sexp.matches(TimestampDate::new(2024, 5, 27)));
+    ///
+    /// // every ten days from 2024-05-01
+    /// let sexp = parse("diary-cyclic 10 5 1 2024");
+    /// assert!(sexp.matches(TimestampDate::new(2024, 5, 21)));
+    /// assert!(!sexp.matches(TimestampDate::new(2024, 4, 21)));
+    ///
+    /// // Tuesdays from May to June, except the week 20
+    /// let sexp = parse("org-class 2024 5 1 2024 6 30 2 20");
+    /// assert!(sexp.matches(TimestampDate::new(2024, 5, 7)));
+    /// assert!(!sexp.matches(TimestampDate::new(2024, 5, 14)));
+    /// ```
+    pub fn matches(&self, date: TimestampDate) -> bool {
+        match self {
+            DiarySexp::Anniversary { month, day, year } => {
+                let (month, day) = if (*month, *day) == (2, 29) && !is_leap_year(date.year) {
+                    (3, 1)
+                } else {
+                    (*month, *day)
+                };
+                date.month == month && date.day == day && year.is_none_or(|y| date.year > y)
+            }
+            DiarySexp::Block { start, end } => start <= &date && &date <= end,
+            DiarySexp::Float {
+                month,
+                weekday,
+                n,
+                day,
+            } => {
+                if month.is_some_and(|m| m != date.month) || date.weekday() != *weekday {
+                    return false;
+                }
+                if *n > 0 {
+                    // nth weekday on or after day
+                    let first = day.unwrap_or(1);
+                    date.day >= first && (date.day - first) / 7 + 1 == *n as u32
+                } else {
+                    // nth weekday on or before day, counting backwards
+                    let last = day.unwrap_or_else(|| {
+                        let first = TimestampDate::new(date.year, date.month, 1);
+                        first.add_months(1).add_days(-1).day
+                    });
+                    date.day <= last && (last - date.day) / 7 + 1 == n.unsigned_abs()
+                }
+            }
+            DiarySexp::Cyclic { interval, start } => {
+                let diff = date.to_days() - start.to_days();
+                diff >= 0 && diff % *interval as i64 == 0
+            }
+            DiarySexp::Date { month, day, year } => {
+                month.is_none_or(|m| m == date.month)
+                    && day.is_none_or(|d| d == date.day)
+                    && year.is_none_or(|y| y == date.year)
+            }
+            DiarySexp::Class {
+                start,
+                end,
+                weekday,
+                skip_weeks,
+            } => {
+                start <= &date
+                    && &date <= end
+                    && date.weekday() == *weekday
+                    && !skip_weeks.contains(&date.iso_week())
+            }
+        }
+    }
+}
+
+impl CalendarDateStyle {
+    /// Reorders arguments to month, day and year
+    fn order<T>(self, a: T, b: T, c: T) -> (T, T, T) {
+        match self {
+            CalendarDateStyle::American => (a, b, c),
+            CalendarDateStyle::European => (b, a, c),
+            CalendarDateStyle::Iso => (b, c, a),
+        }
+    }
+}
+
+impl Timestamp {
+    /// Parses the sexp of this diary timestamp, returns `None` if it's not a
+    /// diary timestamp
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{CalendarDateStyle, Timestamp, TimestampDate}};
+    ///
+    /// let ts = Org::parse("<%%(diary-block 5 1 2024 5 3 2024)>").first_node::<Timestamp>().unwrap();
+    /// let sexp = ts.diary_sexp(CalendarDateStyle::American).unwrap().unwrap();
+    /// assert!(sexp.matches(TimestampDate::new(2024, 5, 2)));
+    /// ```
+    pub fn diary_sexp(&self, style: CalendarDateStyle) -> Option<Result<DiarySexp, DiaryError>> {
+        if !self.is_diary() {
+            return None;
+        }
+        let text = self
+            .syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::TEXT))?;
+        Some(DiarySexp::parse(&text, style))
+    }
+}
+
+fn date(year: i64, month: i64, day: i64) -> Option<TimestampDate> {
+    let year = i32::try_from(year).ok()?;
+    let month = month_of(month)?;
+    let day = day_of(day)?;
+    let date = TimestampDate::new(year, month, day);
+    // rejects days past the end of month
+    (date.add_days(0) == date).then_some(date)
+}
+
+fn month_of(month: i64) -> Option<u32> {
+    (1..=12).contains(&month).then_some(month as u32)
+}
+
+fn day_of(day: i64) -> Option<u32> {
+    (1..=31).contains(&day).then_some(day as u32)
+}
+
+fn weekday_of(weekday: i64) -> Option<u32> {
+    (0..=6).contains(&weekday).then_some(weekday as u32)
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
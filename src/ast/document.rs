@@ -1,6 +1,10 @@
 use rowan::ast::AstNode;
 
-use crate::Org;
+use crate::{
+    config::{StartupOptions, TodoSequence},
+    export::{CiteExport, ExportOptions},
+    Org,
+};
 
 use super::{Document, Keyword, PropertyDrawer};
 
@@ -56,6 +60,171 @@ impl Document {
             })
     }
 
+    /// Returns todo keywords sequences declared by top-level `#+TODO`,
+    /// `#+SEQ_TODO` and `#+TYP_TODO`
+    ///
+    /// ```rust
+    /// use orgize::{Org, config::TodoSequenceType};
+    ///
+    /// let org = Org::parse(r#"#+TODO: NEXT(n) WAIT(w@/!) | DONE(d!) CANCELLED(c@)
+    /// #+TYP_TODO: Fred Sara | Lucy
+    /// * NEXT headline"#);
+    ///
+    /// let sequences = org.document().todo_sequences();
+    /// assert_eq!(sequences.len(), 2);
+    /// assert_eq!(sequences[0].ty, TodoSequenceType::Sequence);
+    /// assert_eq!(sequences[0].todo[1].name, "WAIT");
+    /// assert_eq!(sequences[0].todo[1].key, Some('w'));
+    /// assert_eq!(sequences[1].ty, TodoSequenceType::Type);
+    /// assert_eq!(sequences[1].done[0].name, "Lucy");
+    /// ```
+    pub fn todo_sequences(&self) -> Vec<TodoSequence> {
+        self.keywords()
+            .filter_map(|kw| TodoSequence::from_keyword(&kw))
+            .collect()
+    }
+
+    /// Returns link abbreviations defined by top-level `#+LINK`
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+LINK: gh https://github.com/%s\n#+LINK: wiki https://en.wikipedia.org/wiki/");
+    /// assert_eq!(
+    ///     org.document().link_abbreviations(),
+    ///     vec![
+    ///         ("gh".into(), "https://github.com/%s".into()),
+    ///         ("wiki".into(), "https://en.wikipedia.org/wiki/".into())
+    ///     ]
+    /// );
+    /// ```
+    pub fn link_abbreviations(&self) -> Vec<(String, String)> {
+        self.keywords()
+            .filter(|kw| kw.key().eq_ignore_ascii_case("LINK"))
+            .filter_map(|kw| {
+                let value = kw.value();
+                let (key, value) = value.trim().split_once(char::is_whitespace)?;
+                Some((key.to_string(), value.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns macro definitions defined by top-level `#+MACRO`
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+MACRO: color @@html:<span style=\"color: $1\">$2</span>@@\n#+MACRO: empty");
+    /// assert_eq!(
+    ///     org.document().macro_definitions(),
+    ///     vec![
+    ///         ("color".into(), "@@html:<span style=\"color: $1\">$2</span>@@".into()),
+    ///         ("empty".into(), "".into())
+    ///     ]
+    /// );
+    /// ```
+    pub fn macro_definitions(&self) -> Vec<(String, String)> {
+        self.keywords()
+            .filter(|kw| kw.key().eq_ignore_ascii_case("MACRO"))
+            .filter_map(|kw| {
+                let value = kw.value();
+                let value = value.trim();
+                if value.is_empty() {
+                    return None;
+                }
+                let (name, template) = value.split_once(char::is_whitespace).unwrap_or((value, ""));
+                Some((name.to_string(), template.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns export options parsed from top-level `#+OPTIONS`
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::DepthOption};
+    ///
+    /// let org = Org::parse("#+OPTIONS: toc:nil num:2\n#+OPTIONS: H:2 <:nil");
+    /// let options = org.document().export_options();
+    /// assert_eq!(options.toc, DepthOption::Nil);
+    /// assert_eq!(options.section_numbers, DepthOption::Level(2));
+    /// assert_eq!(options.headline_levels, 2);
+    /// assert!(!options.timestamps);
+    /// assert!(options.footnotes);
+    /// ```
+    pub fn export_options(&self) -> ExportOptions {
+        let mut options = ExportOptions::default();
+        for kw in self.keywords() {
+            if kw.key().eq_ignore_ascii_case("OPTIONS") {
+                options.apply(&kw.value());
+            }
+        }
+        options
+    }
+
+    /// Returns startup options parsed from top-level `#+STARTUP`
+    ///
+    /// ```rust
+    /// use orgize::{Org, config::{StartupVisibility, TodoLog}};
+    ///
+    /// let org = Org::parse("#+STARTUP: overview logdone\n#+STARTUP: hidestars");
+    /// let options = org.document().startup_options();
+    /// assert_eq!(options.visibility, StartupVisibility::Overview);
+    /// assert_eq!(options.log_done, Some(TodoLog::Timestamp));
+    /// assert!(options.hide_stars);
+    /// ```
+    pub fn startup_options(&self) -> StartupOptions {
+        let mut options = StartupOptions::default();
+        for kw in self.keywords() {
+            if kw.key().eq_ignore_ascii_case("STARTUP") {
+                options.apply(&kw.value());
+            }
+        }
+        options
+    }
+
+    /// Returns bibliography files declared by top-level `#+BIBLIOGRAPHY`
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+BIBLIOGRAPHY: refs.bib\n#+BIBLIOGRAPHY: \"my papers.json\"");
+    /// assert_eq!(org.document().bibliographies(), ["refs.bib", "my papers.json"]);
+    /// ```
+    pub fn bibliographies(&self) -> Vec<String> {
+        self.keywords()
+            .filter(|kw| kw.key().eq_ignore_ascii_case("BIBLIOGRAPHY"))
+            .filter_map(|kw| {
+                let value = kw.value();
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value);
+                (!value.is_empty()).then(|| value.to_string())
+            })
+            .collect()
+    }
+
+    /// Returns citation processor declared by the last top-level `#+CITE_EXPORT`
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+CITE_EXPORT: basic author-year numeric");
+    /// let cite_export = org.document().cite_export().unwrap();
+    /// assert_eq!(cite_export.processor, "basic");
+    /// assert_eq!(cite_export.bibliography_style.as_deref(), Some("author-year"));
+    /// assert_eq!(cite_export.citation_style.as_deref(), Some("numeric"));
+    ///
+    /// assert!(Org::parse("").document().cite_export().is_none());
+    /// ```
+    pub fn cite_export(&self) -> Option<CiteExport> {
+        self.keywords()
+            .filter(|kw| kw.key().eq_ignore_ascii_case("CITE_EXPORT"))
+            .filter_map(|kw| CiteExport::parse(&kw.value()))
+            .last()
+    }
+
     /// Returns top-level properties drawer
     ///
     /// ```rust
@@ -81,6 +250,37 @@ impl Org {
         self.document().title()
     }
 
+    /// Equals to `self.document().todo_sequences()`, see [Document::todo_sequences]
+    pub fn todo_sequences(&self) -> Vec<TodoSequence> {
+        self.document().todo_sequences()
+    }
+
+    /// Returns link abbreviations from parse config and `#+LINK` in document
+    ///
+    /// Abbreviations defined in document come last, so they take precedence
+    /// when resolving link path.
+    ///
+    /// ```rust
+    /// use orgize::{ParseConfig, ast::Link};
+    ///
+    /// let config = ParseConfig {
+    ///     link_abbreviations: vec![("gh".into(), "https://gitlab.com/%s".into())],
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("#+LINK: gh https://github.com/%s\n[[gh:PoiScript/orgize]]");
+    /// let link = org.first_node::<Link>().unwrap();
+    /// assert_eq!(org.link_abbreviations().len(), 2);
+    /// assert_eq!(
+    ///     link.resolved_path(&org.link_abbreviations()),
+    ///     "https://github.com/PoiScript/orgize"
+    /// );
+    /// ```
+    pub fn link_abbreviations(&self) -> Vec<(String, String)> {
+        let mut abbreviations = self.config.link_abbreviations.clone();
+        abbreviations.extend(self.document().link_abbreviations());
+        abbreviations
+    }
+
     /// Equals to `self.document().keywords()`, see [Document::keywords]
     pub fn keywords(&self) -> impl Iterator<Item = Keyword> {
         self.document().keywords()
@@ -32,6 +32,15 @@ const nodes = [
     children: [["headlines", "Headline"]],
     post_blank: true,
   },
+  {
+    struct: "Inlinetask",
+    kind: ["INLINETASK"],
+    first_child: [
+      ["planning", "Planning"],
+      ["properties", "PropertyDrawer"],
+    ],
+    post_blank: true,
+  },
   {
     struct: "PropertyDrawer",
     kind: ["PROPERTY_DRAWER"],
@@ -185,10 +194,23 @@ const nodes = [
     struct: "RadioTarget",
     kind: ["RADIO_TARGET"],
   },
+  {
+    struct: "RadioLink",
+    kind: ["RADIO_LINK"],
+  },
   {
     struct: "FnRef",
     kind: ["FN_REF"],
   },
+  {
+    struct: "Citation",
+    kind: ["CITATION"],
+    children: [["references", "CitationReference"]],
+  },
+  {
+    struct: "CitationReference",
+    kind: ["CITATION_REFERENCE"],
+  },
   {
     struct: "Macros",
     kind: ["MACROS"],
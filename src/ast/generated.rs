@@ -214,6 +214,50 @@ impl Headline {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Inlinetask {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for Inlinetask {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == INLINETASK
+    }
+    fn cast(node: SyntaxNode) -> Option<Inlinetask> {
+        Self::can_cast(node.kind()).then(|| Inlinetask { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Inlinetask {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+    pub fn planning(&self) -> Option<Planning> {
+        support::child(&self.syntax)
+    }
+    pub fn properties(&self) -> Option<PropertyDrawer> {
+        support::child(&self.syntax)
+    }
+    pub fn post_blank(&self) -> usize {
+        super::blank_lines(&self.syntax)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PropertyDrawer {
     pub(crate) syntax: SyntaxNode,
@@ -1647,6 +1691,41 @@ impl RadioTarget {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RadioLink {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for RadioLink {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == RADIO_LINK
+    }
+    fn cast(node: SyntaxNode) -> Option<RadioLink> {
+        Self::can_cast(node.kind()).then(|| RadioLink { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RadioLink {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FnRef {
     pub(crate) syntax: SyntaxNode,
@@ -1682,6 +1761,79 @@ impl FnRef {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Citation {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for Citation {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CITATION
+    }
+    fn cast(node: SyntaxNode) -> Option<Citation> {
+        Self::can_cast(node.kind()).then(|| Citation { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Citation {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+    pub fn references(&self) -> AstChildren<CitationReference> {
+        support::children(&self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CitationReference {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for CitationReference {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CITATION_REFERENCE
+    }
+    fn cast(node: SyntaxNode) -> Option<CitationReference> {
+        Self::can_cast(node.kind()).then(|| CitationReference { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl CitationReference {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Macros {
     pub(crate) syntax: SyntaxNode,
@@ -1,6 +1,6 @@
 use rowan::{ast::AstNode, NodeOrToken};
 
-use crate::{syntax::SyntaxKind, SyntaxElement};
+use crate::{syntax::SyntaxKind, SyntaxElement, SyntaxNode};
 
 use super::{filter_token, Clock, Drawer, Headline, Section, Timestamp, Token};
 
@@ -22,16 +22,7 @@ impl Headline {
     /// assert_eq!(hdl.level(), 6);
     /// ```
     pub fn level(&self) -> usize {
-        self.syntax
-            .children_with_tokens()
-            .find_map(filter_token(SyntaxKind::HEADLINE_STARS))
-            .map_or_else(
-                || {
-                    debug_assert!(false, "headline must contains HEADLINE_STARS");
-                    0
-                },
-                |stars| stars.len(),
-            )
+        level(&self.syntax)
     }
 
     /// ```rust
@@ -41,17 +32,7 @@ impl Headline {
     /// assert_eq!(hdl.todo_keyword().unwrap(), "TODO");
     /// ```
     pub fn todo_keyword(&self) -> Option<Token> {
-        self.syntax
-            .children_with_tokens()
-            .find_map(|elem| match elem {
-                NodeOrToken::Token(tk)
-                    if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_TODO
-                        || tk.kind() == SyntaxKind::HEADLINE_KEYWORD_DONE =>
-                {
-                    Some(Token(tk))
-                }
-                _ => None,
-            })
+        todo_keyword(&self.syntax)
     }
 
     /// ```rust
@@ -63,17 +44,7 @@ impl Headline {
     /// assert_eq!(hdl.todo_type().unwrap(), TodoType::Done);
     /// ```
     pub fn todo_type(&self) -> Option<TodoType> {
-        self.syntax
-            .children_with_tokens()
-            .find_map(|elem| match elem {
-                NodeOrToken::Token(tk) if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_TODO => {
-                    Some(TodoType::Todo)
-                }
-                NodeOrToken::Token(tk) if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_DONE => {
-                    Some(TodoType::Done)
-                }
-                _ => None,
-            })
+        todo_type(&self.syntax)
     }
 
     /// ```rust
@@ -113,11 +84,7 @@ impl Headline {
     /// assert_eq!(title[3].to_string(), "/abc/");
     /// ```
     pub fn title(&self) -> impl Iterator<Item = SyntaxElement> {
-        self.syntax
-            .children()
-            .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)
-            .into_iter()
-            .flat_map(|n| n.children_with_tokens())
+        title(&self.syntax)
     }
 
     /// Returns title raw string
@@ -130,11 +97,7 @@ impl Headline {
     /// assert_eq!(title, "abc *abc* /abc/ ");
     /// ```
     pub fn title_raw(&self) -> String {
-        self.syntax
-            .children()
-            .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)
-            .map(|n| n.to_string())
-            .unwrap_or_default()
+        title_raw(&self.syntax)
     }
 
     /// Return `true` if this headline contains a COMMENT keyword
@@ -211,12 +174,7 @@ impl Headline {
     /// assert_eq!(tags_vec("* title :tag:a2%:"), vec!["tag".to_string(), "a2%".to_string()]);
     /// ```
     pub fn tags(&self) -> impl Iterator<Item = Token> {
-        self.syntax
-            .children()
-            .find(|n| n.kind() == SyntaxKind::HEADLINE_TAGS)
-            .into_iter()
-            .flat_map(|t| t.children_with_tokens())
-            .filter_map(filter_token(SyntaxKind::TEXT))
+        tags(&self.syntax)
     }
 
     /// Returns priority text
@@ -232,13 +190,7 @@ impl Headline {
     /// assert_eq!(hdl.priority().unwrap(), "破");
     /// ```
     pub fn priority(&self) -> Option<Token> {
-        self.syntax
-            .children()
-            .find(|n| n.kind() == SyntaxKind::HEADLINE_PRIORITY)
-            .and_then(|n| {
-                n.children_with_tokens()
-                    .find_map(filter_token(SyntaxKind::TEXT))
-            })
+        priority(&self.syntax)
     }
 
     /// Returns an iterator of clock element affiliated with this headline
@@ -274,3 +226,77 @@ impl Headline {
             .flat_map(|x| x.children().filter_map(Clock::cast))
     }
 }
+
+// shared by `Headline` and `Inlinetask`
+
+pub(super) fn level(syntax: &SyntaxNode) -> usize {
+    syntax
+        .children_with_tokens()
+        .find_map(filter_token(SyntaxKind::HEADLINE_STARS))
+        .map_or_else(
+            || {
+                debug_assert!(false, "headline must contains HEADLINE_STARS");
+                0
+            },
+            |stars| stars.len(),
+        )
+}
+
+pub(super) fn todo_keyword(syntax: &SyntaxNode) -> Option<Token> {
+    syntax.children_with_tokens().find_map(|elem| match elem {
+        NodeOrToken::Token(tk)
+            if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_TODO
+                || tk.kind() == SyntaxKind::HEADLINE_KEYWORD_DONE =>
+        {
+            Some(Token(tk))
+        }
+        _ => None,
+    })
+}
+
+pub(super) fn todo_type(syntax: &SyntaxNode) -> Option<TodoType> {
+    syntax.children_with_tokens().find_map(|elem| match elem {
+        NodeOrToken::Token(tk) if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_TODO => {
+            Some(TodoType::Todo)
+        }
+        NodeOrToken::Token(tk) if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_DONE => {
+            Some(TodoType::Done)
+        }
+        _ => None,
+    })
+}
+
+pub(super) fn title(syntax: &SyntaxNode) -> impl Iterator<Item = SyntaxElement> {
+    syntax
+        .children()
+        .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)
+        .into_iter()
+        .flat_map(|n| n.children_with_tokens())
+}
+
+pub(super) fn title_raw(syntax: &SyntaxNode) -> String {
+    syntax
+        .children()
+        .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)
+        .map(|n| n.to_string())
+        .unwrap_or_default()
+}
+
+pub(super) fn tags(syntax: &SyntaxNode) -> impl Iterator<Item = Token> {
+    syntax
+        .children()
+        .find(|n| n.kind() == SyntaxKind::HEADLINE_TAGS)
+        .into_iter()
+        .flat_map(|t| t.children_with_tokens())
+        .filter_map(filter_token(SyntaxKind::TEXT))
+}
+
+pub(super) fn priority(syntax: &SyntaxNode) -> Option<Token> {
+    syntax
+        .children()
+        .find(|n| n.kind() == SyntaxKind::HEADLINE_PRIORITY)
+        .and_then(|n| {
+            n.children_with_tokens()
+                .find_map(filter_token(SyntaxKind::TEXT))
+        })
+}
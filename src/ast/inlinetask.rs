@@ -0,0 +1,153 @@
+use crate::{syntax::SyntaxKind, SyntaxElement};
+
+use super::{headline, Inlinetask, Timestamp, TodoType, Token};
+
+impl Inlinetask {
+    /// Return level of this inlinetask
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** task").first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.level(), 15);
+    /// ```
+    pub fn level(&self) -> usize {
+        headline::level(&self.syntax)
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** TODO a").first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.todo_keyword().unwrap(), "TODO");
+    /// ```
+    pub fn todo_keyword(&self) -> Option<Token> {
+        headline::todo_keyword(&self.syntax)
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::{Inlinetask, TodoType}};
+    ///
+    /// let task = Org::parse("*************** TODO a").first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.todo_type().unwrap(), TodoType::Todo);
+    /// let task = Org::parse("*************** DONE a").first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.todo_type().unwrap(), TodoType::Done);
+    /// ```
+    pub fn todo_type(&self) -> Option<TodoType> {
+        headline::todo_type(&self.syntax)
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** TODO a").first_node::<Inlinetask>().unwrap();
+    /// assert!(task.is_todo());
+    /// let task = Org::parse("*************** a").first_node::<Inlinetask>().unwrap();
+    /// assert!(!task.is_todo());
+    /// ```
+    pub fn is_todo(&self) -> bool {
+        matches!(self.todo_type(), Some(TodoType::Todo))
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** DONE a").first_node::<Inlinetask>().unwrap();
+    /// assert!(task.is_done());
+    /// let task = Org::parse("*************** a").first_node::<Inlinetask>().unwrap();
+    /// assert!(!task.is_done());
+    /// ```
+    pub fn is_done(&self) -> bool {
+        matches!(self.todo_type(), Some(TodoType::Done))
+    }
+
+    /// Returns parsed title
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask, SyntaxKind};
+    ///
+    /// let task = Org::parse("*************** abc *abc* :tag:").first_node::<Inlinetask>().unwrap();
+    /// let title = task.title().collect::<Vec<_>>();
+    /// assert_eq!(title[1].kind(), SyntaxKind::BOLD);
+    /// assert_eq!(title[1].to_string(), "*abc*");
+    /// ```
+    pub fn title(&self) -> impl Iterator<Item = SyntaxElement> {
+        headline::title(&self.syntax)
+    }
+
+    /// Returns title raw string
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** abc *abc* :tag:").first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.title_raw(), "abc *abc* ");
+    /// ```
+    pub fn title_raw(&self) -> String {
+        headline::title_raw(&self.syntax)
+    }
+
+    /// Returns an iterator of text token in this tags
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** title :tag:a2%:").first_node::<Inlinetask>().unwrap();
+    /// let tags: Vec<_> = task.tags().map(|t| t.to_string()).collect();
+    /// assert_eq!(tags, vec!["tag".to_string(), "a2%".to_string()]);
+    /// ```
+    pub fn tags(&self) -> impl Iterator<Item = Token> {
+        headline::tags(&self.syntax)
+    }
+
+    /// Returns priority text
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** TODO [#A] a").first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.priority().unwrap(), "A");
+    /// ```
+    pub fn priority(&self) -> Option<Token> {
+        headline::priority(&self.syntax)
+    }
+
+    /// Returns `true` if this inlinetask is closed by an `END` line
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let task = Org::parse("*************** a\nb\n*************** END").first_node::<Inlinetask>().unwrap();
+    /// assert!(task.has_end());
+    /// let task = Org::parse("*************** a\nb").first_node::<Inlinetask>().unwrap();
+    /// assert!(!task.has_end());
+    /// ```
+    pub fn has_end(&self) -> bool {
+        self.syntax
+            .children()
+            .any(|n| n.kind() == SyntaxKind::INLINETASK_END)
+    }
+
+    /// Returns this inlinetask's closed timestamp, or `None` if not set.
+    pub fn closed(&self) -> Option<Timestamp> {
+        self.planning().and_then(|planning| planning.closed())
+    }
+
+    /// Returns this inlinetask's scheduled timestamp, or `None` if not set.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Inlinetask};
+    ///
+    /// let org = Org::parse("*************** TODO a\nSCHEDULED: <2019-04-08 Mon>\n*************** END");
+    /// let task = org.first_node::<Inlinetask>().unwrap();
+    /// assert_eq!(task.scheduled().unwrap().day_start().unwrap(), "08");
+    /// ```
+    pub fn scheduled(&self) -> Option<Timestamp> {
+        self.planning().and_then(|planning| planning.scheduled())
+    }
+
+    /// Returns this inlinetask's deadline timestamp, or `None` if not set.
+    pub fn deadline(&self) -> Option<Timestamp> {
+        self.planning().and_then(|planning| planning.deadline())
+    }
+}
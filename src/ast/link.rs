@@ -1,8 +1,19 @@
 use rowan::ast::AstNode;
+use std::fmt::Write as _;
 
 use super::{token, AffiliatedKeyword, Link, Paragraph, Token};
 use crate::{syntax::SyntaxKind, SyntaxElement};
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LinkFormat {
+    /// `[[https://orgmode.org][description]]`
+    Bracket,
+    /// `<https://orgmode.org>`
+    Angle,
+    /// `https://orgmode.org`
+    Plain,
+}
+
 impl Link {
     /// Returns link destination
     ///
@@ -20,6 +31,74 @@ impl Link {
         token(&self.syntax, SyntaxKind::LINK_PATH).expect("link must contains LINK_PATH")
     }
 
+    /// Returns the format of this link
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Link, LinkFormat}};
+    ///
+    /// let link = Org::parse("[[https://orgmode.org]]").first_node::<Link>().unwrap();
+    /// assert_eq!(link.format(), LinkFormat::Bracket);
+    /// let link = Org::parse("<https://orgmode.org>").first_node::<Link>().unwrap();
+    /// assert_eq!(link.format(), LinkFormat::Angle);
+    /// assert_eq!(link.path(), "https://orgmode.org");
+    /// let link = Org::parse("see https://orgmode.org.").first_node::<Link>().unwrap();
+    /// assert_eq!(link.format(), LinkFormat::Plain);
+    /// assert_eq!(link.path(), "https://orgmode.org");
+    /// ```
+    pub fn format(&self) -> LinkFormat {
+        match self.syntax.first_token().map(|t| t.kind()) {
+            Some(SyntaxKind::L_BRACKET2) => LinkFormat::Bracket,
+            Some(SyntaxKind::L_ANGLE) => LinkFormat::Angle,
+            _ => LinkFormat::Plain,
+        }
+    }
+
+    /// Returns link destination, with link abbreviation expanded
+    ///
+    /// `abbreviations` is a list of abbreviation and its replacement, usually
+    /// returned by [`Org::link_abbreviations`](crate::Org::link_abbreviations).
+    /// If multiple abbreviations match, the last one is used.
+    ///
+    /// In replacement, `%s` is replaced by the rest of link, and `%h` is replaced
+    /// by the url-encoded rest of link. Otherwise the rest of link is appended.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link};
+    ///
+    /// let abbreviations = vec![
+    ///     ("gh".to_string(), "https://github.com/%s".to_string()),
+    ///     ("search".to_string(), "https://duckduckgo.com/?q=%h".to_string()),
+    ///     ("wiki".to_string(), "https://en.wikipedia.org/wiki/".to_string()),
+    /// ];
+    ///
+    /// let link = Org::parse("[[gh:PoiScript/orgize]]").first_node::<Link>().unwrap();
+    /// assert_eq!(link.resolved_path(&abbreviations), "https://github.com/PoiScript/orgize");
+    /// let link = Org::parse("[[search:org mode]]").first_node::<Link>().unwrap();
+    /// assert_eq!(link.resolved_path(&abbreviations), "https://duckduckgo.com/?q=org%20mode");
+    /// let link = Org::parse("[[wiki:Org-mode][Org]]").first_node::<Link>().unwrap();
+    /// assert_eq!(link.resolved_path(&abbreviations), "https://en.wikipedia.org/wiki/Org-mode");
+    /// let link = Org::parse("[[https://orgmode.org]]").first_node::<Link>().unwrap();
+    /// assert_eq!(link.resolved_path(&abbreviations), "https://orgmode.org");
+    /// ```
+    pub fn resolved_path(&self, abbreviations: &[(String, String)]) -> String {
+        let path = self.path();
+
+        let (abbrev, tag) = path.split_once(':').unwrap_or((&path, ""));
+
+        let Some((_, replacement)) = abbreviations.iter().rev().find(|(key, _)| key == abbrev)
+        else {
+            return path.to_string();
+        };
+
+        if replacement.contains("%s") {
+            replacement.replace("%s", tag)
+        } else if replacement.contains("%h") {
+            replacement.replace("%h", &url_encode(tag))
+        } else {
+            format!("{replacement}{tag}")
+        }
+    }
+
     /// Returns `true` if link contains description
     ///
     /// ```rust
@@ -119,3 +198,16 @@ impl Link {
         Paragraph::cast(self.syntax.parent()?.clone())?.caption()
     }
 }
+
+/// Percent-encodes all characters except unreserved ones, like `url-hexify-string`
+fn url_encode(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            output.push(b as char);
+        } else {
+            let _ = write!(&mut output, "%{b:02X}");
+        }
+    }
+    output
+}
@@ -1,5 +1,48 @@
-use super::{filter_token, List, ListItem, Token};
-use crate::{syntax::SyntaxKind, SyntaxElement};
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use super::{filter_token, Headline, List, ListItem, Token};
+use crate::{syntax::SyntaxKind, Org, SyntaxElement};
+
+/// State of a list item checkbox
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckboxState {
+    /// `[ ]`
+    Off,
+    /// `[X]`
+    On,
+    /// `[-]`, some of its children are checked
+    Partial,
+}
+
+impl CheckboxState {
+    /// Parses the character between brackets
+    ///
+    /// ```rust
+    /// use orgize::ast::CheckboxState;
+    ///
+    /// assert_eq!(CheckboxState::parse(" "), Some(CheckboxState::Off));
+    /// assert_eq!(CheckboxState::parse("x"), Some(CheckboxState::On));
+    /// assert_eq!(CheckboxState::parse("-"), Some(CheckboxState::Partial));
+    /// assert_eq!(CheckboxState::parse("?"), None);
+    /// ```
+    pub fn parse(s: &str) -> Option<CheckboxState> {
+        match s {
+            " " => Some(CheckboxState::Off),
+            "X" | "x" => Some(CheckboxState::On),
+            "-" => Some(CheckboxState::Partial),
+            _ => None,
+        }
+    }
+
+    /// Character between brackets
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CheckboxState::Off => " ",
+            CheckboxState::On => "X",
+            CheckboxState::Partial => "-",
+        }
+    }
+}
 
 impl List {
     /// Returns `true` if this list is an ordered link
@@ -22,7 +65,10 @@ impl List {
                 debug_assert!(false, "list muts contains LIST_ITEM");
                 false
             },
-            |item| item.bullet().starts_with(|c: char| c.is_ascii_digit()),
+            |item| {
+                item.bullet()
+                    .starts_with(|c: char| c.is_ascii_alphanumeric())
+            },
         )
     }
 
@@ -108,6 +154,85 @@ impl ListItem {
             })
     }
 
+    /// ```rust
+    /// use orgize::{Org, ast::{CheckboxState, ListItem}};
+    ///
+    /// let item = Org::parse("- [-] item 1").first_node::<ListItem>().unwrap();
+    /// assert_eq!(item.checkbox_state(), Some(CheckboxState::Partial));
+    /// let item = Org::parse("- item 2").first_node::<ListItem>().unwrap();
+    /// assert_eq!(item.checkbox_state(), None);
+    /// ```
+    pub fn checkbox_state(&self) -> Option<CheckboxState> {
+        self.checkbox()
+            .and_then(|token| CheckboxState::parse(&token))
+    }
+
+    /// Number this item displays in an ordered list
+    ///
+    /// The first item starts from its bullet, e.g. `3.` or `c)`, and each of
+    /// the following items adds one. A counter like `[@5]` sets the number
+    /// of its item and the ones after it.
+    ///
+    /// Returns `None` if the list is unordered.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::ListItem, rowan::ast::AstNode};
+    ///
+    /// let ordinals = |org: Org| -> Vec<Option<usize>> {
+    ///     org.document()
+    ///         .syntax()
+    ///         .descendants()
+    ///         .filter_map(ListItem::cast)
+    ///         .map(|item| item.ordinal())
+    ///         .collect()
+    /// };
+    ///
+    /// let org = Org::parse("3. a\n1. b\n7) [@10] c\n9. d");
+    /// assert_eq!(ordinals(org), [Some(3), Some(4), Some(10), Some(11)]);
+    ///
+    /// let org = Org::parse("- a\n- [@3] b");
+    /// assert_eq!(ordinals(org), [None, None]);
+    ///
+    /// let config = ParseConfig {
+    ///     list_allow_alphabetical: true,
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("b. a\nc. b\nd. [@e] c");
+    /// assert_eq!(ordinals(org), [Some(2), Some(3), Some(5)]);
+    /// ```
+    pub fn ordinal(&self) -> Option<usize> {
+        let list = self.syntax.parent().and_then(List::cast)?;
+        if !list.is_ordered() {
+            return None;
+        }
+
+        let mut ordinal = 0;
+        for (idx, item) in list.items().enumerate() {
+            ordinal = match item.counter().and_then(|counter| parse_ordinal(&counter)) {
+                Some(counter) => counter,
+                None if idx == 0 => {
+                    let bullet = item.bullet();
+                    parse_ordinal(bullet.trim_end().trim_end_matches(['.', ')'])).unwrap_or(1)
+                }
+                None => ordinal + 1,
+            };
+            if item.syntax == self.syntax {
+                return Some(ordinal);
+            }
+        }
+
+        None
+    }
+
+    /// Direct child items, i.e. items of the lists nested in this item
+    pub fn children(&self) -> impl Iterator<Item = ListItem> {
+        self.syntax
+            .children()
+            .filter(|n| n.kind() == SyntaxKind::LIST_ITEM_CONTENT)
+            .flat_map(|n| n.children().filter_map(List::cast))
+            .flat_map(|list| list.items())
+    }
+
     pub fn counter(&self) -> Option<Token> {
         self.syntax
             .children()
@@ -137,3 +262,166 @@ impl ListItem {
             })
     }
 }
+
+impl Org {
+    /// Toggles the checkbox of given item, returns its new state
+    ///
+    /// Checkboxes of all its descendants are set to the same state, then the
+    /// checkboxes of its ancestors are recomputed: `[X]` if all children are
+    /// checked, `[ ]` if none, `[-]` otherwise. Statistics cookies of the item,
+    /// its ancestors and the enclosing headline are updated as well.
+    ///
+    /// Returns `None` and leaves document untouched if item has no checkbox.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{CheckboxState, ListItem}, rowan::ast::AstNode};
+    ///
+    /// let mut org = Org::parse(r#"* Packing [1/3]
+    /// - [ ] clothes [0/2]
+    ///   - [ ] shirts
+    ///   - [ ] socks
+    /// - [X] passport
+    /// - [ ] charger
+    /// "#);
+    /// let socks = org.document().syntax().descendants().filter_map(ListItem::cast).nth(2).unwrap();
+    /// assert_eq!(org.toggle_checkbox(&socks), Some(CheckboxState::On));
+    /// assert_eq!(org.to_org(), r#"* Packing [1/3]
+    /// - [-] clothes [1/2]
+    ///   - [ ] shirts
+    ///   - [X] socks
+    /// - [X] passport
+    /// - [ ] charger
+    /// "#);
+    ///
+    /// let clothes = org.first_node::<ListItem>().unwrap();
+    /// assert_eq!(org.toggle_checkbox(&clothes), Some(CheckboxState::On));
+    /// assert_eq!(org.to_org(), r#"* Packing [2/3]
+    /// - [X] clothes [2/2]
+    ///   - [X] shirts
+    ///   - [X] socks
+    /// - [X] passport
+    /// - [ ] charger
+    /// "#);
+    /// ```
+    pub fn toggle_checkbox(&mut self, item: &ListItem) -> Option<CheckboxState> {
+        let state = match item.checkbox_state()? {
+            CheckboxState::On => CheckboxState::Off,
+            _ => CheckboxState::On,
+        };
+
+        let edits: Vec<_> = item
+            .syntax
+            .descendants()
+            .filter_map(ListItem::cast)
+            .filter_map(|item| Some((checkbox_range(&item)?, item.checkbox_state()?)))
+            .filter(|(_, old)| *old != state)
+            .map(|(range, _)| range)
+            .collect();
+        // checkboxes are always one character, so ranges stay valid
+        for range in edits {
+            self.replace_range(range, state.as_str());
+        }
+
+        let start = item.start();
+        let mut parent = self.list_item_at(start).and_then(|item| parent_item(&item));
+        while let Some(item) = parent {
+            let offset = item.start();
+            if let (Some(range), Some(old)) = (checkbox_range(&item), item.checkbox_state()) {
+                let states: Vec<_> = item.children().filter_map(|i| i.checkbox_state()).collect();
+                let new = if states.iter().all(|s| *s == CheckboxState::On) {
+                    CheckboxState::On
+                } else if states.iter().all(|s| *s == CheckboxState::Off) {
+                    CheckboxState::Off
+                } else {
+                    CheckboxState::Partial
+                };
+                if !states.is_empty() && new != old {
+                    self.replace_range(range, new.as_str());
+                }
+            }
+            parent = self
+                .list_item_at(offset)
+                .and_then(|item| parent_item(&item));
+        }
+
+        let Some(item) = self.list_item_at(start) else {
+            return Some(state);
+        };
+        let owners: Vec<_> = item
+            .syntax
+            .ancestors()
+            .filter(|n| n.kind() == SyntaxKind::LIST_ITEM || n.kind() == SyntaxKind::HEADLINE)
+            .collect();
+        self.update_cookies_where(|cookie| cookie.owner().is_some_and(|o| owners.contains(&o)));
+
+        Some(state)
+    }
+
+    /// Unchecks every checkbox in given headline and its subtree, then updates
+    /// the statistics cookies in it
+    ///
+    /// This is what org-mode does when a repeating task with the
+    /// `RESET_CHECK_BOXES` property is marked as done.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* TODO Review [%]\n- [X] a\n- [-] b\n  - [X] c\n  - [ ] d\n* [X] e");
+    /// let headline = org.first_node::<Headline>().unwrap();
+    /// org.reset_checkboxes(&headline);
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* TODO Review [0%]\n- [ ] a\n- [ ] b\n  - [ ] c\n  - [ ] d\n* [X] e"
+    /// );
+    /// ```
+    pub fn reset_checkboxes(&mut self, headline: &Headline) {
+        let edits: Vec<_> = headline
+            .syntax
+            .descendants()
+            .filter_map(ListItem::cast)
+            .filter(|item| !matches!(item.checkbox_state(), None | Some(CheckboxState::Off)))
+            .filter_map(|item| checkbox_range(&item))
+            .collect();
+        for range in edits {
+            self.replace_range(range, CheckboxState::Off.as_str());
+        }
+
+        let start = headline.start();
+        let Some(headline) = self
+            .document()
+            .syntax
+            .descendants()
+            .filter_map(Headline::cast)
+            .find(|h| h.start() == start)
+        else {
+            return;
+        };
+        let range = headline.text_range();
+        self.update_cookies_where(|cookie| range.contains_range(cookie.text_range()));
+    }
+
+    fn list_item_at(&self, start: TextSize) -> Option<ListItem> {
+        self.document()
+            .syntax
+            .descendants()
+            .filter_map(ListItem::cast)
+            .find(|item| item.start() == start)
+    }
+}
+
+/// Parses a number or a single letter, `a` and `A` being 1
+fn parse_ordinal(s: &str) -> Option<usize> {
+    match s.as_bytes() {
+        [c] if c.is_ascii_alphabetic() => Some((c.to_ascii_lowercase() - b'a') as usize + 1),
+        _ => s.parse().ok(),
+    }
+}
+
+/// Range of the character between checkbox brackets
+fn checkbox_range(item: &ListItem) -> Option<TextRange> {
+    item.checkbox().map(|token| token.text_range())
+}
+
+fn parent_item(item: &ListItem) -> Option<ListItem> {
+    item.syntax.ancestors().skip(1).find_map(ListItem::cast)
+}
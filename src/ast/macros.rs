@@ -32,4 +32,60 @@ impl Macros {
             .filter_map(filter_token(SyntaxKind::TEXT))
             .nth(1)
     }
+
+    /// Returns arguments of this macro
+    ///
+    /// Arguments are separated by commas. A comma preceded by an odd number
+    /// of backslashes is a literal comma, and every two backslashes before
+    /// a comma are reduced to one. Whitespaces in arguments are collapsed into
+    /// one space, but arguments are not trimmed, like emacs.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Macros};
+    ///
+    /// let m = Org::parse("{{{title}}}").first_node::<Macros>().unwrap();
+    /// assert!(m.arguments().is_empty());
+    /// let m = Org::parse("{{{macro()}}}").first_node::<Macros>().unwrap();
+    /// assert_eq!(m.arguments(), [""]);
+    /// let m = Org::parse("{{{macro(1,  2\n3)}}}").first_node::<Macros>().unwrap();
+    /// assert_eq!(m.arguments(), ["1", " 2 3"]);
+    /// let m = Org::parse(r"{{{macro(a\,b,c\\,d)}}}").first_node::<Macros>().unwrap();
+    /// assert_eq!(m.arguments(), [r"a,b", r"c\", "d"]);
+    /// ```
+    pub fn arguments(&self) -> Vec<String> {
+        let Some(args) = self.args() else {
+            return vec![];
+        };
+
+        let args = args.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let mut arguments = vec![String::new()];
+        let mut backslashes = 0;
+
+        for c in args.chars() {
+            let current = arguments.last_mut().unwrap();
+            match c {
+                '\\' => backslashes += 1,
+                ',' => {
+                    current.push_str(&"\\".repeat(backslashes / 2));
+                    if backslashes % 2 == 0 {
+                        arguments.push(String::new());
+                    } else {
+                        current.push(',');
+                    }
+                    backslashes = 0;
+                }
+                _ => {
+                    current.push_str(&"\\".repeat(backslashes));
+                    current.push(c);
+                    backslashes = 0;
+                }
+            }
+        }
+
+        let current = arguments.last_mut().unwrap();
+        current.push_str(&"\\".repeat(backslashes));
+
+        arguments
+    }
 }
@@ -2,10 +2,13 @@ mod generated;
 
 mod affiliated_keyword;
 mod block;
+mod citation;
 mod clock;
 #[cfg(feature = "syntax-org-fc")]
 mod cloze;
 mod comment;
+mod cookie;
+mod diary;
 mod document;
 mod drawer;
 mod entity;
@@ -13,20 +16,31 @@ mod fixed_width;
 mod headline;
 mod inline_call;
 mod inline_src;
+mod inlinetask;
 mod keyword;
 mod link;
 mod list;
 mod macros;
 mod planning;
+mod radio_target;
 mod snippet;
 mod table;
+mod table_el;
 mod timestamp;
 
+pub use block::{LineNumbering, SourceLine, SrcSwitches};
+pub use clock::{ClockWindow, ClockedTime};
 #[cfg(feature = "syntax-org-fc")]
 pub use cloze::*;
+pub use cookie::CookieStatistics;
+pub use diary::{CalendarDateStyle, DiaryError, DiarySexp};
 pub use generated::*;
 pub use headline::*;
+pub use link::*;
+pub use list::CheckboxState;
 pub use rowan::ast::support::*;
+pub use table::{ColumnAlignment, ColumnCookie, ColumnGroup, RowMark};
+pub use table_el::{TableElCell, TableElGrid};
 pub use timestamp::*;
 
 use crate::{
@@ -0,0 +1,19 @@
+use super::RadioTarget;
+use crate::syntax::SyntaxKind;
+
+impl RadioTarget {
+    /// Returns the raw contents of this radio target
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::RadioTarget};
+    ///
+    /// let target = Org::parse("<<<radio \\alpha>>>").first_node::<RadioTarget>().unwrap();
+    /// assert_eq!(target.value(), "radio \\alpha");
+    /// ```
+    pub fn value(&self) -> String {
+        self.syntax
+            .children_with_tokens()
+            .filter(|e| e.kind() != SyntaxKind::L_ANGLE3 && e.kind() != SyntaxKind::R_ANGLE3)
+            .fold(String::new(), |acc, e| acc + &e.to_string())
+    }
+}
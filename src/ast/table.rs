@@ -1,7 +1,105 @@
-use rowan::ast::AstNode;
+use rowan::{ast::AstNode, TextRange};
 
-use super::{filter_token, OrgTable, OrgTableRow, Token};
-use crate::syntax::SyntaxKind;
+use super::{filter_token, OrgTable, OrgTableCell, OrgTableRow, Token};
+use crate::{syntax::SyntaxKind, table::TableFormula};
+
+/// Alignment of a table column
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    Left,
+    Right,
+    Center,
+}
+
+/// Alignment and width cookie of a column, e.g. `<r10>`
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ColumnCookie {
+    pub alignment: Option<ColumnAlignment>,
+    pub width: Option<usize>,
+}
+
+impl ColumnCookie {
+    /// Parses a cookie like `<l>`, `<10>` or `<c5>`
+    ///
+    /// ```rust
+    /// use orgize::ast::{ColumnAlignment, ColumnCookie};
+    ///
+    /// let cookie = ColumnCookie::parse("<r10>").unwrap();
+    /// assert_eq!(cookie.alignment, Some(ColumnAlignment::Right));
+    /// assert_eq!(cookie.width, Some(10));
+    /// assert_eq!(ColumnCookie::parse("<8>").unwrap().alignment, None);
+    /// assert!(ColumnCookie::parse("<>").is_none());
+    /// assert!(ColumnCookie::parse("<x>").is_none());
+    /// ```
+    pub fn parse(s: &str) -> Option<ColumnCookie> {
+        let s = s.strip_prefix('<')?.strip_suffix('>')?;
+        let (alignment, width) = match s.as_bytes().first()? {
+            b'l' => (Some(ColumnAlignment::Left), &s[1..]),
+            b'r' => (Some(ColumnAlignment::Right), &s[1..]),
+            b'c' => (Some(ColumnAlignment::Center), &s[1..]),
+            _ => (None, s),
+        };
+        if !width.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some(ColumnCookie {
+            alignment,
+            width: width.parse().ok(),
+        })
+    }
+}
+
+/// Column group boundary, defined in a row marked with `/`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColumnGroup {
+    /// `<`, starts a group
+    Start,
+    /// `>`, ends a group
+    End,
+    /// `<>`, a group by itself
+    StartEnd,
+}
+
+/// Mark in the first column of a row, see [`OrgTable::has_marking_column`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RowMark {
+    /// `!`, column names
+    ColumnNames,
+    /// `^`, field names of the row above
+    NamesAbove,
+    /// `_`, field names of the row below
+    NamesBelow,
+    /// `$`, parameters
+    Parameters,
+    /// `#`, recalculated automatically
+    Recalculate,
+    /// `*`, recalculated by global recalculation only
+    GlobalRecalculate,
+    /// `/`, column groups, not exported
+    NoExport,
+}
+
+impl RowMark {
+    /// Parses a mark like `!` or `#`
+    pub fn parse(s: &str) -> Option<RowMark> {
+        match s {
+            "!" => Some(RowMark::ColumnNames),
+            "^" => Some(RowMark::NamesAbove),
+            "_" => Some(RowMark::NamesBelow),
+            "$" => Some(RowMark::Parameters),
+            "#" => Some(RowMark::Recalculate),
+            "*" => Some(RowMark::GlobalRecalculate),
+            "/" => Some(RowMark::NoExport),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if rows with this mark are excluded from export
+    /// and column formulas
+    pub fn is_special(self) -> bool {
+        !matches!(self, RowMark::Recalculate | RowMark::GlobalRecalculate)
+    }
+}
 
 impl OrgTable {
     /// Returns `true` if this table has a header
@@ -79,6 +177,220 @@ impl OrgTable {
             }
         })
     }
+
+    /// Parsed formulas from all `#+TBLFM` lines, malformed formulas are skipped
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable, table::{ColumnRef, FormulaTarget}};
+    ///
+    /// let table = Org::parse(r#"
+    /// | a | 1 | 2 |   |
+    /// |---+---+---+---|
+    /// |   |   |   |   |
+    /// #+TBLFM: $4=vsum($2..$3)::@>$1=Total
+    /// #+TBLFM: $2=$1*2;%.2f"#).first_node::<OrgTable>().unwrap();
+    /// let formulas = table.formulas();
+    /// assert_eq!(formulas.len(), 3);
+    /// assert_eq!(formulas[0].target, FormulaTarget::Column(ColumnRef::Absolute(4)));
+    /// assert_eq!(formulas[2].format.as_ref().unwrap().printf.as_deref(), Some("%.2f"));
+    /// ```
+    pub fn formulas(&self) -> Vec<TableFormula> {
+        self.tblfm()
+            .flat_map(|tblfm| TableFormula::parse_line(&tblfm))
+            .collect()
+    }
+
+    /// Trimmed cell contents of standard rows, rules are skipped
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let table = Org::parse("| a | b |\n|---+---|\n|  c |\n").first_node::<OrgTable>().unwrap();
+    /// assert_eq!(table.rows(), [vec!["a", "b"], vec!["c"]]);
+    /// ```
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        self.syntax
+            .children()
+            .filter_map(OrgTableRow::cast)
+            .filter(|row| row.is_standard())
+            .map(|row| row.cells())
+            .collect()
+    }
+
+    /// Number of columns, i.e. the cell count of the widest row
+    pub fn column_count(&self) -> usize {
+        self.rows().iter().map(Vec::len).max().unwrap_or_default()
+    }
+
+    /// Trimmed cell contents of the n-th column, starting from zero
+    ///
+    /// Rows shorter than `n` yield an empty string.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let table = Org::parse("| a | b |\n|---+---|\n| c |\n").first_node::<OrgTable>().unwrap();
+    /// assert_eq!(table.column(1), ["b", ""]);
+    /// ```
+    pub fn column(&self, n: usize) -> Vec<String> {
+        self.rows()
+            .into_iter()
+            .map(|mut row| {
+                if n < row.len() {
+                    row.swap_remove(n)
+                } else {
+                    String::new()
+                }
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the first column only contains row marks
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let table = Org::parse("| ! | a |\n| # | 1 |\n|   | 2 |").first_node::<OrgTable>().unwrap();
+    /// assert!(table.has_marking_column());
+    /// let table = Org::parse("| x | a |\n| # | 1 |").first_node::<OrgTable>().unwrap();
+    /// assert!(!table.has_marking_column());
+    /// ```
+    pub fn has_marking_column(&self) -> bool {
+        has_marking_column(&self.rows())
+    }
+
+    /// Mark of each standard row, in the same order as [`OrgTable::rows`]
+    ///
+    /// All marks are `None` if the table has no marking column. Prefer this
+    /// over calling [`OrgTableRow::mark`] on every row, which scans the whole
+    /// table each time.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{OrgTable, RowMark}};
+    ///
+    /// let table = Org::parse("| ! | a |\n|---|\n| # | 1 |\n|   | 2 |").first_node::<OrgTable>().unwrap();
+    /// assert_eq!(table.row_marks(), [Some(RowMark::ColumnNames), Some(RowMark::Recalculate), None]);
+    /// ```
+    pub fn row_marks(&self) -> Vec<Option<RowMark>> {
+        let rows = self.rows();
+        let marking = has_marking_column(&rows);
+        rows.iter()
+            .map(|row| marking.then(|| RowMark::parse(row.first()?)).flatten())
+            .collect()
+    }
+
+    /// Alignment and width cookies of each column
+    ///
+    /// Cookies are defined in rows containing cookies only. If a column has
+    /// more than one cookie, the first one is used.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{ColumnAlignment, ColumnCookie, OrgTable}};
+    ///
+    /// let table = Org::parse("|  <r> | <c8> |    |\n| a    | b    | c  |").first_node::<OrgTable>().unwrap();
+    /// let cookies = table.column_cookies();
+    /// assert_eq!(cookies[0].alignment, Some(ColumnAlignment::Right));
+    /// assert_eq!(cookies[1].width, Some(8));
+    /// assert_eq!(cookies[2], ColumnCookie::default());
+    /// ```
+    pub fn column_cookies(&self) -> Vec<ColumnCookie> {
+        let mut cookies = vec![ColumnCookie::default(); self.column_count()];
+        let mut defined = vec![false; cookies.len()];
+
+        for row in self.syntax.children().filter_map(OrgTableRow::cast) {
+            if !row.is_cookie_row() {
+                continue;
+            }
+            for (idx, cell) in row.cells().iter().enumerate() {
+                if let Some(cookie) = ColumnCookie::parse(cell) {
+                    if !defined[idx] {
+                        cookies[idx] = cookie;
+                        defined[idx] = true;
+                    }
+                }
+            }
+        }
+
+        cookies
+    }
+
+    /// Alignment of each column
+    ///
+    /// Alignment cookie is used if present. Otherwise a column is right-aligned
+    /// if at least half of its non-empty cells are numbers, like `org-table-align`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{ColumnAlignment, OrgTable}};
+    ///
+    /// let table = Org::parse("|    |   | <c> |\n| 1  | x | 2   |\n| 2% | y | 3   |").first_node::<OrgTable>().unwrap();
+    /// assert_eq!(
+    ///     table.column_alignments(),
+    ///     [ColumnAlignment::Right, ColumnAlignment::Left, ColumnAlignment::Center]
+    /// );
+    /// ```
+    pub fn column_alignments(&self) -> Vec<ColumnAlignment> {
+        let cookies = self.column_cookies();
+        let rows: Vec<_> = self
+            .syntax
+            .children()
+            .filter_map(OrgTableRow::cast)
+            .filter(|row| row.is_standard() && !row.is_cookie_row())
+            .map(|row| row.cells())
+            .collect();
+
+        cookies
+            .iter()
+            .enumerate()
+            .map(|(idx, cookie)| {
+                if let Some(alignment) = cookie.alignment {
+                    return alignment;
+                }
+                let cells = rows
+                    .iter()
+                    .filter_map(|row| row.get(idx))
+                    .filter(|cell| !cell.is_empty());
+                let (numbers, total) = cells.fold((0, 0), |(numbers, total), cell| {
+                    (numbers + is_number(cell) as usize, total + 1)
+                });
+                if total > 0 && numbers * 2 >= total {
+                    ColumnAlignment::Right
+                } else {
+                    ColumnAlignment::Left
+                }
+            })
+            .collect()
+    }
+
+    /// Column groups of each column, defined in a row marked with `/`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{ColumnGroup, OrgTable}};
+    ///
+    /// let table = Org::parse("| / | <> | < |   | > |\n| # | 1  | 2 | 3 | 4 |").first_node::<OrgTable>().unwrap();
+    /// assert_eq!(
+    ///     table.column_groups(),
+    ///     [None, Some(ColumnGroup::StartEnd), Some(ColumnGroup::Start), None, Some(ColumnGroup::End)]
+    /// );
+    /// ```
+    pub fn column_groups(&self) -> Vec<Option<ColumnGroup>> {
+        let mut groups = vec![None; self.column_count()];
+
+        for (row, mark) in self.rows().iter().zip(self.row_marks()) {
+            if mark != Some(RowMark::NoExport) {
+                continue;
+            }
+            for (idx, cell) in row.iter().enumerate().skip(1) {
+                groups[idx] = match cell.as_str() {
+                    "<" => Some(ColumnGroup::Start),
+                    ">" => Some(ColumnGroup::End),
+                    "<>" => Some(ColumnGroup::StartEnd),
+                    _ => groups[idx],
+                };
+            }
+        }
+
+        groups
+    }
 }
 
 impl OrgTableRow {
@@ -107,4 +419,131 @@ impl OrgTableRow {
     pub fn is_standard(&self) -> bool {
         self.syntax.kind() == SyntaxKind::ORG_TABLE_STANDARD_ROW
     }
+
+    /// Trimmed cell contents, including empty cells
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTableRow};
+    ///
+    /// let row = Org::parse("| a |  | *b* |").first_node::<OrgTableRow>().unwrap();
+    /// assert_eq!(row.cells(), ["a", "", "*b*"]);
+    /// let row = Org::parse("|---+---|").first_node::<OrgTableRow>().unwrap();
+    /// assert!(row.cells().is_empty());
+    /// ```
+    pub fn cells(&self) -> Vec<String> {
+        self.slots()
+            .into_iter()
+            .map(|(_, cell)| cell.map(|c| c.syntax.to_string()).unwrap_or_default())
+            .collect()
+    }
+
+    /// Cells of this row, paired with the range between two vertical bars
+    ///
+    /// An empty cell has no `ORG_TABLE_CELL` node, so it's returned as `None`.
+    pub(crate) fn slots(&self) -> Vec<(TextRange, Option<OrgTableCell>)> {
+        let mut slots = vec![];
+        let mut slot_start = None;
+        let mut cell = None;
+
+        for element in self.syntax.children_with_tokens() {
+            match element.kind() {
+                SyntaxKind::PIPE => {
+                    if let Some(start) = slot_start {
+                        let range = TextRange::new(start, element.text_range().start());
+                        slots.push((range, cell.take()));
+                    }
+                    slot_start = Some(element.text_range().end());
+                }
+                SyntaxKind::ORG_TABLE_CELL => {
+                    cell = element.into_node().and_then(OrgTableCell::cast);
+                }
+                _ => {}
+            }
+        }
+
+        // last cell without trailing vertical bar
+        if let (Some(start), Some(cell)) = (slot_start, cell) {
+            slots.push((TextRange::new(start, cell.end()), Some(cell)));
+        }
+
+        slots
+    }
+
+    /// Returns `true` if this row only contains alignment and width cookies
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTableRow};
+    ///
+    /// let row = Org::parse("| <l> |  | <10> |").first_node::<OrgTableRow>().unwrap();
+    /// assert!(row.is_cookie_row());
+    /// let row = Org::parse("| / | <r> |").first_node::<OrgTableRow>().unwrap();
+    /// assert!(row.is_cookie_row());
+    /// let row = Org::parse("| <l> | a |").first_node::<OrgTableRow>().unwrap();
+    /// assert!(!row.is_cookie_row());
+    /// ```
+    pub fn is_cookie_row(&self) -> bool {
+        let cells = self.cells();
+        // row mark in the first column is ignored
+        let skip = cells
+            .first()
+            .is_some_and(|cell| RowMark::parse(cell).is_some()) as usize;
+        let mut cells = cells[skip..]
+            .iter()
+            .filter(|cell| !cell.is_empty())
+            .peekable();
+        cells.peek().is_some() && cells.all(|cell| ColumnCookie::parse(cell).is_some())
+    }
+
+    /// Mark of this row, if its table has a marking column
+    ///
+    /// This scans the whole table, use [`OrgTable::row_marks`] for marks of
+    /// all rows.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{OrgTableRow, RowMark}};
+    ///
+    /// let org = Org::parse("| ! | a |\n| # | 1 |");
+    /// let row = org.first_node::<OrgTableRow>().unwrap();
+    /// assert_eq!(row.mark(), Some(RowMark::ColumnNames));
+    ///
+    /// let org = Org::parse("| ! | a |\n| x | 1 |");
+    /// let row = org.first_node::<OrgTableRow>().unwrap();
+    /// assert_eq!(row.mark(), None);
+    /// ```
+    pub fn mark(&self) -> Option<RowMark> {
+        let table = self.syntax.parent().and_then(OrgTable::cast)?;
+        if !table.has_marking_column() {
+            return None;
+        }
+        RowMark::parse(self.cells().first()?)
+    }
+}
+
+/// Returns `true` if the first column of rows only contains row marks
+fn has_marking_column(rows: &[Vec<String>]) -> bool {
+    let first = || {
+        rows.iter()
+            .map(|row| row.first().map_or("", String::as_str))
+    };
+    first().all(|cell| cell.is_empty() || RowMark::parse(cell).is_some())
+        && first().any(|cell| !cell.is_empty())
+}
+
+/// Returns `true` if the cell looks like a number, similar to `org-table-number-regexp`
+pub(crate) fn is_number(s: &str) -> bool {
+    let s = s.trim_start_matches(['+', '-']);
+    let s = s.strip_suffix('%').unwrap_or(s);
+    let s = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => {
+            let exponent = exponent.trim_start_matches(['+', '-']);
+            if exponent.is_empty() || !exponent.bytes().all(|b| b.is_ascii_digit()) {
+                return false;
+            }
+            mantissa
+        }
+        None => s,
+    };
+    s.bytes().any(|b| b.is_ascii_digit())
+        && s.bytes()
+            .all(|b| b.is_ascii_digit() || b == b'.' || b == b',')
 }
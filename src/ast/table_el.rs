@@ -0,0 +1,225 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use super::{filter_token, TableEl};
+use crate::syntax::SyntaxKind;
+
+/// Grid of a table.el table, built from its border layout
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableElGrid {
+    /// Number of rows, counting a merged cell once per row it spans
+    pub rows: usize,
+    /// Number of columns, counting a merged cell once per column it spans
+    pub columns: usize,
+    /// Number of rows above the first `+===+` border, zero if none
+    pub header_rows: usize,
+    /// Cells ordered by row then column
+    pub cells: Vec<TableElCell>,
+}
+
+/// A cell of table.el table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableElCell {
+    /// Index of first row this cell occupies
+    pub row: usize,
+    /// Index of first column this cell occupies
+    pub column: usize,
+    pub rowspan: usize,
+    pub colspan: usize,
+    /// Cell contents, one trimmed line per text line inside the cell
+    pub text: String,
+}
+
+impl TableElGrid {
+    /// Cells starting at given row
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &TableElCell> {
+        self.cells.iter().filter(move |cell| cell.row == row)
+    }
+}
+
+impl TableEl {
+    /// Parses the table into grid, returns `None` if its borders are malformed
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::TableEl};
+    ///
+    /// let org = Org::parse(r#"
+    /// +-----+-----+
+    /// | a   | b   |
+    /// +=====+=====+
+    /// | c         |
+    /// +-----+-----+
+    /// | d   | e   |
+    /// +-----+ f   |
+    /// | g   |     |
+    /// +-----+-----+
+    /// "#);
+    /// let grid = org.first_node::<TableEl>().unwrap().grid().unwrap();
+    /// assert_eq!((grid.rows, grid.columns, grid.header_rows), (4, 2, 1));
+    ///
+    /// let cells: Vec<_> = grid
+    ///     .cells
+    ///     .iter()
+    ///     .map(|c| (c.text.as_str(), c.row, c.column, c.rowspan, c.colspan))
+    ///     .collect();
+    /// assert_eq!(
+    ///     cells,
+    ///     [
+    ///         ("a", 0, 0, 1, 1),
+    ///         ("b", 0, 1, 1, 1),
+    ///         ("c", 1, 0, 1, 2),
+    ///         ("d", 2, 0, 1, 1),
+    ///         ("e\nf", 2, 1, 2, 1),
+    ///         ("g", 3, 0, 1, 1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn grid(&self) -> Option<TableElGrid> {
+        let text = filter_token(SyntaxKind::TEXT)(self.syntax.first_child_or_token()?)?;
+
+        let lines: Vec<&str> = text
+            .lines()
+            .map(|line| line.trim_end())
+            .filter(|line| !line.is_empty())
+            .collect();
+        let indent = lines
+            .iter()
+            .map(|line| line.len() - line.trim_start().len())
+            .min()?;
+        let lines: Vec<Vec<char>> = lines
+            .iter()
+            .map(|line| line[indent..].chars().collect())
+            .collect();
+
+        let at = |y: usize, x: usize| -> char {
+            lines
+                .get(y)
+                .and_then(|line| line.get(x))
+                .copied()
+                .unwrap_or(' ')
+        };
+
+        if at(0, 0) != '+' {
+            return None;
+        }
+
+        // walks from top-left corners to find every cell rectangle, same as
+        // the grid table parser of docutils
+        let mut rects = vec![];
+        let mut corners = VecDeque::from([(0, 0)]);
+        let mut visited = BTreeSet::new();
+        while let Some((top, left)) = corners.pop_front() {
+            if !visited.insert((top, left)) {
+                continue;
+            }
+            let Some((bottom, right)) = scan_cell(&at, top, left) else {
+                continue;
+            };
+            if right + 1 < lines[top].len() {
+                corners.push_back((top, right));
+            }
+            if bottom + 1 < lines.len() {
+                corners.push_back((bottom, left));
+            }
+            rects.push((top, left, bottom, right));
+        }
+
+        if rects.is_empty() {
+            return None;
+        }
+
+        let ys: Vec<usize> = rects
+            .iter()
+            .flat_map(|r| [r.0, r.2])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let xs: Vec<usize> = rects
+            .iter()
+            .flat_map(|r| [r.1, r.3])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let y_index = |y: usize| ys.binary_search(&y).unwrap_or_default();
+        let x_index = |x: usize| xs.binary_search(&x).unwrap_or_default();
+
+        let mut cells: Vec<_> = rects
+            .iter()
+            .map(|&(top, left, bottom, right)| {
+                let content: Vec<String> = (top + 1..bottom)
+                    .map(|y| {
+                        (left + 1..right)
+                            .map(|x| at(y, x))
+                            .collect::<String>()
+                            .trim()
+                            .to_string()
+                    })
+                    .collect();
+                let start = content.iter().position(|l| !l.is_empty());
+                let end = content.iter().rposition(|l| !l.is_empty());
+                let text = match (start, end) {
+                    (Some(start), Some(end)) => content[start..=end].join("\n"),
+                    _ => String::new(),
+                };
+
+                TableElCell {
+                    row: y_index(top),
+                    column: x_index(left),
+                    rowspan: y_index(bottom) - y_index(top),
+                    colspan: x_index(right) - x_index(left),
+                    text,
+                }
+            })
+            .collect();
+        cells.sort_by_key(|cell| (cell.row, cell.column));
+
+        let header_rows = ys
+            .iter()
+            .position(|&y| lines[y].get(1) == Some(&'='))
+            .unwrap_or_default();
+
+        Some(TableElGrid {
+            rows: ys.len() - 1,
+            columns: xs.len() - 1,
+            header_rows,
+            cells,
+        })
+    }
+}
+
+/// Finds bottom-right corner of the cell starting at given top-left corner
+fn scan_cell(
+    at: &impl Fn(usize, usize) -> char,
+    top: usize,
+    left: usize,
+) -> Option<(usize, usize)> {
+    if at(top, left) != '+' {
+        return None;
+    }
+
+    let mut right = left + 1;
+    loop {
+        match at(top, right) {
+            '-' | '=' => {}
+            '+' => {
+                let mut bottom = top + 1;
+                loop {
+                    match at(bottom, right) {
+                        '|' => {}
+                        '+' => {
+                            let closed = (left..=right)
+                                .all(|x| matches!(at(bottom, x), '-' | '=' | '+'))
+                                && (top..=bottom).all(|y| matches!(at(y, left), '|' | '+'));
+                            if closed {
+                                return Some((bottom, right));
+                            }
+                        }
+                        _ => break,
+                    }
+                    bottom += 1;
+                }
+            }
+            _ => return None,
+        }
+        right += 1;
+    }
+}
@@ -1,7 +1,17 @@
+use std::fmt;
+
+use rowan::{ast::AstNode, NodeOrToken};
+
 use super::{filter_token, Timestamp};
-use crate::syntax::SyntaxKind;
+use crate::{
+    syntax::{
+        timestamp::{timestamp_active_node, timestamp_inactive_node},
+        SyntaxKind, SyntaxNode,
+    },
+    ParseConfig,
+};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum TimeUnit {
     Hour,
     Day,
@@ -10,14 +20,14 @@ pub enum TimeUnit {
     Year,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum RepeaterType {
     Cumulate,
     CatchUp,
     Restart,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DelayType {
     All,
     First,
@@ -168,6 +178,94 @@ impl Timestamp {
         self.nth_delay(0).map(|i| i.2)
     }
 
+    /// Converts this timestamp to an owned [`TimestampValue`]
+    ///
+    /// Returns `None` if it's a diary timestamp.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Timestamp, TimestampDate, TimestampTime}};
+    ///
+    /// let ts = Org::parse("[2003-09-16 Tue 09:39]--[2003-09-18 Thu 10:39 -1d]").first_node::<Timestamp>().unwrap();
+    /// let value = ts.value().unwrap();
+    /// assert!(!value.active);
+    /// assert_eq!(value.start_date, TimestampDate::new(2003, 9, 16));
+    /// assert_eq!(value.start_time, Some(TimestampTime::new(9, 39)));
+    /// assert_eq!(value.end_date, Some(TimestampDate::new(2003, 9, 18)));
+    /// assert_eq!(value.end_time, Some(TimestampTime::new(10, 39)));
+    /// assert_eq!(value.warning.unwrap().value, 1);
+    ///
+    /// // day name is fixed when writing back
+    /// let ts = Org::parse("<2024-05-01 Mon 10:00-11:00 .+1w>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.value().unwrap().to_string(), "<2024-05-01 Wed 10:00-11:00 .+1w>");
+    /// ```
+    pub fn value(&self) -> Option<TimestampValue> {
+        if self.is_diary() {
+            return None;
+        }
+
+        let mut dates = [Option::<TimestampDate>::None; 2];
+        let mut times: [Vec<TimestampTime>; 2] = Default::default();
+        let mut part = 0;
+        let (mut year, mut month, mut hour) = (0, 0, 0);
+
+        for token in self
+            .syntax
+            .children_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+        {
+            let number = || token.text().parse::<u32>().ok();
+            match token.kind() {
+                SyntaxKind::MINUS2 => part = 1,
+                SyntaxKind::TIMESTAMP_YEAR => year = token.text().parse().ok()?,
+                SyntaxKind::TIMESTAMP_MONTH => month = number()?,
+                SyntaxKind::TIMESTAMP_DAY => {
+                    dates[part] = Some(TimestampDate::new(year, month, number()?));
+                }
+                SyntaxKind::TIMESTAMP_HOUR => hour = number()?,
+                SyntaxKind::TIMESTAMP_MINUTE => {
+                    times[part].push(TimestampTime::new(hour, number()?));
+                }
+                _ => {}
+            }
+        }
+
+        let [start_times, end_times] = times;
+        let (end_date, end_time) = match dates[1] {
+            Some(date) => (Some(date), end_times.first().copied()),
+            None => (None, start_times.get(1).copied()),
+        };
+
+        Some(TimestampValue {
+            active: self.is_active(),
+            start_date: dates[0]?,
+            start_time: start_times.first().copied(),
+            end_date,
+            end_time,
+            repeater: self.nth_repeater(0).map(|(kind, value, unit)| Repeater {
+                kind,
+                value,
+                unit,
+            }),
+            warning: self
+                .nth_delay(0)
+                .map(|(kind, value, unit)| Delay { kind, value, unit }),
+        })
+    }
+
+    /// Returns an iterator over occurrences of this timestamp within a window,
+    /// see [`TimestampValue::occurrences`]
+    ///
+    /// Diary timestamps have no occurrences.
+    pub fn occurrences(
+        &self,
+        from: TimestampDate,
+        to: TimestampDate,
+    ) -> impl Iterator<Item = TimestampValue> {
+        self.value()
+            .into_iter()
+            .flat_map(move |value| value.occurrences(from, to))
+    }
+
     fn nth_repeater(&self, nth: usize) -> Option<(RepeaterType, u32, TimeUnit)> {
         let mut i = nth + 1;
 
@@ -299,3 +397,623 @@ impl Timestamp {
         Some(self.end_to_chrono()? - self.start_to_chrono()?)
     }
 }
+
+const DAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Calendar date of a timestamp
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimestampDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl TimestampDate {
+    pub fn new(year: i32, month: u32, day: u32) -> TimestampDate {
+        TimestampDate { year, month, day }
+    }
+
+    /// Abbreviated English day name, as org-mode writes in timestamps
+    ///
+    /// ```rust
+    /// use orgize::ast::TimestampDate;
+    ///
+    /// assert_eq!(TimestampDate::new(2024, 5, 1).day_name(), "Wed");
+    /// assert_eq!(TimestampDate::new(2000, 2, 29).day_name(), "Tue");
+    /// assert_eq!(TimestampDate::new(1970, 1, 1).day_name(), "Thu");
+    /// ```
+    pub fn day_name(&self) -> &'static str {
+        DAY_NAMES[self.weekday() as usize]
+    }
+
+    /// Day of week, 0 being Sunday
+    pub fn weekday(&self) -> u32 {
+        (self.to_days() + 4).rem_euclid(7) as u32
+    }
+
+    /// ISO 8601 week number
+    ///
+    /// ```rust
+    /// use orgize::ast::TimestampDate;
+    ///
+    /// assert_eq!(TimestampDate::new(2024, 5, 1).iso_week(), 18);
+    /// assert_eq!(TimestampDate::new(2021, 1, 3).iso_week(), 53);
+    /// assert_eq!(TimestampDate::new(2024, 12, 30).iso_week(), 1);
+    /// ```
+    pub fn iso_week(&self) -> u32 {
+        // the week belongs to the year containing its Thursday
+        let thursday = self.add_days(3 - (self.weekday() as i64 + 6) % 7);
+        let first = TimestampDate::new(thursday.year, 1, 1);
+        ((thursday.to_days() - first.to_days()) / 7 + 1) as u32
+    }
+
+    /// ```rust
+    /// use orgize::ast::TimestampDate;
+    ///
+    /// assert_eq!(TimestampDate::new(2024, 2, 28).add_days(2), TimestampDate::new(2024, 3, 1));
+    /// assert_eq!(TimestampDate::new(2024, 1, 1).add_days(-1), TimestampDate::new(2023, 12, 31));
+    /// ```
+    pub fn add_days(self, days: i64) -> TimestampDate {
+        TimestampDate::from_days(self.to_days() + days)
+    }
+
+    /// Adds months, clamping the day to the end of month
+    ///
+    /// ```rust
+    /// use orgize::ast::TimestampDate;
+    ///
+    /// assert_eq!(TimestampDate::new(2024, 1, 31).add_months(1), TimestampDate::new(2024, 2, 29));
+    /// assert_eq!(TimestampDate::new(2024, 2, 29).add_months(12), TimestampDate::new(2025, 2, 28));
+    /// assert_eq!(TimestampDate::new(2024, 3, 15).add_months(-3), TimestampDate::new(2023, 12, 15));
+    /// ```
+    pub fn add_months(self, months: i64) -> TimestampDate {
+        let index = self.year as i64 * 12 + self.month as i64 - 1 + months;
+        let year = index.div_euclid(12) as i32;
+        let month = index.rem_euclid(12) as u32 + 1;
+        TimestampDate::new(year, month, self.day.min(days_in_month(year, month)))
+    }
+
+    /// Number of days since 1970-01-01
+    pub(crate) fn to_days(self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+
+    pub(crate) fn from_days(days: i64) -> TimestampDate {
+        let (year, month, day) = civil_from_days(days);
+        TimestampDate::new(year, month, day)
+    }
+
+    /// Converts to chrono NaiveDate, returns `None` if date is invalid
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::from_ymd_opt(self.year, self.month, self.day)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for TimestampDate {
+    fn from(date: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+
+        TimestampDate::new(date.year(), date.month(), date.day())
+    }
+}
+
+/// Time of day of a timestamp
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimestampTime {
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl TimestampTime {
+    pub fn new(hour: u32, minute: u32) -> TimestampTime {
+        TimestampTime { hour, minute }
+    }
+
+    /// Minutes since midnight
+    pub(crate) fn minutes(self) -> i64 {
+        self.hour as i64 * 60 + self.minute as i64
+    }
+
+    fn from_minutes(minutes: i64) -> TimestampTime {
+        TimestampTime::new((minutes / 60) as u32, (minutes % 60) as u32)
+    }
+}
+
+/// Repeater of a timestamp, e.g. `.+2w`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Repeater {
+    pub kind: RepeaterType,
+    pub value: u32,
+    pub unit: TimeUnit,
+}
+
+/// Warning delay of a timestamp, e.g. `--3d`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Delay {
+    pub kind: DelayType,
+    pub value: u32,
+    pub unit: TimeUnit,
+}
+
+/// An owned timestamp which can be built programmatically
+///
+/// Its `Display` implementation writes canonical org syntax.
+///
+/// ```rust
+/// use orgize::ast::{DelayType, RepeaterType, TimeUnit, TimestampDate, TimestampValue};
+///
+/// let ts = TimestampValue::new(TimestampDate::new(2024, 5, 1))
+///     .with_time(10, 0)
+///     .with_end_time(11, 0)
+///     .with_repeater(RepeaterType::Cumulate, 1, TimeUnit::Week)
+///     .with_warning(DelayType::All, 2, TimeUnit::Day);
+/// assert_eq!(ts.to_string(), "<2024-05-01 Wed 10:00-11:00 +1w -2d>");
+///
+/// let ts = TimestampValue::new(TimestampDate::new(2024, 5, 1))
+///     .inactive()
+///     .with_end_date(TimestampDate::new(2024, 5, 3));
+/// assert_eq!(ts.to_string(), "[2024-05-01 Wed]--[2024-05-03 Fri]");
+///
+/// // converts to syntax node and back
+/// let node = ts.to_timestamp().unwrap();
+/// assert!(node.is_range());
+/// assert_eq!(node.value().unwrap(), ts);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampValue {
+    pub active: bool,
+    pub start_date: TimestampDate,
+    pub start_time: Option<TimestampTime>,
+    /// End date of a range spanning multiple days, e.g.
+    /// `<2024-05-01>--<2024-05-03>`
+    pub end_date: Option<TimestampDate>,
+    /// End time, on the end date if any, or on the start date, e.g.
+    /// `<2024-05-01 10:00-11:00>`
+    ///
+    /// An end time on the start date requires `start_time`, otherwise it's
+    /// not written, see [`TimestampValue::with_end_time`].
+    pub end_time: Option<TimestampTime>,
+    pub repeater: Option<Repeater>,
+    pub warning: Option<Delay>,
+}
+
+impl TimestampValue {
+    /// Creates an active timestamp on given date
+    pub fn new(date: TimestampDate) -> TimestampValue {
+        TimestampValue {
+            active: true,
+            start_date: date,
+            start_time: None,
+            end_date: None,
+            end_time: None,
+            repeater: None,
+            warning: None,
+        }
+    }
+
+    /// Makes this timestamp inactive, i.e. `[...]`
+    pub fn inactive(mut self) -> TimestampValue {
+        self.active = false;
+        self
+    }
+
+    pub fn with_time(mut self, hour: u32, minute: u32) -> TimestampValue {
+        self.start_time = Some(TimestampTime::new(hour, minute));
+        self
+    }
+
+    pub fn with_end_date(mut self, date: TimestampDate) -> TimestampValue {
+        self.end_date = Some(date);
+        self
+    }
+
+    /// Sets end time, on the end date if any, or on the start date
+    ///
+    /// Org syntax has no time range without a start time, so an end time on
+    /// the start date requires [`TimestampValue::with_time`], otherwise it's
+    /// dropped when writing the timestamp.
+    ///
+    /// ```rust
+    /// use orgize::ast::{TimestampDate, TimestampValue};
+    ///
+    /// let date = TimestampDate::new(2024, 5, 1);
+    /// let ts = TimestampValue::new(date).with_time(10, 0).with_end_time(11, 0);
+    /// assert_eq!(ts.to_string(), "<2024-05-01 Wed 10:00-11:00>");
+    ///
+    /// // end time on the end date doesn't need a start time
+    /// let ts = TimestampValue::new(date)
+    ///     .with_end_date(TimestampDate::new(2024, 5, 2))
+    ///     .with_end_time(11, 0);
+    /// assert_eq!(ts.to_string(), "<2024-05-01 Wed>--<2024-05-02 Thu 11:00>");
+    ///
+    /// // without start time, the end time is dropped
+    /// let ts = TimestampValue::new(date).with_end_time(11, 0);
+    /// assert_eq!(ts.to_string(), "<2024-05-01 Wed>");
+    /// ```
+    pub fn with_end_time(mut self, hour: u32, minute: u32) -> TimestampValue {
+        self.end_time = Some(TimestampTime::new(hour, minute));
+        self
+    }
+
+    pub fn with_repeater(
+        mut self,
+        kind: RepeaterType,
+        value: u32,
+        unit: TimeUnit,
+    ) -> TimestampValue {
+        self.repeater = Some(Repeater { kind, value, unit });
+        self
+    }
+
+    pub fn with_warning(mut self, kind: DelayType, value: u32, unit: TimeUnit) -> TimestampValue {
+        self.warning = Some(Delay { kind, value, unit });
+        self
+    }
+
+    /// Parses the org text of this timestamp into a syntax node
+    ///
+    /// Returns `None` if the text can't be parsed back, e.g. year is negative.
+    pub fn to_timestamp(&self) -> Option<Timestamp> {
+        let text = self.to_string();
+        let config = ParseConfig::default();
+        let parser = if self.active {
+            timestamp_active_node
+        } else {
+            timestamp_inactive_node
+        };
+        let (rest, green) = parser((text.as_str(), &config).into()).ok()?;
+        if !rest.is_empty() {
+            return None;
+        }
+        Timestamp::cast(SyntaxNode::new_root(green.into_node()?))
+    }
+
+    /// Returns an iterator over occurrences of this timestamp which overlap
+    /// the window between `from` and `to`, both inclusive
+    ///
+    /// Occurrences of a repeating timestamp are `n` repeater intervals after
+    /// its start, like in agenda views, no matter if the repeater is `+`, `++`
+    /// or `.+`. Monthly and yearly repeats are clamped to the end of month,
+    /// e.g. `<2024-01-31 +1m>` occurs on 2024-02-29 then 2024-03-31. Time
+    /// ranges and multi-day ranges keep their length.
+    ///
+    /// A timestamp without repeater occurs once.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Timestamp, TimestampDate}};
+    ///
+    /// let ts = Org::parse("<2024-01-31 Wed 10:00-11:00 +1m>").first_node::<Timestamp>().unwrap();
+    /// let occurrences: Vec<_> = ts
+    ///     .occurrences(TimestampDate::new(2024, 2, 1), TimestampDate::new(2024, 5, 31))
+    ///     .map(|ts| ts.to_string())
+    ///     .collect();
+    /// assert_eq!(
+    ///     occurrences,
+    ///     [
+    ///         "<2024-02-29 Thu 10:00-11:00 +1m>",
+    ///         "<2024-03-31 Sun 10:00-11:00 +1m>",
+    ///         "<2024-04-30 Tue 10:00-11:00 +1m>",
+    ///         "<2024-05-31 Fri 10:00-11:00 +1m>",
+    ///     ]
+    /// );
+    ///
+    /// let ts = Org::parse("<2024-05-01 Wed 22:00 .+6h>").first_node::<Timestamp>().unwrap();
+    /// let next = ts.occurrences(TimestampDate::new(2024, 5, 2), TimestampDate::new(2024, 5, 2)).next();
+    /// assert_eq!(next.unwrap().to_string(), "<2024-05-02 Thu 04:00 .+6h>");
+    ///
+    /// // ranges overlapping the window are included
+    /// let ts = Org::parse("<2024-05-01 Wed +1w>--<2024-05-03 Fri +1w>").first_node::<Timestamp>().unwrap();
+    /// let day = TimestampDate::new(2024, 5, 9);
+    /// let occurrences: Vec<_> = ts.occurrences(day, day).map(|ts| ts.to_string()).collect();
+    /// assert_eq!(occurrences, ["<2024-05-08 Wed +1w>--<2024-05-10 Fri +1w>"]);
+    /// ```
+    pub fn occurrences(&self, from: TimestampDate, to: TimestampDate) -> Occurrences {
+        let first = match self.repeater {
+            Some(repeater) if repeater.value > 0 => {
+                let value = repeater.value as i64;
+                let span = self
+                    .end_date
+                    .map_or(0, |end| end.to_days() - self.start_date.to_days());
+                let lag = match repeater.unit {
+                    TimeUnit::Hour => {
+                        let start = self.start_date.to_days() * 1440
+                            + self.start_time.map_or(0, TimestampTime::minutes);
+                        (from.to_days() * 1440 - start - span * 1440 - 1440) / (value * 60)
+                    }
+                    TimeUnit::Day | TimeUnit::Week => {
+                        let days = if repeater.unit == TimeUnit::Week {
+                            7
+                        } else {
+                            1
+                        };
+                        (from.to_days() - self.start_date.to_days() - span - 1) / (value * days)
+                    }
+                    TimeUnit::Month | TimeUnit::Year => {
+                        let months = if repeater.unit == TimeUnit::Year {
+                            12
+                        } else {
+                            1
+                        };
+                        let diff = (from.year - self.start_date.year) as i64 * 12
+                            + from.month as i64
+                            - self.start_date.month as i64;
+                        (diff - span / 28 - 1) / (value * months)
+                    }
+                };
+                lag.max(0)
+            }
+            _ => 0,
+        };
+
+        Occurrences {
+            value: self.clone(),
+            from,
+            to,
+            next: Some(first),
+        }
+    }
+
+    /// Returns the timestamp after its task is marked as done at given date
+    /// and time, returns `None` if it has no repeater
+    ///
+    /// - `+1w` is shifted by one interval
+    /// - `++1w` is shifted by as many intervals as needed to be in the future
+    /// - `.+1w` is shifted to one interval after `now`
+    ///
+    /// ```rust
+    /// use orgize::ast::{RepeaterType, TimeUnit, TimestampDate, TimestampTime, TimestampValue};
+    ///
+    /// let now = (TimestampDate::new(2024, 5, 20), TimestampTime::new(12, 0));
+    /// let ts = |kind| {
+    ///     TimestampValue::new(TimestampDate::new(2024, 5, 1))
+    ///         .with_time(9, 0)
+    ///         .with_repeater(kind, 1, TimeUnit::Week)
+    /// };
+    /// assert_eq!(
+    ///     ts(RepeaterType::Cumulate).next_repeat(now.0, now.1).unwrap().to_string(),
+    ///     "<2024-05-08 Wed 09:00 +1w>"
+    /// );
+    /// assert_eq!(
+    ///     ts(RepeaterType::CatchUp).next_repeat(now.0, now.1).unwrap().to_string(),
+    ///     "<2024-05-22 Wed 09:00 ++1w>"
+    /// );
+    /// assert_eq!(
+    ///     ts(RepeaterType::Restart).next_repeat(now.0, now.1).unwrap().to_string(),
+    ///     "<2024-05-27 Mon 09:00 .+1w>"
+    /// );
+    /// ```
+    pub fn next_repeat(&self, date: TimestampDate, time: TimestampTime) -> Option<TimestampValue> {
+        let repeater = self.repeater?;
+        let value = repeater.value as i64;
+
+        Some(match repeater.kind {
+            RepeaterType::Cumulate => self.shifted(repeater.unit, value),
+            RepeaterType::CatchUp => {
+                let now = date.to_days() * 1440 + time.minutes();
+                let mut shifted = self.shifted(repeater.unit, value);
+                let mut n = 1;
+                while value > 0 && shifted.is_before(now, repeater.unit == TimeUnit::Hour) {
+                    n += 1;
+                    shifted = self.shifted(repeater.unit, n * value);
+                }
+                shifted
+            }
+            RepeaterType::Restart => {
+                let restarted = if repeater.unit == TimeUnit::Hour {
+                    let start = self.start_date.to_days() * 1440
+                        + self.start_time.map_or(0, TimestampTime::minutes);
+                    self.shift_minutes(date.to_days() * 1440 + time.minutes() - start)
+                } else {
+                    self.shifted(TimeUnit::Day, date.to_days() - self.start_date.to_days())
+                };
+                restarted.shifted(repeater.unit, value)
+            }
+        })
+    }
+
+    /// Returns `true` if start of this timestamp isn't after given minutes
+    /// since epoch, or given day if `with_time` is false
+    fn is_before(&self, minutes: i64, with_time: bool) -> bool {
+        if with_time {
+            self.start_date.to_days() * 1440 + self.start_time.map_or(0, TimestampTime::minutes)
+                <= minutes
+        } else {
+            self.start_date.to_days() <= minutes.div_euclid(1440)
+        }
+    }
+
+    /// Shifts start and end of this timestamp by given minutes
+    fn shift_minutes(&self, minutes: i64) -> TimestampValue {
+        let mut shifted = self.clone();
+
+        let start = self.start_date.to_days() * 1440
+            + self.start_time.map_or(0, TimestampTime::minutes)
+            + minutes;
+        shifted.start_date = TimestampDate::from_days(start.div_euclid(1440));
+        shifted.start_time = self
+            .start_time
+            .map(|_| TimestampTime::from_minutes(start.rem_euclid(1440)));
+
+        let end_date = self.end_date.unwrap_or(self.start_date);
+        if self.end_date.is_some() || self.end_time.is_some() {
+            let end = end_date.to_days() * 1440
+                + self.end_time.map_or(0, TimestampTime::minutes)
+                + minutes;
+            let end_date = TimestampDate::from_days(end.div_euclid(1440));
+            shifted.end_date =
+                (end_date != shifted.start_date || self.end_date.is_some()).then_some(end_date);
+            shifted.end_time = self
+                .end_time
+                .map(|_| TimestampTime::from_minutes(end.rem_euclid(1440)));
+        }
+
+        shifted
+    }
+
+    /// Shifts start and end of this timestamp by `amount` units
+    fn shifted(&self, unit: TimeUnit, amount: i64) -> TimestampValue {
+        let days = match unit {
+            TimeUnit::Hour => return self.shift_minutes(amount * 60),
+            TimeUnit::Day => amount,
+            TimeUnit::Week => amount * 7,
+            TimeUnit::Month => {
+                self.start_date.add_months(amount).to_days() - self.start_date.to_days()
+            }
+            TimeUnit::Year => {
+                self.start_date.add_months(amount * 12).to_days() - self.start_date.to_days()
+            }
+        };
+
+        let mut shifted = self.clone();
+        shifted.start_date = self.start_date.add_days(days);
+        shifted.end_date = self.end_date.map(|end| end.add_days(days));
+        shifted
+    }
+
+    fn fmt_part(
+        &self,
+        f: &mut fmt::Formatter,
+        date: TimestampDate,
+        time: Option<TimestampTime>,
+        end_time: Option<TimestampTime>,
+    ) -> fmt::Result {
+        let (open, close) = if self.active { ('<', '>') } else { ('[', ']') };
+
+        write!(
+            f,
+            "{open}{:04}-{:02}-{:02} {}",
+            date.year,
+            date.month,
+            date.day,
+            date.day_name()
+        )?;
+        if let Some(time) = time {
+            write!(f, " {time}")?;
+            if let Some(end_time) = end_time {
+                write!(f, "-{end_time}")?;
+            }
+        }
+        if let Some(repeater) = &self.repeater {
+            let mark = match repeater.kind {
+                RepeaterType::Cumulate => "+",
+                RepeaterType::CatchUp => "++",
+                RepeaterType::Restart => ".+",
+            };
+            write!(f, " {mark}{}{}", repeater.value, repeater.unit)?;
+        }
+        if let Some(warning) = &self.warning {
+            let mark = match warning.kind {
+                DelayType::All => "-",
+                DelayType::First => "--",
+            };
+            write!(f, " {mark}{}{}", warning.value, warning.unit)?;
+        }
+        write!(f, "{close}")
+    }
+}
+
+/// Iterator over occurrences of a timestamp, see [`TimestampValue::occurrences`]
+#[derive(Debug, Clone)]
+pub struct Occurrences {
+    value: TimestampValue,
+    from: TimestampDate,
+    to: TimestampDate,
+    next: Option<i64>,
+}
+
+impl Iterator for Occurrences {
+    type Item = TimestampValue;
+
+    fn next(&mut self) -> Option<TimestampValue> {
+        loop {
+            let n = self.next?;
+            let occurrence = match self.value.repeater {
+                Some(repeater) if repeater.value > 0 => {
+                    self.next = Some(n + 1);
+                    self.value.shifted(repeater.unit, n * repeater.value as i64)
+                }
+                _ => {
+                    self.next = None;
+                    self.value.clone()
+                }
+            };
+
+            if occurrence.start_date > self.to {
+                self.next = None;
+                return None;
+            }
+            if occurrence.end_date.unwrap_or(occurrence.start_date) >= self.from {
+                return Some(occurrence);
+            }
+        }
+    }
+}
+
+impl fmt::Display for TimestampValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.end_date {
+            Some(end_date) => {
+                self.fmt_part(f, self.start_date, self.start_time, None)?;
+                write!(f, "--")?;
+                self.fmt_part(f, end_date, self.end_time, None)
+            }
+            None => self.fmt_part(f, self.start_date, self.start_time, self.end_time),
+        }
+    }
+}
+
+impl fmt::Display for TimestampTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TimeUnit::Hour => "h",
+            TimeUnit::Day => "d",
+            TimeUnit::Week => "w",
+            TimeUnit::Month => "m",
+            TimeUnit::Year => "y",
+        })
+    }
+}
+
+/// Number of days since 1970-01-01 of given proleptic Gregorian date
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64 - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Proleptic Gregorian date of given number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year as i32, month, day)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
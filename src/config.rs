@@ -1,4 +1,7 @@
-use crate::syntax::document::document_node;
+use rowan::{ast::AstNode, GreenNode};
+
+use crate::ast::{Document, Keyword, RadioTarget};
+use crate::syntax::{document::document_node, SyntaxNode};
 use crate::Org;
 
 #[derive(Clone, Debug)]
@@ -45,18 +48,262 @@ pub struct ParseConfig {
     ///
     /// Equivalent to [`org-element-affiliated-keywords`](https://git.sr.ht/~bzg/org-mode/tree/6f960f3c6a4dfe137fbd33fef9f7dadfd229600c/item/lisp/org-element.el#L331)
     pub affiliated_keywords: Vec<String>,
+
+    /// Minimum number of stars for a headline to be parsed as inlinetask
+    ///
+    /// Equivalent to `org-inlinetask-min-level`
+    pub inlinetask_min_level: usize,
+
+    /// Link types recognized in plain links and angle links
+    ///
+    /// Equivalent to the keys of `org-link-parameters`
+    pub link_types: Vec<String>,
+
+    /// Radio targets used for recognizing radio links
+    ///
+    /// [`ParseConfig::parse`] collects radio targets from input before
+    /// parsing, and appends them to this list.
+    pub radio_targets: Vec<String>,
+
+    /// Link abbreviations, in addition to the ones defined by `#+LINK` in document
+    ///
+    /// Equivalent to `org-link-abbrev-alist`
+    pub link_abbreviations: Vec<(String, String)>,
+
+    /// Allow single alphabetical characters as bullets of ordered lists,
+    /// e.g. `a.` or `B)`
+    ///
+    /// Equivalent to `org-list-allow-alphabetical`
+    pub list_allow_alphabetical: bool,
 }
 
 impl ParseConfig {
     /// Parses input with current config
+    ///
+    /// In-buffer settings (e.g. `#+TODO`) and radio targets can only be known
+    /// after parsing, so input will be parsed again if they change current config.
+    ///
+    /// ```rust
+    /// use orgize::{ParseConfig, ast::Headline};
+    ///
+    /// let org = ParseConfig::default().parse("#+TODO: NEXT(n) | DONE\n* NEXT a\n* TODO b");
+    /// assert_eq!(org.config().todo_keywords.0, ["NEXT"]);
+    ///
+    /// let headlines: Vec<_> = org.document().headlines().collect();
+    /// assert_eq!(headlines[0].todo_keyword().unwrap(), "NEXT");
+    /// assert!(headlines[1].todo_keyword().is_none());
+    /// ```
     pub fn parse(self, input: impl AsRef<str>) -> Org {
-        let input = (input.as_ref(), &self).into();
-        let node = document_node(input).unwrap().1;
+        self.parse_with(input.as_ref(), Document::todo_sequences)
+    }
+
+    /// Parses input like [`ParseConfig::parse`], todo keywords sequences of
+    /// document are returned by `todo_sequences`
+    pub(crate) fn parse_with(
+        mut self,
+        input: &str,
+        todo_sequences: impl Fn(&Document) -> Vec<TodoSequence>,
+    ) -> Org {
+        let mut green = self.parse_document(input);
+
+        if self.apply_in_buffer_settings(&green, todo_sequences) {
+            green = self.parse_document(input);
+        }
 
         Org {
             config: self,
-            green: node.into_node().unwrap(),
+            green,
+        }
+    }
+
+    fn parse_document(&self, input: &str) -> GreenNode {
+        let node = document_node((input, self).into()).unwrap().1;
+        node.into_node().unwrap()
+    }
+
+    /// Updates config using in-buffer settings and radio targets found in document,
+    /// returns `true` if config was changed
+    fn apply_in_buffer_settings(
+        &mut self,
+        green: &GreenNode,
+        todo_sequences: impl Fn(&Document) -> Vec<TodoSequence>,
+    ) -> bool {
+        let document = Document {
+            syntax: SyntaxNode::new_root(green.clone()),
+        };
+
+        let mut changed = false;
+
+        // like emacs, in-buffer todo keywords replace the default ones
+        let sequences = todo_sequences(&document);
+        if !sequences.is_empty() {
+            let todo_keywords = TodoSequence::keywords(&sequences);
+            changed |= self.todo_keywords != todo_keywords;
+            self.todo_keywords = todo_keywords;
         }
+
+        for target in document.syntax.descendants().filter_map(RadioTarget::cast) {
+            let target = target.value();
+            if !self.radio_targets.contains(&target) {
+                self.radio_targets.push(target);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+/// Type of todo keywords sequence
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TodoSequenceType {
+    /// Declared by `#+TODO` or `#+SEQ_TODO`, keywords are workflow states
+    Sequence,
+    /// Declared by `#+TYP_TODO`, keywords are types of tasks
+    Type,
+}
+
+/// Logging action when entering or leaving a todo state
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TodoLog {
+    /// `!`, records a timestamp
+    Timestamp,
+    /// `@`, records a note with timestamp
+    Note,
+}
+
+/// Todo keyword with its fast-access key and logging settings, e.g. `WAIT(w@/!)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoKeyword {
+    pub name: String,
+    /// Fast-access key, e.g. `w` in `WAIT(w@/!)`
+    pub key: Option<char>,
+    /// Logging action when entering this state, e.g. `@` in `WAIT(w@/!)`
+    pub log_enter: Option<TodoLog>,
+    /// Logging action when leaving this state, e.g. `!` in `WAIT(w@/!)`
+    pub log_leave: Option<TodoLog>,
+}
+
+impl TodoKeyword {
+    /// Parses a todo keyword declaration
+    ///
+    /// ```rust
+    /// use orgize::config::{TodoKeyword, TodoLog};
+    ///
+    /// let kw = TodoKeyword::parse("WAIT(w@/!)");
+    /// assert_eq!(kw.name, "WAIT");
+    /// assert_eq!(kw.key, Some('w'));
+    /// assert_eq!(kw.log_enter, Some(TodoLog::Note));
+    /// assert_eq!(kw.log_leave, Some(TodoLog::Timestamp));
+    ///
+    /// let kw = TodoKeyword::parse("DONE(!)");
+    /// assert_eq!((kw.key, kw.log_enter, kw.log_leave), (None, Some(TodoLog::Timestamp), None));
+    ///
+    /// let kw = TodoKeyword::parse("NEXT");
+    /// assert_eq!((kw.name.as_str(), kw.key, kw.log_enter), ("NEXT", None, None));
+    /// ```
+    pub fn parse(s: &str) -> TodoKeyword {
+        let (name, settings) = match s.strip_suffix(')').and_then(|s| s.split_once('(')) {
+            Some((name, settings)) if !name.is_empty() => (name, settings),
+            _ => (s, ""),
+        };
+
+        let key = settings
+            .chars()
+            .next()
+            .filter(|c| !matches!(c, '@' | '!' | '/'));
+
+        let settings = &settings[key.map_or(0, char::len_utf8)..];
+        let (enter, leave) = settings.split_once('/').unwrap_or((settings, ""));
+
+        let log = |s: &str| match s {
+            "!" => Some(TodoLog::Timestamp),
+            "@" | "@!" => Some(TodoLog::Note),
+            _ => None,
+        };
+
+        TodoKeyword {
+            name: name.to_string(),
+            key,
+            log_enter: log(enter),
+            log_leave: log(leave),
+        }
+    }
+}
+
+/// Todo keywords sequence declared by `#+TODO`, `#+SEQ_TODO` or `#+TYP_TODO`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoSequence {
+    pub ty: TodoSequenceType,
+    pub todo: Vec<TodoKeyword>,
+    pub done: Vec<TodoKeyword>,
+}
+
+impl TodoSequence {
+    /// Parses a todo keywords sequence from keyword value
+    ///
+    /// Keywords after `|` are done keywords. If `|` is missing, the last
+    /// keyword will be the only done keyword.
+    ///
+    /// ```rust
+    /// use orgize::config::{TodoSequence, TodoSequenceType};
+    ///
+    /// let seq = TodoSequence::parse(TodoSequenceType::Sequence, "NEXT WAIT(w@/!) | DONE(d!) CANCELLED").unwrap();
+    /// assert_eq!(seq.todo.iter().map(|k| &k.name).collect::<Vec<_>>(), ["NEXT", "WAIT"]);
+    /// assert_eq!(seq.done.iter().map(|k| &k.name).collect::<Vec<_>>(), ["DONE", "CANCELLED"]);
+    /// assert_eq!(seq.todo[1].key, Some('w'));
+    ///
+    /// let seq = TodoSequence::parse(TodoSequenceType::Type, "Fred Sara Lucy").unwrap();
+    /// assert_eq!(seq.todo.iter().map(|k| &k.name).collect::<Vec<_>>(), ["Fred", "Sara"]);
+    /// assert_eq!(seq.done.iter().map(|k| &k.name).collect::<Vec<_>>(), ["Lucy"]);
+    ///
+    /// assert!(TodoSequence::parse(TodoSequenceType::Sequence, " ").is_none());
+    /// ```
+    pub fn parse(ty: TodoSequenceType, value: &str) -> Option<TodoSequence> {
+        let words: Vec<_> = value.split_whitespace().collect();
+
+        let (todo, done) = match words.iter().position(|w| *w == "|") {
+            Some(idx) => (&words[..idx], &words[idx + 1..]),
+            None => words.split_at(words.len().checked_sub(1)?),
+        };
+
+        if todo.is_empty() && done.is_empty() {
+            return None;
+        }
+
+        Some(TodoSequence {
+            ty,
+            todo: todo.iter().map(|w| TodoKeyword::parse(w)).collect(),
+            done: done.iter().map(|w| TodoKeyword::parse(w)).collect(),
+        })
+    }
+
+    /// Parses a todo keywords sequence from `#+TODO`, `#+SEQ_TODO` or `#+TYP_TODO`
+    pub(crate) fn from_keyword(keyword: &Keyword) -> Option<TodoSequence> {
+        let key = keyword.key();
+        let ty = if key.eq_ignore_ascii_case("TODO") || key.eq_ignore_ascii_case("SEQ_TODO") {
+            TodoSequenceType::Sequence
+        } else if key.eq_ignore_ascii_case("TYP_TODO") {
+            TodoSequenceType::Type
+        } else {
+            return None;
+        };
+        TodoSequence::parse(ty, &keyword.value())
+    }
+
+    /// Names of todo and done keywords in given sequences, in the form of
+    /// [`ParseConfig::todo_keywords`]
+    pub(crate) fn keywords(sequences: &[TodoSequence]) -> (Vec<String>, Vec<String>) {
+        (
+            sequences
+                .iter()
+                .flat_map(|seq| seq.todo.iter().map(|kw| kw.name.clone()))
+                .collect(),
+            sequences
+                .iter()
+                .flat_map(|seq| seq.done.iter().map(|kw| kw.name.clone()))
+                .collect(),
+        )
     }
 }
 
@@ -82,6 +329,198 @@ impl Default for ParseConfig {
                 "SRCNAME".into(),
                 "TBLNAME".into(),
             ],
+            inlinetask_min_level: 15,
+            link_types: vec![
+                "attachment".into(),
+                "bbdb".into(),
+                "bibtex".into(),
+                "docview".into(),
+                "doi".into(),
+                "elisp".into(),
+                "eww".into(),
+                "file".into(),
+                "file+emacs".into(),
+                "file+sys".into(),
+                "ftp".into(),
+                "gnus".into(),
+                "help".into(),
+                "http".into(),
+                "https".into(),
+                "id".into(),
+                "info".into(),
+                "irc".into(),
+                "mailto".into(),
+                "mhe".into(),
+                "news".into(),
+                "rmail".into(),
+                "shell".into(),
+                "w3m".into(),
+            ],
+            radio_targets: vec![],
+            link_abbreviations: vec![],
+            list_allow_alphabetical: false,
+        }
+    }
+}
+
+/// Initial visibility, set by `#+STARTUP`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StartupVisibility {
+    /// `overview`, top-level headlines only
+    Overview,
+    /// `content`, all headlines
+    Content,
+    /// `showall`, no folding on any entry
+    ShowAll,
+    /// `show2levels` .. `show5levels`, headlines up to given level
+    ShowLevels(usize),
+    /// `showeverything`, show even drawer contents
+    ShowEverything,
+}
+
+/// Startup options, parsed from `#+STARTUP` keywords
+///
+/// Default values are the same as emacs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartupOptions {
+    /// Equivalent to `org-startup-folded`
+    pub visibility: StartupVisibility,
+    /// `indent` or `noindent`, equivalent to `org-startup-indented`
+    pub indent: bool,
+    /// `num` or `nonum`, equivalent to `org-startup-numerated`
+    pub numerate: bool,
+    /// `align` or `noalign`, equivalent to `org-startup-align-all-tables`
+    pub align: bool,
+    /// `inlineimages` or `noinlineimages`, equivalent to `org-startup-with-inline-images`
+    pub inline_images: bool,
+    /// `latexpreview` or `nolatexpreview`, equivalent to `org-startup-with-latex-preview`
+    pub latex_preview: bool,
+    /// `hidestars` or `showstars`, equivalent to `org-hide-leading-stars`
+    pub hide_stars: bool,
+    /// `odd` or `oddeven`, equivalent to `org-odd-levels-only`
+    pub odd_levels_only: bool,
+    /// `hideblocks` or `nohideblocks`, equivalent to `org-hide-block-startup`
+    pub hide_blocks: bool,
+    /// `hidedrawers` or `nohidedrawers`, equivalent to `org-cycle-hide-drawer-startup`
+    pub hide_drawers: bool,
+    /// `entitiespretty` or `entitiesplain`, equivalent to `org-pretty-entities`
+    pub pretty_entities: bool,
+    /// `logdone`, `lognotedone` or `nologdone`, equivalent to `org-log-done`
+    pub log_done: Option<TodoLog>,
+    /// `logrepeat`, `lognoterepeat` or `nologrepeat`, equivalent to `org-log-repeat`
+    pub log_repeat: Option<TodoLog>,
+    /// `logreschedule`, `lognotereschedule` or `nologreschedule`, equivalent to `org-log-reschedule`
+    pub log_reschedule: Option<TodoLog>,
+    /// `logredeadline`, `lognoteredeadline` or `nologredeadline`, equivalent to `org-log-redeadline`
+    pub log_redeadline: Option<TodoLog>,
+    /// `logrefile`, `lognoterefile` or `nologrefile`, equivalent to `org-log-refile`
+    pub log_refile: Option<TodoLog>,
+    /// `lognoteclock-out` or `nolognoteclock-out`, equivalent to `org-log-note-clock-out`
+    pub log_note_clock_out: bool,
+    /// `logdrawer` or `nologdrawer`, equivalent to `org-log-into-drawer`
+    pub log_into_drawer: bool,
+    /// `logstatesreversed` or `nologstatesreversed`, equivalent to `org-log-states-order-reversed`
+    pub log_states_reversed: bool,
+}
+
+impl Default for StartupOptions {
+    fn default() -> Self {
+        StartupOptions {
+            visibility: StartupVisibility::ShowEverything,
+            indent: false,
+            numerate: false,
+            align: false,
+            inline_images: false,
+            latex_preview: false,
+            hide_stars: false,
+            odd_levels_only: false,
+            hide_blocks: false,
+            hide_drawers: true,
+            pretty_entities: false,
+            log_done: None,
+            log_repeat: Some(TodoLog::Timestamp),
+            log_reschedule: None,
+            log_redeadline: None,
+            log_refile: None,
+            log_note_clock_out: false,
+            log_into_drawer: false,
+            log_states_reversed: true,
+        }
+    }
+}
+
+impl StartupOptions {
+    /// Updates options with the value of `#+STARTUP` keyword
+    ///
+    /// Unknown options are ignored.
+    ///
+    /// ```rust
+    /// use orgize::config::{StartupOptions, StartupVisibility, TodoLog};
+    ///
+    /// let mut options = StartupOptions::default();
+    /// options.apply("overview hidestars lognotedone nologrepeat unknown");
+    /// assert_eq!(options.visibility, StartupVisibility::Overview);
+    /// assert!(options.hide_stars);
+    /// assert_eq!(options.log_done, Some(TodoLog::Note));
+    /// assert_eq!(options.log_repeat, None);
+    ///
+    /// options.apply("show3levels");
+    /// assert_eq!(options.visibility, StartupVisibility::ShowLevels(3));
+    /// ```
+    pub fn apply(&mut self, value: &str) {
+        for option in value.split_whitespace() {
+            match option {
+                "overview" | "fold" => self.visibility = StartupVisibility::Overview,
+                "content" => self.visibility = StartupVisibility::Content,
+                "showall" | "nofold" => self.visibility = StartupVisibility::ShowAll,
+                "showeverything" => self.visibility = StartupVisibility::ShowEverything,
+                "show2levels" => self.visibility = StartupVisibility::ShowLevels(2),
+                "show3levels" => self.visibility = StartupVisibility::ShowLevels(3),
+                "show4levels" => self.visibility = StartupVisibility::ShowLevels(4),
+                "show5levels" => self.visibility = StartupVisibility::ShowLevels(5),
+                "indent" => self.indent = true,
+                "noindent" => self.indent = false,
+                "num" => self.numerate = true,
+                "nonum" => self.numerate = false,
+                "align" => self.align = true,
+                "noalign" => self.align = false,
+                "inlineimages" => self.inline_images = true,
+                "noinlineimages" => self.inline_images = false,
+                "latexpreview" => self.latex_preview = true,
+                "nolatexpreview" => self.latex_preview = false,
+                "hidestars" => self.hide_stars = true,
+                "showstars" => self.hide_stars = false,
+                "odd" => self.odd_levels_only = true,
+                "oddeven" => self.odd_levels_only = false,
+                "hideblocks" => self.hide_blocks = true,
+                "nohideblocks" => self.hide_blocks = false,
+                "hidedrawers" => self.hide_drawers = true,
+                "nohidedrawers" => self.hide_drawers = false,
+                "entitiespretty" => self.pretty_entities = true,
+                "entitiesplain" => self.pretty_entities = false,
+                "logdone" => self.log_done = Some(TodoLog::Timestamp),
+                "lognotedone" => self.log_done = Some(TodoLog::Note),
+                "nologdone" => self.log_done = None,
+                "logrepeat" => self.log_repeat = Some(TodoLog::Timestamp),
+                "lognoterepeat" => self.log_repeat = Some(TodoLog::Note),
+                "nologrepeat" => self.log_repeat = None,
+                "logreschedule" => self.log_reschedule = Some(TodoLog::Timestamp),
+                "lognotereschedule" => self.log_reschedule = Some(TodoLog::Note),
+                "nologreschedule" => self.log_reschedule = None,
+                "logredeadline" => self.log_redeadline = Some(TodoLog::Timestamp),
+                "lognoteredeadline" => self.log_redeadline = Some(TodoLog::Note),
+                "nologredeadline" => self.log_redeadline = None,
+                "logrefile" => self.log_refile = Some(TodoLog::Timestamp),
+                "lognoterefile" => self.log_refile = Some(TodoLog::Note),
+                "nologrefile" => self.log_refile = None,
+                "lognoteclock-out" => self.log_note_clock_out = true,
+                "nolognoteclock-out" => self.log_note_clock_out = false,
+                "logdrawer" => self.log_into_drawer = true,
+                "nologdrawer" => self.log_into_drawer = false,
+                "logstatesreversed" => self.log_states_reversed = true,
+                "nologstatesreversed" => self.log_states_reversed = false,
+                _ => {}
+            }
         }
     }
 }
@@ -0,0 +1,1048 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use rowan::ast::AstNode;
+
+use crate::ast::{Citation, Document};
+use crate::include::Loader;
+use crate::SyntaxElement;
+
+/// Bibliography entry, read from BibTeX or CSL-JSON
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BibEntry {
+    /// Citation key
+    pub key: String,
+    /// Entry type, e.g. `article` or `book`
+    pub entry_type: String,
+    pub authors: Vec<Name>,
+    pub title: Option<String>,
+    pub year: Option<String>,
+    /// Journal or book title
+    pub container_title: Option<String>,
+    pub publisher: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub doi: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Author name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Name {
+    /// Family name, or the full name of institutions
+    pub family: String,
+    pub given: Option<String>,
+}
+
+/// A collection of bibliography entries
+///
+/// ```rust
+/// use orgize::export::Bibliography;
+///
+/// let mut bibliography = Bibliography::from_bibtex(r#"
+/// @article{doe2020,
+///   author = {Doe, John and Jane Smith},
+///   title = {On {Org} Mode},
+///   year = 2020,
+/// }"#);
+/// bibliography.extend(Bibliography::from_csl_json(r#"[
+///   { "id": "roe", "type": "book", "title": "Plain Text", "issued": { "date-parts": [[2019, 5]] } }
+/// ]"#).unwrap());
+///
+/// let entry = bibliography.get("doe2020").unwrap();
+/// assert_eq!(entry.authors[1].family, "Smith");
+/// assert_eq!(entry.title.as_deref(), Some("On Org Mode"));
+/// assert_eq!(entry.year.as_deref(), Some("2020"));
+/// assert_eq!(bibliography.get("roe").unwrap().year.as_deref(), Some("2019"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Bibliography {
+    entries: Vec<BibEntry>,
+}
+
+impl Bibliography {
+    /// Reads entries from BibTeX source
+    ///
+    /// `@string` abbreviations and `#` concatenation are supported,
+    /// `@comment` and `@preamble` are ignored.
+    pub fn from_bibtex(s: &str) -> Bibliography {
+        Bibliography {
+            entries: BibtexParser::new(s).entries(),
+        }
+    }
+
+    /// Reads entries from CSL-JSON source, returns `None` if it's not a valid json array
+    pub fn from_csl_json(s: &str) -> Option<Bibliography> {
+        let mut parser = JsonParser { s, pos: 0 };
+        let Json::Array(items) = parser.value()? else {
+            return None;
+        };
+        Some(Bibliography {
+            entries: items.iter().filter_map(csl_entry).collect(),
+        })
+    }
+
+    /// Reads bibliography files declared by `#+BIBLIOGRAPHY` in document
+    ///
+    /// Files ending with `.json` are read as CSL-JSON, others as BibTeX.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::Bibliography, include::MemoryLoader};
+    ///
+    /// let mut loader = MemoryLoader::default();
+    /// loader.insert("refs.bib", "@book{doe, title = {Org}}");
+    /// loader.insert("refs.json", r#"[{ "id": "roe", "title": "Plain Text" }]"#);
+    ///
+    /// let org = Org::parse("#+BIBLIOGRAPHY: refs.bib\n#+BIBLIOGRAPHY: refs.json");
+    /// let bibliography = Bibliography::load(&org.document(), &loader).unwrap();
+    /// assert_eq!(bibliography.iter().count(), 2);
+    /// ```
+    pub fn load<L: Loader + ?Sized>(document: &Document, loader: &L) -> io::Result<Bibliography> {
+        let mut bibliography = Bibliography::default();
+
+        for file in document.bibliographies() {
+            let content = loader.load(Path::new(&file))?;
+            if file.to_ascii_lowercase().ends_with(".json") {
+                bibliography.extend(Bibliography::from_csl_json(&content).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{file} is not CSL-JSON"),
+                    )
+                })?);
+            } else {
+                bibliography.extend(Bibliography::from_bibtex(&content));
+            }
+        }
+
+        Ok(bibliography)
+    }
+
+    /// Appends entries from another bibliography
+    pub fn extend(&mut self, other: Bibliography) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Returns entry with given key
+    pub fn get(&self, key: &str) -> Option<&BibEntry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BibEntry> {
+        self.entries.iter()
+    }
+}
+
+/// Style of rendered citations, set by the third parameter of `#+CITE_EXPORT`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CitationStyle {
+    /// `(Doe, 2020)`, references are sorted by author and year
+    #[default]
+    AuthorYear,
+    /// `[1]`, references are sorted by the order of citation
+    Numeric,
+}
+
+/// Part of a rendered citation
+#[derive(Debug, Clone)]
+pub enum CitationPart {
+    /// Plain text, like brackets and separators
+    Text(&'static str),
+    /// Objects in prefix or suffix
+    Objects(Vec<SyntaxElement>),
+    /// Reference to a bibliography entry
+    Reference { key: String, label: String },
+}
+
+/// Entry in the generated reference list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub key: String,
+    /// Label in numeric style, e.g. `[1]`
+    pub label: Option<String>,
+    pub text: String,
+}
+
+/// Resolves citation keys and renders citations and reference list
+///
+/// Citation variants `t` (text), `a` (author), `na` (noauthor) and `n` (nocite)
+/// are supported in author-year style.
+///
+/// ```rust
+/// use orgize::{Org, ast::Citation, export::{Bibliography, CitationPart, CitationProcessor}};
+///
+/// let org = Org::parse("[cite:@doe] [cite/t:@doe] [cite/n:@*]\n#+CITE_EXPORT: basic nil author-year");
+/// let mut processor = CitationProcessor::new(Bibliography::from_bibtex(
+///     "@book{doe, author = {Doe, John}, title = {Org}, year = 2020}\n@book{roe, title = {Text}}",
+/// ));
+/// processor.prepare(&org.document());
+///
+/// let labels = |c: &Citation, p: &mut CitationProcessor| -> Vec<String> {
+///     p.process(c).into_iter().map(|part| match part {
+///         CitationPart::Text(s) => s.to_string(),
+///         CitationPart::Reference { label, .. } => label,
+///         CitationPart::Objects(o) => o.iter().map(|e| e.to_string()).collect(),
+///     }).collect()
+/// };
+///
+/// let citation = org.first_node::<Citation>().unwrap();
+/// assert_eq!(labels(&citation, &mut processor).concat(), "(Doe, 2020)");
+///
+/// let references = processor.references();
+/// assert_eq!(references.len(), 2);
+/// assert_eq!(references[0].text, "Doe, John (2020). Org.");
+/// assert_eq!(references[1].text, "Text.");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CitationProcessor {
+    pub bibliography: Bibliography,
+    pub style: CitationStyle,
+    /// Cited keys, in the order of first citation
+    cited: Vec<String>,
+}
+
+impl CitationProcessor {
+    pub fn new(bibliography: Bibliography) -> CitationProcessor {
+        CitationProcessor {
+            bibliography,
+            ..Default::default()
+        }
+    }
+
+    /// Reads citation style from `#+CITE_EXPORT` and collects all cited keys in document
+    ///
+    /// Citations have to be collected before rendering, so that numbers are
+    /// assigned in document order and the reference list is complete even if
+    /// `#+PRINT_BIBLIOGRAPHY` appears before citations.
+    pub fn prepare(&mut self, document: &Document) {
+        if let Some(cite_export) = document.cite_export() {
+            self.style = match cite_export.citation_style.as_deref() {
+                Some("numeric") => CitationStyle::Numeric,
+                _ => CitationStyle::AuthorYear,
+            };
+        }
+
+        self.cited.clear();
+        for citation in document.syntax.descendants().filter_map(Citation::cast) {
+            for reference in citation.references() {
+                self.cite(&reference.key());
+            }
+        }
+    }
+
+    /// Records a cited key, returns its number if it's found in bibliography
+    fn cite(&mut self, key: &str) -> Option<usize> {
+        if !self.cited.iter().any(|k| k == key) {
+            self.cited.push(key.to_string());
+        }
+        self.numbered().position(|k| k == key).map(|idx| idx + 1)
+    }
+
+    /// Cited keys found in bibliography, in the order they are numbered
+    ///
+    /// Missing keys and `*` don't take a number.
+    fn numbered(&self) -> impl Iterator<Item = &str> {
+        self.cited
+            .iter()
+            .map(String::as_str)
+            .filter(|k| *k != "*" && self.bibliography.get(k).is_some())
+    }
+
+    /// Renders given citation into parts
+    ///
+    /// Returns an empty vector for `nocite` citations.
+    pub fn process(&mut self, citation: &Citation) -> Vec<CitationPart> {
+        let variant = citation
+            .style()
+            .map(|s| s.split('/').next().unwrap_or_default().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        if matches!(variant.as_str(), "n" | "nocite") {
+            return vec![];
+        }
+
+        let (open, separator, close) = match (self.style, variant.as_str()) {
+            (CitationStyle::Numeric, _) => ("[", ", ", "]"),
+            (_, "t" | "text" | "a" | "author") => ("", "; ", ""),
+            _ => ("(", "; ", ")"),
+        };
+
+        let mut parts = vec![CitationPart::Text(open)];
+
+        if !citation.prefix_raw().is_empty() {
+            parts.push(CitationPart::Objects(citation.prefix().collect()));
+            parts.push(CitationPart::Text(" "));
+        }
+
+        for (idx, reference) in citation.references().enumerate() {
+            if idx != 0 {
+                parts.push(CitationPart::Text(separator));
+            }
+            if !reference.prefix_raw().is_empty() {
+                parts.push(CitationPart::Objects(reference.prefix().collect()));
+            }
+            let key = reference.key().to_string();
+            let label = self.label(&key, &variant);
+            parts.push(CitationPart::Reference { key, label });
+            if !reference.suffix_raw().is_empty() {
+                parts.push(CitationPart::Objects(reference.suffix().collect()));
+            }
+        }
+
+        if !citation.suffix_raw().is_empty() {
+            parts.push(CitationPart::Text(" "));
+            parts.push(CitationPart::Objects(citation.suffix().collect()));
+        }
+
+        parts.push(CitationPart::Text(close));
+        parts.retain(|p| !matches!(p, CitationPart::Text("")));
+        parts
+    }
+
+    fn label(&mut self, key: &str, variant: &str) -> String {
+        let number = self.cite(key);
+
+        if self.style == CitationStyle::Numeric {
+            return number.map_or_else(|| key.to_string(), |n| n.to_string());
+        }
+
+        let Some(entry) = self.bibliography.get(key) else {
+            return key.to_string();
+        };
+
+        let author = short_authors(entry);
+        let year = entry.year.as_deref().unwrap_or("n.d.");
+
+        match variant {
+            "t" | "text" => format!("{author} ({year})"),
+            "a" | "author" => author,
+            "na" | "noauthor" => year.to_string(),
+            _ => format!("{author}, {year}"),
+        }
+    }
+
+    /// Returns the reference list of cited entries
+    ///
+    /// Keys missing in bibliography are ignored. Citing `@*` includes all
+    /// entries in bibliography, the ones not cited elsewhere come last.
+    /// Numbers in numeric style are the same as in citations.
+    pub fn references(&self) -> Vec<Reference> {
+        let mut entries: Vec<&BibEntry> = self
+            .numbered()
+            .filter_map(|k| self.bibliography.get(k))
+            .collect();
+
+        if self.cited.iter().any(|k| k == "*") {
+            for entry in self.bibliography.iter() {
+                if !entries.iter().any(|e| e.key == entry.key) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        let mut entries: Vec<(usize, &BibEntry)> = entries.into_iter().enumerate().collect();
+
+        if self.style == CitationStyle::AuthorYear {
+            entries.sort_by_cached_key(|(_, e)| {
+                (
+                    short_authors(e).to_lowercase(),
+                    e.year.clone().unwrap_or_default(),
+                )
+            });
+        }
+
+        entries
+            .into_iter()
+            .map(|(idx, entry)| Reference {
+                key: entry.key.clone(),
+                label: (self.style == CitationStyle::Numeric).then(|| format!("[{}]", idx + 1)),
+                text: format_entry(entry),
+            })
+            .collect()
+    }
+}
+
+/// `Doe`, `Doe and Smith` or `Doe et al.`, falls back to title or key
+fn short_authors(entry: &BibEntry) -> String {
+    match entry.authors.as_slice() {
+        [] => entry.title.clone().unwrap_or_else(|| entry.key.clone()),
+        [a] => a.family.clone(),
+        [a, b] => format!("{} and {}", a.family, b.family),
+        [a, ..] => format!("{} et al.", a.family),
+    }
+}
+
+/// Formats entry as `Authors (Year). Title. Container, Volume(Issue), Pages. Publisher. Link`
+fn format_entry(entry: &BibEntry) -> String {
+    fn sentence(output: &mut String, s: &str) {
+        if s.is_empty() {
+            return;
+        }
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        output.push_str(s);
+        if !s.ends_with(['.', '?', '!']) {
+            output.push('.');
+        }
+    }
+
+    let mut output = String::new();
+
+    let names: Vec<_> = entry
+        .authors
+        .iter()
+        .map(|n| match &n.given {
+            Some(given) => format!("{}, {}", n.family, given),
+            None => n.family.clone(),
+        })
+        .collect();
+    let mut authors = match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, init)) => format!("{} and {}", init.join(", "), last),
+        None => String::new(),
+    };
+    if let Some(year) = &entry.year {
+        if !authors.is_empty() {
+            authors.push(' ');
+        }
+        authors += &format!("({year})");
+    }
+    sentence(&mut output, &authors);
+
+    sentence(&mut output, entry.title.as_deref().unwrap_or_default());
+
+    if let Some(container) = &entry.container_title {
+        let mut s = container.clone();
+        if let Some(volume) = &entry.volume {
+            s += &format!(", {volume}");
+            if let Some(issue) = &entry.issue {
+                s += &format!("({issue})");
+            }
+        }
+        if let Some(pages) = &entry.pages {
+            s += &format!(", {pages}");
+        }
+        sentence(&mut output, &s);
+    }
+
+    sentence(&mut output, entry.publisher.as_deref().unwrap_or_default());
+
+    let link = match (&entry.doi, &entry.url) {
+        (Some(doi), _) => Some(format!("https://doi.org/{doi}")),
+        (None, Some(url)) => Some(url.clone()),
+        _ => None,
+    };
+    if let Some(link) = link {
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        output += &link;
+    }
+
+    output
+}
+
+struct BibtexParser<'a> {
+    s: &'a str,
+    pos: usize,
+    /// `@string` abbreviations, values are kept raw
+    strings: HashMap<String, String>,
+}
+
+impl<'a> BibtexParser<'a> {
+    fn new(s: &'a str) -> Self {
+        let months = [
+            "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+        ];
+        BibtexParser {
+            s,
+            pos: 0,
+            strings: months
+                .iter()
+                .enumerate()
+                .map(|(i, m)| (m.to_string(), (i + 1).to_string()))
+                .collect(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        self.pos = self.s.len() - self.rest().trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn identifier(&mut self) -> &'a str {
+        self.skip_ws();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || "{}(),=#\"".contains(c))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Takes contents until the closing delimiter at brace depth zero,
+    /// the delimiter itself is consumed
+    fn delimited(&mut self, close: char) -> &'a str {
+        let rest = self.rest();
+        let mut depth = 0;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth > 0 => depth -= 1,
+                _ if c == close && depth == 0 => {
+                    self.pos += i + c.len_utf8();
+                    return &rest[..i];
+                }
+                _ => {}
+            }
+        }
+        self.pos = self.s.len();
+        rest
+    }
+
+    /// Field value, parts concatenated by `#`
+    fn value(&mut self) -> String {
+        let mut value = String::new();
+        loop {
+            if self.eat('{') {
+                value += self.delimited('}');
+            } else if self.eat('"') {
+                value += self.delimited('"');
+            } else {
+                let ident = self.identifier();
+                match self.strings.get(&ident.to_ascii_lowercase()) {
+                    Some(s) => value += s,
+                    None => value += ident,
+                }
+            }
+            if !self.eat('#') {
+                return value;
+            }
+        }
+    }
+
+    /// `name = value` pairs until the closing delimiter
+    fn fields(&mut self, close: char) -> Vec<(String, String)> {
+        let mut fields = vec![];
+        loop {
+            while self.eat(',') {}
+            if self.eat(close) || self.rest().is_empty() {
+                return fields;
+            }
+            let name = self.identifier().to_ascii_lowercase();
+            if name.is_empty() || !self.eat('=') {
+                // malformed field, skips to the end of entry
+                self.delimited(close);
+                return fields;
+            }
+            let value = self.value();
+            fields.push((name, value));
+        }
+    }
+
+    fn entries(mut self) -> Vec<BibEntry> {
+        let mut entries = vec![];
+
+        while let Some(at) = self.rest().find('@') {
+            self.pos += at + 1;
+            let entry_type = self.identifier().to_ascii_lowercase();
+
+            let close = if self.eat('{') {
+                '}'
+            } else if self.eat('(') {
+                ')'
+            } else {
+                continue;
+            };
+
+            match entry_type.as_str() {
+                "comment" | "preamble" => {
+                    self.delimited(close);
+                }
+                "string" => {
+                    for (name, value) in self.fields(close) {
+                        self.strings.insert(name, value);
+                    }
+                }
+                _ => {
+                    // key ends at the first comma, or at the end of entry without fields
+                    let rest = self.rest();
+                    let len = rest.find([',', close]).unwrap_or(rest.len());
+                    self.pos += len;
+                    let key = rest[..len].trim().to_string();
+                    let fields = self.fields(close);
+                    entries.push(bibtex_entry(key, entry_type, fields));
+                }
+            }
+        }
+
+        entries
+    }
+}
+
+fn bibtex_entry(key: String, entry_type: String, fields: Vec<(String, String)>) -> BibEntry {
+    let mut entry = BibEntry {
+        key,
+        entry_type,
+        ..Default::default()
+    };
+
+    for (name, value) in fields {
+        let clean = clean_latex(&value);
+        match name.as_str() {
+            "author" => entry.authors = bibtex_names(&value),
+            "title" => entry.title = Some(clean),
+            "year" => entry.year = Some(clean),
+            "date" if entry.year.is_none() => entry.year = Some(clean.chars().take(4).collect()),
+            "journal" | "journaltitle" | "booktitle" => entry.container_title = Some(clean),
+            "publisher" | "institution" | "school" | "organization" => {
+                entry.publisher = Some(clean)
+            }
+            "volume" => entry.volume = Some(clean),
+            "number" | "issue" => entry.issue = Some(clean),
+            "pages" => entry.pages = Some(clean.replace("--", "–")),
+            "doi" => entry.doi = Some(clean),
+            "url" => entry.url = Some(clean),
+            _ => {}
+        }
+    }
+
+    entry
+}
+
+/// Splits names by `and` outside of braces
+fn bibtex_names(value: &str) -> Vec<Name> {
+    let mut names = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let words: Vec<_> = value.split_inclusive(char::is_whitespace).collect();
+
+    let mut offset = 0;
+    for word in words {
+        depth += word.matches('{').count() as i32 - word.matches('}').count() as i32;
+        if depth == 0 && word.trim() == "and" {
+            names.push(&value[start..offset]);
+            start = offset + word.len();
+        }
+        offset += word.len();
+    }
+    names.push(&value[start..]);
+
+    names
+        .into_iter()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(|name| {
+            // `{Org Mode Team}` is a single name
+            if name.starts_with('{') && name.ends_with('}') && !name[1..].contains('{') {
+                return Name {
+                    family: clean_latex(name),
+                    given: None,
+                };
+            }
+            match name.split_once(',') {
+                Some((family, given)) => Name {
+                    family: clean_latex(family),
+                    given: Some(clean_latex(given.rsplit(',').next().unwrap_or(given)))
+                        .filter(|g| !g.is_empty()),
+                },
+                None => {
+                    let (given, family) = name.rsplit_once(' ').unwrap_or(("", name));
+                    Name {
+                        family: clean_latex(family),
+                        given: Some(clean_latex(given)).filter(|g| !g.is_empty()),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Removes braces and converts common LaTeX escapes and accents
+fn clean_latex(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '\\' => {
+                let accent = match chars.peek() {
+                    Some('"') => Some('\u{308}'),
+                    Some('\'') => Some('\u{301}'),
+                    Some('`') => Some('\u{300}'),
+                    Some('^') => Some('\u{302}'),
+                    Some('~') => Some('\u{303}'),
+                    _ => None,
+                };
+                match (accent, chars.next()) {
+                    (Some(accent), _) => {
+                        while chars.next_if(|c| *c == '{').is_some() {}
+                        if let Some(base) = chars.next() {
+                            output.push(base);
+                            output.push(accent);
+                        }
+                    }
+                    (None, Some(c)) if !c.is_alphabetic() => output.push(c),
+                    // unknown command, e.g. `\emph`
+                    (None, _) => while chars.next_if(|c| c.is_alphabetic()).is_some() {},
+                }
+            }
+            '~' => output.push('\u{a0}'),
+            _ if c.is_whitespace() => {
+                if !output.ends_with(' ') {
+                    output.push(' ');
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output.trim().to_string()
+}
+
+#[derive(Debug)]
+enum Json {
+    /// `null`, `true` or `false`
+    Literal,
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_string(&self) -> Option<String> {
+        match self {
+            Json::String(s) | Json::Number(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.pos = self.s.len() - self.s[self.pos..].trim_start().len();
+        self.s[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Option<Json> {
+        match self.peek()? {
+            '{' => {
+                self.pos += 1;
+                let mut fields = vec![];
+                if self.eat('}') {
+                    return Some(Json::Object(fields));
+                }
+                loop {
+                    let Json::String(key) = self.value()? else {
+                        return None;
+                    };
+                    if !self.eat(':') {
+                        return None;
+                    }
+                    fields.push((key, self.value()?));
+                    if self.eat('}') {
+                        return Some(Json::Object(fields));
+                    }
+                    if !self.eat(',') {
+                        return None;
+                    }
+                }
+            }
+            '[' => {
+                self.pos += 1;
+                let mut items = vec![];
+                if self.eat(']') {
+                    return Some(Json::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    if self.eat(']') {
+                        return Some(Json::Array(items));
+                    }
+                    if !self.eat(',') {
+                        return None;
+                    }
+                }
+            }
+            '"' => {
+                self.pos += 1;
+                self.string().map(Json::String)
+            }
+            _ => {
+                let rest = &self.s[self.pos..];
+                let len = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && !"+-.".contains(c))
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                match &rest[..len] {
+                    "null" | "true" | "false" => Some(Json::Literal),
+                    n if n.parse::<f64>().is_ok() => Some(Json::Number(n.to_string())),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// String contents after the opening quote
+    fn string(&mut self) -> Option<String> {
+        let mut output = String::new();
+        let mut chars = self.s[self.pos..].char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Some(output);
+                }
+                '\\' => match chars.next()?.1 {
+                    'b' => output.push('\u{8}'),
+                    'f' => output.push('\u{c}'),
+                    'n' => output.push('\n'),
+                    'r' => output.push('\r'),
+                    't' => output.push('\t'),
+                    'u' => {
+                        let high = hex4(&mut chars)?;
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            chars.next().filter(|c| c.1 == '\\')?;
+                            chars.next().filter(|c| c.1 == 'u')?;
+                            let low = hex4(&mut chars)?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.checked_sub(0xDC00)?)
+                        } else {
+                            high
+                        };
+                        output.push(char::from_u32(code)?);
+                    }
+                    c => output.push(c),
+                },
+                _ => output.push(c),
+            }
+        }
+
+        None
+    }
+}
+
+fn hex4(chars: &mut std::str::CharIndices) -> Option<u32> {
+    let s: String = chars.take(4).map(|(_, c)| c).collect();
+    u32::from_str_radix(&s, 16).ok().filter(|_| s.len() == 4)
+}
+
+fn csl_entry(item: &Json) -> Option<BibEntry> {
+    let string = |key: &str| item.get(key).and_then(Json::as_string);
+
+    let authors = match item.get("author") {
+        Some(Json::Array(names)) => names
+            .iter()
+            .filter_map(|name| {
+                let family = name
+                    .get("family")
+                    .or_else(|| name.get("literal"))
+                    .and_then(Json::as_string)?;
+                let given = name.get("given").and_then(Json::as_string);
+                Some(Name { family, given })
+            })
+            .collect(),
+        _ => vec![],
+    };
+
+    let issued = item.get("issued");
+    let year = match issued.and_then(|i| i.get("date-parts")) {
+        Some(Json::Array(parts)) => match parts.first() {
+            Some(Json::Array(date)) => date.first().and_then(Json::as_string),
+            _ => None,
+        },
+        _ => issued
+            .and_then(|i| i.get("raw").or_else(|| i.get("literal")))
+            .and_then(Json::as_string)
+            .map(|s| s.chars().take(4).collect()),
+    };
+
+    Some(BibEntry {
+        key: string("id")?,
+        entry_type: string("type").unwrap_or_default(),
+        authors,
+        title: string("title"),
+        year,
+        container_title: string("container-title"),
+        publisher: string("publisher"),
+        volume: string("volume"),
+        issue: string("issue"),
+        pages: string("page"),
+        doi: string("DOI"),
+        url: string("URL"),
+    })
+}
+
+#[test]
+fn bibtex() {
+    let bibliography = Bibliography::from_bibtex(
+        r#"
+@comment{ignored @book{no, title = {No}}}
+@string{ acm = "ACM" }
+@InProceedings{godel1931,
+  author    = "Kurt G{\"o}del and {The Org Team} and van Rossum, Guido",
+  title     = {{Über} \emph{formal} unentscheidbare S\"atze},
+  booktitle = acm # { Proceedings},
+  month     = jan,
+  year      = 1931,
+  pages     = {173--198},
+  doi       = {10.1007/BF01700692}
+}
+@misc(bad, title)
+"#,
+    );
+
+    let entries: Vec<_> = bibliography.iter().collect();
+    assert_eq!(entries.len(), 2);
+
+    let entry = entries[0];
+    assert_eq!(entry.key, "godel1931");
+    assert_eq!(entry.entry_type, "inproceedings");
+    assert_eq!(
+        entry.authors,
+        [
+            Name {
+                family: "Go\u{308}del".into(),
+                given: Some("Kurt".into())
+            },
+            Name {
+                family: "The Org Team".into(),
+                given: None
+            },
+            Name {
+                family: "van Rossum".into(),
+                given: Some("Guido".into())
+            },
+        ]
+    );
+    assert_eq!(
+        entry.title.as_deref(),
+        Some("Über formal unentscheidbare Sa\u{308}tze")
+    );
+    assert_eq!(entry.container_title.as_deref(), Some("ACM Proceedings"));
+    assert_eq!(entry.pages.as_deref(), Some("173–198"));
+
+    assert_eq!(
+        format_entry(entry),
+        "Go\u{308}del, Kurt, The Org Team and van Rossum, Guido (1931). \
+         Über formal unentscheidbare Sa\u{308}tze. ACM Proceedings, 173–198. \
+         https://doi.org/10.1007/BF01700692"
+    );
+
+    assert_eq!(entries[1].key, "bad");
+
+    // entries without fields
+    let bibliography = Bibliography::from_bibtex("@book{doe}\n@book{x, title={T}}");
+    let entries: Vec<_> = bibliography.iter().collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].key, "doe");
+    assert_eq!(entries[1].key, "x");
+    assert_eq!(entries[1].title.as_deref(), Some("T"));
+}
+
+#[test]
+fn csl_json() {
+    let bibliography = Bibliography::from_csl_json(
+        r#"[{
+  "id": "doe",
+  "type": "article-journal",
+  "title": "Café \"Org\" 🦄",
+  "author": [{ "family": "Doe", "given": "Jane" }, { "literal": "Org Team" }],
+  "container-title": "Journal",
+  "volume": 3, "issue": "2", "page": "1-10",
+  "issued": { "raw": "2021-03" },
+  "extra": [null, true, false, -1.5e3, {}]
+}]"#,
+    )
+    .unwrap();
+
+    let entry = bibliography.get("doe").unwrap();
+    assert_eq!(entry.title.as_deref(), Some("Café \"Org\" 🦄"));
+    assert_eq!(entry.authors[1].family, "Org Team");
+    assert_eq!(entry.year.as_deref(), Some("2021"));
+    assert_eq!(
+        format_entry(entry),
+        "Doe, Jane and Org Team (2021). Café \"Org\" 🦄. Journal, 3(2), 1-10."
+    );
+
+    assert!(Bibliography::from_csl_json("{}").is_none());
+    assert!(Bibliography::from_csl_json("[{]").is_none());
+}
+
+#[test]
+fn numeric() {
+    use crate::{ast::Citation, Org};
+
+    let org = Org::parse(
+        "[cite:@missing] [cite:@b] [cite/n:@*] [cite:@a;@b]\n#+CITE_EXPORT: basic nil numeric",
+    );
+    let mut processor = CitationProcessor::new(Bibliography::from_bibtex(
+        "@book{a, title = {A}}\n@book{b, title = {B}}\n@book{c, title = {C}}",
+    ));
+    processor.prepare(&org.document());
+
+    let labels: Vec<String> = org
+        .document()
+        .syntax
+        .descendants()
+        .filter_map(Citation::cast)
+        .flat_map(|citation| processor.process(&citation))
+        .filter_map(|part| match part {
+            CitationPart::Reference { label, .. } => Some(label),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(labels, ["missing", "1", "2", "1"]);
+
+    let references: Vec<_> = processor
+        .references()
+        .into_iter()
+        .map(|r| (r.key, r.label.unwrap()))
+        .collect();
+    assert_eq!(
+        references,
+        [
+            ("b".to_string(), "[1]".to_string()),
+            ("a".to_string(), "[2]".to_string()),
+            ("c".to_string(), "[3]".to_string()),
+        ]
+    );
+}
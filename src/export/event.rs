@@ -7,10 +7,12 @@ pub enum Container {
     Section(Section),
     Paragraph(Paragraph),
     Headline(Headline),
+    Inlinetask(Inlinetask),
 
     OrgTable(OrgTable),
     OrgTableRow(OrgTableRow),
     OrgTableCell(OrgTableCell),
+    /// Table.el table, its contents are not traversed, see [`TableEl::grid`]
     TableEl(TableEl),
 
     List(List),
@@ -32,6 +34,7 @@ pub enum Container {
 
     Link(Link),
     RadioTarget(RadioTarget),
+    RadioLink(RadioLink),
     FnRef(FnRef),
     Target(Target),
     Bold(Bold),
@@ -67,6 +70,9 @@ pub enum Event {
     LatexFragment(LatexFragment),
     LatexEnvironment(LatexEnvironment),
     Entity(Entity),
+    Citation(Citation),
+    /// `#+PRINT_BIBLIOGRAPHY` keyword
+    PrintBibliography(Keyword),
 
     #[cfg(feature = "syntax-org-fc")]
     Cloze(Cloze),
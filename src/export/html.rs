@@ -1,12 +1,20 @@
-use rowan::NodeOrToken;
+use rowan::{ast::AstNode, NodeOrToken};
 use std::cmp::min;
 use std::fmt;
 use std::fmt::Write as _;
 
 use super::event::{Container, Event};
+use super::options::is_list_headline_node;
 use super::TraversalContext;
 use super::Traverser;
-use crate::{SyntaxElement, SyntaxKind, SyntaxNode};
+use super::{
+    Bibliography, CitationPart, CitationProcessor, ExportOptions, MacroExpander, TagsOption,
+};
+use crate::ast::{
+    Bold, ColumnAlignment, ColumnGroup, Document, Headline, Italic, RowMark, SourceBlock, Strike,
+    Subscript, Superscript, Underline,
+};
+use crate::{ParseConfig, SyntaxElement, SyntaxKind, SyntaxNode};
 
 /// A wrapper for escaping sensitive characters in html.
 ///
@@ -54,6 +62,29 @@ pub struct HtmlExport {
     in_descriptive_list: Vec<bool>,
 
     table_row: TableRow,
+
+    /// column alignments of current table
+    table_alignments: Vec<ColumnAlignment>,
+
+    /// whether current table has a marking column, which isn't exported
+    table_marking_column: bool,
+
+    options: ExportOptions,
+
+    macros: MacroExpander,
+
+    /// parse config for macro expansions
+    parse_config: ParseConfig,
+
+    link_abbreviations: Vec<(String, String)>,
+
+    citations: CitationProcessor,
+
+    /// coderef label and the text of links pointing to it
+    coderefs: Vec<(String, String)>,
+
+    /// last line number of numbered source blocks
+    src_line_number: u32,
 }
 
 #[derive(Default, PartialEq, Eq)]
@@ -74,6 +105,81 @@ impl HtmlExport {
         self.output
     }
 
+    /// Writes headline title, with TODO keyword, priority and tags if enabled
+    fn headline_title(&mut self, headline: &Headline, ctx: &mut TraversalContext) {
+        if let Some(keyword) = headline
+            .todo_keyword()
+            .filter(|_| self.options.todo_keywords)
+        {
+            let class = if headline.is_done() { "done" } else { "todo" };
+            let _ = write!(
+                &mut self.output,
+                r#"<span class="{class} {0}">{0}</span> "#,
+                HtmlEscape(&*keyword)
+            );
+        }
+        if let Some(priority) = headline.priority().filter(|_| self.options.priority) {
+            let _ = write!(&mut self.output, "[#{}] ", HtmlEscape(&*priority));
+        }
+        for elem in headline.title() {
+            self.element(elem, ctx);
+        }
+        // drop whitespace between title and tags
+        self.output.truncate(self.output.trim_end().len());
+        if self.options.tags != TagsOption::Nil {
+            let tags: Vec<_> = headline.tags().collect();
+            if !tags.is_empty() {
+                self.output += r#" <span class="tag">"#;
+                for (idx, tag) in tags.iter().enumerate() {
+                    if idx != 0 {
+                        self.output += "&#xa0;";
+                    }
+                    let _ = write!(
+                        &mut self.output,
+                        r#"<span class="{0}">{0}</span>"#,
+                        HtmlEscape(&**tag)
+                    );
+                }
+                self.output += "</span>";
+            }
+        }
+    }
+
+    /// Appends link abbreviations used for resolving link path
+    ///
+    /// Abbreviations defined by `#+LINK` in document are appended automatically
+    /// when entering the document, see [`Link::resolved_path`](crate::ast::Link::resolved_path).
+    pub fn extend_link_abbreviations(&mut self, iter: impl IntoIterator<Item = (String, String)>) {
+        self.link_abbreviations.extend(iter);
+    }
+
+    /// Sets parse config used for parsing macro expansions, it should be
+    /// the one used for parsing the document
+    ///
+    /// [`Org::to_html`](crate::Org::to_html) sets it to [`Org::config`](crate::Org::config).
+    pub fn set_parse_config(&mut self, config: ParseConfig) {
+        self.parse_config = config;
+    }
+
+    /// Sets bibliography used for rendering citations and `#+PRINT_BIBLIOGRAPHY`
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::{Bibliography, HtmlExport}};
+    ///
+    /// let org = Org::parse("[cite:@doe p. 3]\n#+PRINT_BIBLIOGRAPHY:");
+    /// let mut html = HtmlExport::default();
+    /// html.set_bibliography(Bibliography::from_bibtex("@book{doe, author = {Doe, John}, title = {Org}, year = 2020}"));
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><p><span class=\"citation\">(<a href=\"#cite-doe\">Doe, 2020</a> p. 3)</span>\n</p>\
+    ///     <ul class=\"bibliography\"><li id=\"cite-doe\">Doe, John (2020). Org.</li></ul></section></main>"
+    /// );
+    /// ```
+    pub fn set_bibliography(&mut self, bibliography: Bibliography) {
+        self.citations.bibliography = bibliography;
+    }
+
     /// Render syntax node to html string
     ///
     /// ```rust
@@ -91,28 +197,123 @@ impl HtmlExport {
     }
 }
 
+/// Collects coderef labels of all source blocks in document, along with
+/// the text of links pointing to them: line number if labels are removed
+/// by `-r`, otherwise the label itself
+fn coderefs(document: &Document) -> Vec<(String, String)> {
+    let mut coderefs = vec![];
+    let mut last_number = 0;
+
+    for block in document.syntax.descendants().filter_map(SourceBlock::cast) {
+        let remove_labels = block.src_switches().remove_labels;
+        let lines = block.lines(last_number);
+
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(label) = &line.label {
+                let text = if remove_labels {
+                    line.number.unwrap_or(idx as u32 + 1).to_string()
+                } else {
+                    label.clone()
+                };
+                coderefs.push((label.clone(), text));
+            }
+        }
+
+        if let Some(number) = lines.last().and_then(|line| line.number) {
+            last_number = number;
+        }
+    }
+
+    coderefs
+}
+
+fn alignment_class(alignment: ColumnAlignment) -> &'static str {
+    match alignment {
+        ColumnAlignment::Left => "org-left",
+        ColumnAlignment::Right => "org-right",
+        ColumnAlignment::Center => "org-center",
+    }
+}
+
+/// Radio links match their target case-insensitively and ignoring
+/// whitespaces, so both of them are normalized into the same id
+fn radio_id(s: &str) -> String {
+    let words: Vec<_> = s.split_whitespace().map(str::to_lowercase).collect();
+    format!("radio-{}", words.join("-"))
+}
+
 impl Traverser for HtmlExport {
     fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
         match event {
-            Event::Enter(Container::Document(_)) => self.output += "<main>",
+            Event::Enter(Container::Document(document)) => {
+                self.options = document.export_options();
+                self.macros = MacroExpander::with_config(&document, self.parse_config.clone());
+                self.link_abbreviations
+                    .extend(document.link_abbreviations());
+                self.citations.prepare(&document);
+                self.coderefs = coderefs(&document);
+                self.src_line_number = 0;
+                self.output += "<main>";
+            }
             Event::Leave(Container::Document(_)) => self.output += "</main>",
 
+            // `H:`, deeper headlines are exported as list items
+            Event::Enter(Container::Headline(headline))
+                if self.options.is_list_headline(&headline) =>
+            {
+                if !is_list_headline_node(&self.options, headline.syntax.prev_sibling()) {
+                    self.output += "<ul>";
+                }
+                self.output += "<li>";
+                self.headline_title(&headline, ctx);
+            }
+            Event::Leave(Container::Headline(headline))
+                if self.options.is_list_headline(&headline) =>
+            {
+                self.output += "</li>";
+                if !is_list_headline_node(&self.options, headline.syntax.next_sibling()) {
+                    self.output += "</ul>";
+                }
+            }
             Event::Enter(Container::Headline(headline)) => {
                 let level = min(headline.level(), 6);
                 let _ = write!(&mut self.output, "<h{level}>");
-                for elem in headline.title() {
-                    self.element(elem, ctx);
-                }
+                self.headline_title(&headline, ctx);
                 let _ = write!(&mut self.output, "</h{level}>");
             }
             Event::Leave(Container::Headline(_)) => {}
 
+            Event::Enter(Container::Inlinetask(inlinetask)) => {
+                self.output += "<div class=\"inlinetask\"><b>";
+                for elem in inlinetask.title() {
+                    self.element(elem, ctx);
+                }
+                self.output += "</b>";
+            }
+            Event::Leave(Container::Inlinetask(_)) => self.output += "</div>",
+
+            // `f:nil`, footnotes are not exported
+            Event::Enter(Container::FnRef(_) | Container::FnDef(_)) if !self.options.footnotes => {
+                ctx.skip()
+            }
+
             Event::Enter(Container::Paragraph(_)) => self.output += "<p>",
             Event::Leave(Container::Paragraph(_)) => self.output += "</p>",
 
             Event::Enter(Container::Section(_)) => self.output += "<section>",
             Event::Leave(Container::Section(_)) => self.output += "</section>",
 
+            // `*:nil`, emphasized text is exported as it is
+            Event::Enter(
+                Container::Italic(Italic { syntax })
+                | Container::Bold(Bold { syntax })
+                | Container::Strike(Strike { syntax })
+                | Container::Underline(Underline { syntax }),
+            ) if !self.options.emphasis => {
+                let _ = write!(&mut self.output, "{}", HtmlEscape(syntax.to_string()));
+                ctx.skip();
+            }
+
             Event::Enter(Container::Italic(_)) => self.output += "<i>",
             Event::Leave(Container::Italic(_)) => self.output += "</i>",
 
@@ -141,8 +342,35 @@ impl Traverser for HtmlExport {
                 } else {
                     self.output += r#"<pre><code>"#
                 }
+
+                let lines = block.lines(self.src_line_number);
+                for line in &lines {
+                    if let Some(label) = &line.label {
+                        let _ = write!(
+                            &mut self.output,
+                            r#"<span id="coderef-{}" class="coderef">"#,
+                            HtmlEscape(label)
+                        );
+                    }
+                    if let Some(number) = line.number {
+                        let _ = write!(
+                            &mut self.output,
+                            r#"<span class="linenr">{number}: </span>"#
+                        );
+                    }
+                    let _ = write!(&mut self.output, "{}", HtmlEscape(&line.content));
+                    if line.label.is_some() {
+                        self.output += "</span>";
+                    }
+                    self.output += "\n";
+                }
+                if let Some(number) = lines.last().and_then(|line| line.number) {
+                    self.src_line_number = number;
+                }
+
+                self.output += "</code></pre>";
+                ctx.skip();
             }
-            Event::Leave(Container::SourceBlock(_)) => self.output += "</code></pre>",
 
             Event::Enter(Container::QuoteBlock(_)) => self.output += "<blockquote>",
             Event::Leave(Container::QuoteBlock(_)) => self.output += "</blockquote>",
@@ -162,6 +390,14 @@ impl Traverser for HtmlExport {
             Event::Enter(Container::Comment(_)) => self.output += "<!--",
             Event::Leave(Container::Comment(_)) => self.output += "-->",
 
+            Event::Enter(
+                Container::Subscript(Subscript { syntax })
+                | Container::Superscript(Superscript { syntax }),
+            ) if !self.options.use_sub_superscript(&syntax) => {
+                let _ = write!(&mut self.output, "{}", HtmlEscape(syntax.to_string()));
+                ctx.skip();
+            }
+
             Event::Enter(Container::Subscript(_)) => self.output += "<sub>",
             Event::Leave(Container::Subscript(_)) => self.output += "</sub>",
 
@@ -169,6 +405,12 @@ impl Traverser for HtmlExport {
             Event::Leave(Container::Superscript(_)) => self.output += "</sup>",
 
             Event::Enter(Container::List(list)) => {
+                let start = list.items().next().and_then(|item| item.ordinal());
+                if let Some(start) = start.filter(|start| *start != 1) {
+                    self.in_descriptive_list.push(false);
+                    let _ = write!(&mut self.output, r#"<ol start="{start}">"#);
+                    return;
+                }
                 self.output += if list.is_ordered() {
                     self.in_descriptive_list.push(false);
                     "<ol>"
@@ -197,6 +439,17 @@ impl Traverser for HtmlExport {
                         self.element(elem, ctx);
                     }
                     self.output += "</dt><dd>";
+                } else if let Some(value) = list_item
+                    .counter()
+                    .and_then(|_| list_item.ordinal())
+                    .filter(|_| {
+                        list_item
+                            .syntax
+                            .prev_sibling()
+                            .is_some_and(|n| n.kind() == SyntaxKind::LIST_ITEM)
+                    })
+                {
+                    let _ = write!(&mut self.output, r#"<li value="{value}">"#);
                 } else {
                     self.output += "<li>";
                 }
@@ -209,12 +462,50 @@ impl Traverser for HtmlExport {
                 }
             }
 
+            Event::Enter(Container::OrgTable(_)) if !self.options.tables => ctx.skip(),
             Event::Enter(Container::OrgTable(table)) => {
                 self.output += "<table>";
                 self.table_row = if table.has_header() {
                     TableRow::HeaderRule
                 } else {
                     TableRow::BodyRule
+                };
+                self.table_alignments = table.column_alignments();
+                self.table_marking_column = table.has_marking_column();
+
+                let skip = self.table_marking_column as usize;
+                let groups = table.column_groups();
+                if groups.iter().any(Option::is_some) {
+                    let mut opened = false;
+                    for (idx, group) in groups.iter().enumerate().skip(skip) {
+                        if opened
+                            && matches!(group, Some(ColumnGroup::Start | ColumnGroup::StartEnd))
+                        {
+                            self.output += "</colgroup>";
+                            opened = false;
+                        }
+                        if !opened {
+                            self.output += "<colgroup>";
+                            opened = true;
+                        }
+                        match self.table_alignments.get(idx).copied() {
+                            Some(alignment) => {
+                                let _ = write!(
+                                    &mut self.output,
+                                    r#"<col class="{}">"#,
+                                    alignment_class(alignment)
+                                );
+                            }
+                            None => self.output += "<col>",
+                        }
+                        if matches!(group, Some(ColumnGroup::End | ColumnGroup::StartEnd)) {
+                            self.output += "</colgroup>";
+                            opened = false;
+                        }
+                    }
+                    if opened {
+                        self.output += "</colgroup>";
+                    }
                 }
             }
             Event::Leave(Container::OrgTable(_)) => {
@@ -239,6 +530,15 @@ impl Traverser for HtmlExport {
                         _ => {}
                     }
                     ctx.skip();
+                } else if row.is_cookie_row()
+                    || (self.table_marking_column
+                        && row
+                            .cells()
+                            .first()
+                            .and_then(|cell| RowMark::parse(cell))
+                            .is_some_and(RowMark::is_special))
+                {
+                    ctx.skip();
                 } else {
                     match self.table_row {
                         TableRow::HeaderRule => {
@@ -252,6 +552,27 @@ impl Traverser for HtmlExport {
                         _ => {}
                     }
                     self.output += "<tr>";
+
+                    let skip = self.table_marking_column as usize;
+                    for (idx, (_, cell)) in row.slots().into_iter().enumerate().skip(skip) {
+                        match self.table_alignments.get(idx).copied() {
+                            Some(alignment) => {
+                                let _ = write!(
+                                    &mut self.output,
+                                    r#"<td class="{}">"#,
+                                    alignment_class(alignment)
+                                );
+                            }
+                            None => self.output += "<td>",
+                        }
+                        for child in cell.iter().flat_map(|c| c.syntax.children_with_tokens()) {
+                            self.element(child, ctx);
+                        }
+                        self.output += "</td>";
+                    }
+
+                    self.output += "</tr>";
+                    ctx.skip();
                 }
             }
             Event::Leave(Container::OrgTableRow(row)) => {
@@ -275,8 +596,73 @@ impl Traverser for HtmlExport {
             Event::Enter(Container::OrgTableCell(_)) => self.output += "<td>",
             Event::Leave(Container::OrgTableCell(_)) => self.output += "</td>",
 
+            Event::Enter(Container::TableEl(_)) if !self.options.tables => ctx.skip(),
+            Event::Enter(Container::TableEl(table)) => {
+                let Some(grid) = table.grid() else {
+                    // keeps malformed tables as they are written
+                    let text = table
+                        .syntax
+                        .children_with_tokens()
+                        .find(|e| e.kind() == SyntaxKind::TEXT)
+                        .map(|e| e.to_string())
+                        .unwrap_or_default();
+                    let _ = write!(&mut self.output, "<pre>{}</pre>", HtmlEscape(&text));
+                    return ctx.skip();
+                };
+
+                self.output += "<table>";
+                for row in 0..grid.rows {
+                    if row == 0 && grid.header_rows > 0 {
+                        self.output += "<thead>";
+                    } else if row == grid.header_rows {
+                        self.output += "<tbody>";
+                    }
+                    let tag = if row < grid.header_rows { "th" } else { "td" };
+
+                    self.output += "<tr>";
+                    for cell in grid.row(row) {
+                        let _ = write!(&mut self.output, "<{tag}");
+                        if cell.rowspan > 1 {
+                            let _ = write!(&mut self.output, r#" rowspan="{}""#, cell.rowspan);
+                        }
+                        if cell.colspan > 1 {
+                            let _ = write!(&mut self.output, r#" colspan="{}""#, cell.colspan);
+                        }
+                        let _ = write!(&mut self.output, ">{}</{tag}>", HtmlEscape(&cell.text));
+                    }
+                    self.output += "</tr>";
+
+                    if row + 1 == grid.header_rows {
+                        self.output += "</thead>";
+                    }
+                }
+                if grid.header_rows < grid.rows {
+                    self.output += "</tbody>";
+                }
+                self.output += "</table>";
+                ctx.skip();
+            }
+
             Event::Enter(Container::Link(link)) => {
                 let path = link.path();
+                let coderef = path
+                    .strip_prefix('(')
+                    .and_then(|p| p.strip_suffix(')'))
+                    .and_then(|label| self.coderefs.iter().rev().find(|(l, _)| l == label));
+                if let Some((label, text)) = coderef {
+                    let _ = write!(
+                        &mut self.output,
+                        r##"<a href="#coderef-{}" class="coderef">"##,
+                        HtmlEscape(label)
+                    );
+                    if !link.has_description() {
+                        let _ = write!(&mut self.output, "{}</a>", HtmlEscape(text));
+                        ctx.skip();
+                    }
+                    return;
+                }
+
+                let path = link.resolved_path(&self.link_abbreviations);
                 let path = path.trim_start_matches("file:");
 
                 if link.is_image() {
@@ -293,20 +679,40 @@ impl Traverser for HtmlExport {
             }
             Event::Leave(Container::Link(_)) => self.output += "</a>",
 
+            Event::Enter(Container::RadioTarget(target)) => {
+                let id = radio_id(&target.value());
+                let _ = write!(&mut self.output, r#"<a id="{}">"#, HtmlEscape(&id));
+            }
+            Event::Leave(Container::RadioTarget(_)) => self.output += "</a>",
+
+            Event::Enter(Container::RadioLink(link)) => {
+                let id = radio_id(&link.syntax.to_string());
+                let _ = write!(&mut self.output, r##"<a href="#{}">"##, HtmlEscape(&id));
+            }
+            Event::Leave(Container::RadioLink(_)) => self.output += "</a>",
+
             Event::Text(text) => {
                 let _ = write!(&mut self.output, "{}", HtmlEscape(text));
             }
 
             Event::LineBreak(_) => self.output += "<br/>",
 
-            Event::Snippet(snippet) => {
-                if snippet.backend().eq_ignore_ascii_case("html") {
-                    self.output += &snippet.value();
+            Event::Snippet(snippet) if snippet.backend().eq_ignore_ascii_case("html") => {
+                self.output += &snippet.value();
+            }
+
+            Event::Macros(macros) => {
+                if let Some(expanded) = self.macros.expand(&macros) {
+                    for elem in self.macros.objects(&expanded).children_with_tokens() {
+                        self.element(elem, ctx);
+                    }
                 }
             }
 
             Event::Rule(_) => self.output += "<hr/>",
 
+            Event::Timestamp(_) if !self.options.timestamps => {}
+
             Event::Timestamp(timestamp) => {
                 self.output += r#"<span class="timestamp-wrapper"><span class="timestamp">"#;
                 for e in timestamp.syntax.children_with_tokens() {
@@ -330,9 +736,63 @@ impl Traverser for HtmlExport {
                 let _ = write!(&mut self.output, "{}", &latex.syntax);
             }
 
+            Event::Citation(citation) => {
+                let parts = self.citations.process(&citation);
+                if parts.is_empty() {
+                    return;
+                }
+                self.output += r#"<span class="citation">"#;
+                for part in parts {
+                    match part {
+                        CitationPart::Text(text) => self.output += text,
+                        CitationPart::Objects(objects) => {
+                            for elem in objects {
+                                self.element(elem, ctx);
+                            }
+                        }
+                        CitationPart::Reference { key, label } => {
+                            let _ = write!(
+                                &mut self.output,
+                                r##"<a href="#cite-{}">{}</a>"##,
+                                HtmlEscape(&key),
+                                HtmlEscape(&label)
+                            );
+                        }
+                    }
+                }
+                self.output += "</span>";
+            }
+
+            Event::PrintBibliography(_) => {
+                let references = self.citations.references();
+                if references.is_empty() {
+                    return;
+                }
+                self.output += r#"<ul class="bibliography">"#;
+                for reference in references {
+                    let _ = write!(
+                        &mut self.output,
+                        r#"<li id="cite-{}">"#,
+                        HtmlEscape(&reference.key)
+                    );
+                    if let Some(label) = &reference.label {
+                        let _ = write!(&mut self.output, "{} ", HtmlEscape(label));
+                    }
+                    let _ = write!(&mut self.output, "{}</li>", HtmlEscape(&reference.text));
+                }
+                self.output += "</ul>";
+            }
+
             // ignores keyword
             Event::Enter(Container::Keyword(_)) => ctx.skip(),
 
+            Event::Entity(entity) if !self.options.entities => {
+                let _ = write!(
+                    &mut self.output,
+                    "{}",
+                    HtmlEscape(entity.syntax.to_string())
+                );
+            }
             Event::Entity(entity) => self.output += entity.html(),
 
             _ => {}
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use rowan::{ast::AstNode, NodeOrToken};
+
+use crate::{
+    ast::{Document, Headline, Macros},
+    syntax::{combinator::node, object::standard_object_nodes, SyntaxKind},
+    ParseConfig, SyntaxNode,
+};
+
+/// Maximum depth of nested macro expansion, deeper macro is considered circular
+const MAX_DEPTH: usize = 16;
+
+/// Macro expander
+///
+/// Expands macros defined by `#+MACRO` and following built-in macros:
+///
+/// - `{{{title}}}`, `{{{author}}}`, `{{{email}}}`: value of corresponding keyword
+/// - `{{{date}}}`, `{{{date(FORMAT)}}}`: value of `#+DATE`, formatted if `chrono` feature is enabled
+/// - `{{{time(FORMAT)}}}`: current time, requires `chrono` feature
+/// - `{{{property(NAME)}}}`: property value of current headline
+/// - `{{{keyword(NAME)}}}`: value of given keyword
+/// - `{{{n}}}`, `{{{n(NAME, ACTION)}}}`: counter
+/// - `{{{input-file}}}`: value of [`MacroExpander::input_file`]
+///
+/// Macros defined by `#+MACRO` take precedence over built-in ones.
+///
+/// ```rust
+/// use orgize::{Org, ast::Macros, export::MacroExpander, rowan::ast::AstNode};
+///
+/// let org = Org::parse(r#"#+TITLE: Orgize
+/// #+MACRO: greet Hello, $1!
+/// {{{title}}} {{{greet(world)}}} {{{n}}} {{{n}}} {{{unknown}}}"#);
+///
+/// let mut expander = MacroExpander::new(&org.document());
+/// let expanded: Vec<_> = org
+///     .document()
+///     .syntax()
+///     .descendants()
+///     .filter_map(Macros::cast)
+///     .map(|m| expander.expand(&m))
+///     .collect();
+///
+/// assert_eq!(
+///     expanded,
+///     [
+///         Some("Orgize".into()),
+///         Some("Hello, world!".into()),
+///         Some("1".into()),
+///         Some("2".into()),
+///         None
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MacroExpander {
+    definitions: Vec<(String, String)>,
+    keywords: Vec<(String, String)>,
+    counters: HashMap<String, i64>,
+    config: ParseConfig,
+
+    /// Value of `{{{input-file}}}`
+    pub input_file: Option<String>,
+}
+
+impl MacroExpander {
+    /// Creates an expander with macro definitions and keywords in given document
+    pub fn new(document: &Document) -> MacroExpander {
+        MacroExpander::with_config(document, ParseConfig::default())
+    }
+
+    /// Creates an expander like [`MacroExpander::new`], expansions are parsed
+    /// with given config, which should be the one used for parsing the document
+    ///
+    /// ```rust
+    /// use orgize::{ParseConfig, ast::Macros, export::MacroExpander};
+    ///
+    /// let config = ParseConfig::default();
+    /// let org = config.clone().parse("#+MACRO: all $0 and $1\n{{{all(a, b)}}}");
+    /// let macros = org.first_node::<Macros>().unwrap();
+    ///
+    /// let mut expander = MacroExpander::with_config(&org.document(), config);
+    /// assert_eq!(expander.expand(&macros).as_deref(), Some("a, b and a"));
+    /// ```
+    pub fn with_config(document: &Document, config: ParseConfig) -> MacroExpander {
+        MacroExpander {
+            definitions: document.macro_definitions(),
+            keywords: document
+                .keywords()
+                .map(|kw| (kw.key().to_string(), kw.value().trim().to_string()))
+                .collect(),
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Expands given macro, including macros nested in its expansion
+    ///
+    /// Returns `None` if macro is undefined or its expansion is circular.
+    pub fn expand(&mut self, macros: &Macros) -> Option<String> {
+        self.expand_macros(macros, &macros.syntax, 0)
+    }
+
+    /// Parses macro expansion as standard set of objects
+    pub(crate) fn objects(&self, expanded: &str) -> SyntaxNode {
+        let children = standard_object_nodes((expanded, &self.config).into());
+        SyntaxNode::new_root(node(SyntaxKind::PARAGRAPH, children).into_node().unwrap())
+    }
+
+    /// `context` is the original macro node in document, used for looking up properties
+    fn expand_macros(
+        &mut self,
+        macros: &Macros,
+        context: &SyntaxNode,
+        depth: usize,
+    ) -> Option<String> {
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+
+        let name = macros.key();
+        let args = macros.arguments();
+
+        let expanded = if let Some((_, template)) = self
+            .definitions
+            .iter()
+            .rev()
+            .find(|(n, _)| n.eq_ignore_ascii_case(&name))
+        {
+            substitute(template, &args)
+        } else {
+            match name.to_ascii_lowercase().as_str() {
+                "title" | "author" | "email" => self.keyword(&name)?,
+                "date" => self.date(args.first().map(|s| s.trim()))?,
+                "time" => time(args.first()?.trim())?,
+                "keyword" => self.keyword(args.first()?.trim())?,
+                "property" => property(context, args.first()?.trim())?,
+                "n" => self.counter(&args),
+                "input-file" => self.input_file.clone()?,
+                _ => return None,
+            }
+        };
+
+        let node = self.objects(&expanded);
+
+        if !node.descendants().any(|n| n.kind() == SyntaxKind::MACROS) {
+            return Some(expanded);
+        }
+
+        let mut output = String::new();
+        for event in node.preorder_with_tokens() {
+            match event {
+                rowan::WalkEvent::Enter(NodeOrToken::Node(n)) => {
+                    if let Some(nested) = Macros::cast(n.clone()) {
+                        output += &self.expand_macros(&nested, context, depth + 1)?;
+                    }
+                }
+                rowan::WalkEvent::Enter(NodeOrToken::Token(t))
+                    if !t.parent_ancestors().any(|n| n.kind() == SyntaxKind::MACROS) =>
+                {
+                    output += t.text();
+                }
+                _ => {}
+            }
+        }
+
+        Some(output)
+    }
+
+    /// Values of given keywords, joined with spaces
+    fn keyword(&self, key: &str) -> Option<String> {
+        let values: Vec<_> = self
+            .keywords
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+            .collect();
+
+        (!values.is_empty()).then(|| values.join(" "))
+    }
+
+    fn date(&self, format: Option<&str>) -> Option<String> {
+        let date = self.keyword("DATE")?;
+
+        #[cfg(feature = "chrono")]
+        if let Some(format) = format.filter(|f| !f.is_empty()) {
+            use crate::{ast::Timestamp, Org};
+            use std::fmt::Write as _;
+
+            let org = Org::parse(&date);
+            if let Some(datetime) = org
+                .first_node::<Timestamp>()
+                .and_then(|ts| ts.start_to_chrono())
+            {
+                let mut s = String::new();
+                if write!(&mut s, "{}", datetime.format(format)).is_ok() {
+                    return Some(s);
+                }
+            }
+        }
+
+        #[cfg(not(feature = "chrono"))]
+        let _ = format;
+
+        Some(date)
+    }
+
+    /// Counter macro, `{{{n(NAME, ACTION)}}}`
+    ///
+    /// - empty `ACTION` increments the counter
+    /// - `-` returns current value without incrementing
+    /// - a number sets the counter to this number
+    /// - other values reset the counter to `1`
+    fn counter(&mut self, args: &[String]) -> String {
+        let name = args.first().map(|s| s.trim()).unwrap_or_default();
+        let action = args.get(1).map(|s| s.trim()).unwrap_or_default();
+
+        let value = self.counters.entry(name.to_string()).or_insert(0);
+
+        if action.is_empty() {
+            *value += 1;
+        } else if let Ok(number) = action.parse() {
+            *value = number;
+        } else if action != "-" {
+            *value = 1;
+        }
+
+        value.to_string()
+    }
+}
+
+/// Replaces `$1`, `$2`, ... in template with arguments, and `$0` with all
+/// arguments joined with commas
+fn substitute(template: &str, args: &[String]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(idx) = rest.find('$') {
+        output += &rest[..idx];
+        rest = &rest[idx + 1..];
+
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            output.push('$');
+            continue;
+        }
+
+        let n: usize = rest[..len].parse().unwrap_or_default();
+        if n == 0 {
+            output += &args.join(",");
+        } else if let Some(arg) = args.get(n - 1) {
+            output += arg;
+        }
+        rest = &rest[len..];
+    }
+
+    output + rest
+}
+
+/// Property of the headline containing given node, or document property
+/// if it's not inside any headline
+fn property(node: &SyntaxNode, key: &str) -> Option<String> {
+    let drawer = match node.ancestors().find_map(Headline::cast) {
+        Some(headline) => headline.properties()?,
+        None => Document::cast(node.ancestors().last()?)?.properties()?,
+    };
+
+    drawer
+        .iter()
+        .find_map(|(k, v)| k.eq_ignore_ascii_case(key).then(|| v.to_string()))
+}
+
+#[cfg(feature = "chrono")]
+fn time(format: &str) -> Option<String> {
+    use std::fmt::Write as _;
+
+    let mut s = String::new();
+    write!(&mut s, "{}", chrono::Local::now().format(format)).ok()?;
+    Some(s)
+}
+
+#[cfg(not(feature = "chrono"))]
+fn time(_: &str) -> Option<String> {
+    None
+}
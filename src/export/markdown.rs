@@ -1,17 +1,30 @@
 use std::cmp::min;
 use std::fmt::Write as _;
 
-use crate::{SyntaxElement, SyntaxNode};
+use crate::ast::{Bold, Headline, Italic, Strike, Subscript, Superscript, Underline};
+use crate::{ParseConfig, SyntaxElement, SyntaxNode};
 
 use super::event::{Container, Event};
 use super::TraversalContext;
 use super::Traverser;
+use super::{
+    Bibliography, CitationPart, CitationProcessor, ExportOptions, MacroExpander, TagsOption,
+};
 
 #[derive(Default)]
 pub struct MarkdownExport {
     output: String,
 
     inside_blockquote: bool,
+
+    options: ExportOptions,
+
+    macros: MacroExpander,
+
+    /// parse config for macro expansions
+    parse_config: ParseConfig,
+
+    citations: CitationProcessor,
 }
 
 impl MarkdownExport {
@@ -19,6 +32,17 @@ impl MarkdownExport {
         self.output += s.as_ref();
     }
 
+    /// Sets parse config used for parsing macro expansions, it should be
+    /// the one used for parsing the document
+    pub fn set_parse_config(&mut self, config: ParseConfig) {
+        self.parse_config = config;
+    }
+
+    /// Sets bibliography used for rendering citations and `#+PRINT_BIBLIOGRAPHY`
+    pub fn set_bibliography(&mut self, bibliography: Bibliography) {
+        self.citations.bibliography = bibliography;
+    }
+
     /// Render syntax node to markdown string
     ///
     /// ```rust
@@ -39,6 +63,30 @@ impl MarkdownExport {
         self.output
     }
 
+    /// Writes headline title, with TODO keyword, priority and tags if enabled
+    fn headline_title(&mut self, headline: &Headline, ctx: &mut TraversalContext) {
+        if let Some(keyword) = headline
+            .todo_keyword()
+            .filter(|_| self.options.todo_keywords)
+        {
+            let _ = write!(&mut self.output, "{} ", &*keyword);
+        }
+        if let Some(priority) = headline.priority().filter(|_| self.options.priority) {
+            let _ = write!(&mut self.output, "[#{}] ", &*priority);
+        }
+        for elem in headline.title() {
+            self.element(elem, ctx);
+        }
+        // drop whitespace between title and tags
+        self.output.truncate(self.output.trim_end().len());
+        if self.options.tags != TagsOption::Nil {
+            let tags: Vec<_> = headline.tags().map(|tag| tag.to_string()).collect();
+            if !tags.is_empty() {
+                let _ = write!(&mut self.output, " :{}:", tags.join(":"));
+            }
+        }
+    }
+
     fn follows_newline(&mut self) {
         if !self.output.is_empty() && !self.output.ends_with(['\n', '\r']) {
             self.output += "\n";
@@ -49,18 +97,44 @@ impl MarkdownExport {
 impl Traverser for MarkdownExport {
     fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
         match event {
-            Event::Enter(Container::Document(_)) => {}
+            Event::Enter(Container::Document(document)) => {
+                self.options = document.export_options();
+                self.macros = MacroExpander::with_config(&document, self.parse_config.clone());
+                self.citations.prepare(&document);
+            }
             Event::Leave(Container::Document(_)) => {}
 
+            // `H:`, deeper headlines are exported as list items
+            Event::Enter(Container::Headline(headline))
+                if self.options.is_list_headline(&headline) =>
+            {
+                self.follows_newline();
+                let depth = headline.level() - self.options.headline_levels - 1;
+                let _ = write!(&mut self.output, "{}- ", "  ".repeat(depth));
+                self.headline_title(&headline, ctx);
+            }
             Event::Enter(Container::Headline(headline)) => {
                 self.follows_newline();
                 let level = min(headline.level(), 6);
                 let _ = write!(&mut self.output, "{} ", "#".repeat(level));
-                for elem in headline.title() {
+                self.headline_title(&headline, ctx);
+            }
+            Event::Leave(Container::Headline(_)) => {}
+
+            Event::Enter(Container::Inlinetask(inlinetask)) => {
+                self.follows_newline();
+                self.output += "**";
+                for elem in inlinetask.title() {
                     self.element(elem, ctx);
                 }
+                self.output += "**\n";
+            }
+            Event::Leave(Container::Inlinetask(_)) => {}
+
+            // `f:nil`, footnotes are not exported
+            Event::Enter(Container::FnRef(_) | Container::FnDef(_)) if !self.options.footnotes => {
+                ctx.skip()
             }
-            Event::Leave(Container::Headline(_)) => {}
 
             Event::Enter(Container::Paragraph(_)) => {}
             Event::Leave(Container::Paragraph(_)) => self.output += "\n",
@@ -68,6 +142,17 @@ impl Traverser for MarkdownExport {
             Event::Enter(Container::Section(_)) => self.follows_newline(),
             Event::Leave(Container::Section(_)) => {}
 
+            // `*:nil`, emphasized text is exported as it is
+            Event::Enter(
+                Container::Italic(Italic { syntax })
+                | Container::Bold(Bold { syntax })
+                | Container::Strike(Strike { syntax })
+                | Container::Underline(Underline { syntax }),
+            ) if !self.options.emphasis => {
+                self.output += &syntax.to_string();
+                ctx.skip();
+            }
+
             Event::Enter(Container::Italic(_)) => self.output += "*",
             Event::Leave(Container::Italic(_)) => self.output += "*",
 
@@ -107,6 +192,14 @@ impl Traverser for MarkdownExport {
             Event::Enter(Container::Comment(_)) => self.output += "<!--",
             Event::Leave(Container::Comment(_)) => self.output += "-->",
 
+            Event::Enter(
+                Container::Subscript(Subscript { syntax })
+                | Container::Superscript(Superscript { syntax }),
+            ) if !self.options.use_sub_superscript(&syntax) => {
+                self.output += &syntax.to_string();
+                ctx.skip();
+            }
+
             Event::Enter(Container::Subscript(_)) => self.output += "<sub>",
             Event::Leave(Container::Subscript(_)) => self.output += "</sub>",
 
@@ -123,12 +216,14 @@ impl Traverser for MarkdownExport {
             }
             Event::Leave(Container::ListItem(_)) => {}
 
+            Event::Enter(Container::OrgTable(_)) if !self.options.tables => ctx.skip(),
             Event::Enter(Container::OrgTable(_table)) => {}
             Event::Leave(Container::OrgTable(_)) => {}
             Event::Enter(Container::OrgTableRow(_row)) => {}
             Event::Leave(Container::OrgTableRow(_row)) => {}
             Event::Enter(Container::OrgTableCell(_)) => {}
             Event::Leave(Container::OrgTableCell(_)) => {}
+            Event::Enter(Container::TableEl(_)) => ctx.skip(),
 
             Event::Enter(Container::Link(link)) => {
                 let path = link.path();
@@ -167,8 +262,45 @@ impl Traverser for MarkdownExport {
 
             Event::Snippet(_snippet) => {}
 
+            Event::Macros(macros) => {
+                if let Some(expanded) = self.macros.expand(&macros) {
+                    for elem in self.macros.objects(&expanded).children_with_tokens() {
+                        self.element(elem, ctx);
+                    }
+                }
+            }
+
+            Event::Citation(citation) => {
+                for part in self.citations.process(&citation) {
+                    match part {
+                        CitationPart::Text(text) => self.output += text,
+                        CitationPart::Objects(objects) => {
+                            for elem in objects {
+                                self.element(elem, ctx);
+                            }
+                        }
+                        CitationPart::Reference { key, label } => {
+                            let _ = write!(&mut self.output, "[{label}](#cite-{key})");
+                        }
+                    }
+                }
+            }
+
+            Event::PrintBibliography(_) => {
+                for reference in self.citations.references() {
+                    self.follows_newline();
+                    let _ = write!(&mut self.output, r#"- <a id="cite-{}"></a>"#, reference.key);
+                    if let Some(label) = &reference.label {
+                        let _ = write!(&mut self.output, "{label} ");
+                    }
+                    self.output += &reference.text;
+                }
+            }
+
             Event::Rule(_) => self.output += "\n-----\n",
 
+            Event::Timestamp(_) if !self.options.timestamps => {}
+
             Event::Timestamp(_timestamp) => {}
 
             Event::LatexFragment(latex) => {
@@ -178,6 +310,9 @@ impl Traverser for MarkdownExport {
                 let _ = write!(&mut self.output, "{}", &latex.syntax);
             }
 
+            Event::Entity(entity) if !self.options.entities => {
+                self.output += &entity.syntax.to_string();
+            }
             Event::Entity(entity) => self.output += entity.utf8(),
 
             _ => {}
@@ -1,11 +1,19 @@
 //! Export `Org` struct to various formats.
 
+mod bibliography;
 mod event;
 mod html;
+mod macros;
 mod markdown;
+mod options;
 mod traverse;
 
+pub use bibliography::{
+    BibEntry, Bibliography, CitationPart, CitationProcessor, CitationStyle, Name, Reference,
+};
 pub use event::{Container, Event};
 pub use html::{HtmlEscape, HtmlExport};
+pub use macros::MacroExpander;
 pub use markdown::MarkdownExport;
+pub use options::{CiteExport, DepthOption, ExportOptions, TagsOption};
 pub use traverse::{from_fn, from_fn_with_ctx, FromFn, FromFnWithCtx, TraversalContext, Traverser};
@@ -0,0 +1,271 @@
+use rowan::ast::AstNode;
+
+use crate::{ast::Headline, config::UseSubSuperscript, SyntaxKind, SyntaxNode};
+
+/// Export options, parsed from `#+OPTIONS` keywords
+///
+/// Default values are the same as emacs.
+///
+/// Built-in exporters honor `*:`, `e:`, `f:`, `H:`, `^:`, `|:`, `tags:`, `<:`,
+/// `todo:` and `pri:`. Other options are parsed for custom exporters and
+/// ignored by [`HtmlExport`](super::HtmlExport) and
+/// [`MarkdownExport`](super::MarkdownExport).
+#[derive(Clone, Debug)]
+pub struct ExportOptions {
+    /// `author:`, include author name
+    pub author: bool,
+    /// `c:`, include clock keywords
+    pub clocks: bool,
+    /// `creator:`, include creator information
+    pub creator: bool,
+    /// `date:`, include creation date
+    pub date: bool,
+    /// `email:`, include author email
+    pub email: bool,
+    /// `*:`, render emphasized text
+    pub emphasis: bool,
+    /// `e:`, render entities
+    pub entities: bool,
+    /// `::`, include fixed-width sections
+    pub fixed_width: bool,
+    /// `f:`, include footnotes
+    pub footnotes: bool,
+    /// `H:`, number of headline levels for export, deeper headlines will be exported as lists
+    pub headline_levels: usize,
+    /// `inline:`, include inlinetasks
+    pub inlinetasks: bool,
+    /// `\n:`, preserve line breaks
+    pub line_breaks: bool,
+    /// `p:`, include planning info
+    pub planning: bool,
+    /// `pri:`, include priority cookies
+    pub priority: bool,
+    /// `prop:`, include properties drawers
+    pub properties: bool,
+    /// `num:`, section numbers
+    pub section_numbers: DepthOption,
+    /// `':`, use smart quotes
+    pub smart_quotes: bool,
+    /// `-:`, convert special strings
+    pub special_strings: bool,
+    /// `stat:`, include statistics cookies
+    pub statistics_cookies: bool,
+    /// `^:`, interpret sub/superscripts
+    pub sub_superscript: UseSubSuperscript,
+    /// `|:`, include tables
+    pub tables: bool,
+    /// `tags:`, include tags
+    pub tags: TagsOption,
+    /// `<:`, include timestamps
+    pub timestamps: bool,
+    /// `title:`, include title
+    pub title: bool,
+    /// `toc:`, include table of contents
+    pub toc: DepthOption,
+    /// `todo:`, include todo keywords
+    pub todo_keywords: bool,
+}
+
+/// Value of `toc:` and `num:` options
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DepthOption {
+    Nil,
+    True,
+    /// Only applies to headlines whose level is lower or equal to given level
+    Level(usize),
+}
+
+/// Value of `tags:` option
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TagsOption {
+    Nil,
+    True,
+    NotInToc,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            author: true,
+            clocks: false,
+            creator: false,
+            date: true,
+            email: false,
+            emphasis: true,
+            entities: true,
+            fixed_width: true,
+            footnotes: true,
+            headline_levels: 3,
+            inlinetasks: true,
+            line_breaks: false,
+            planning: false,
+            priority: false,
+            properties: false,
+            section_numbers: DepthOption::True,
+            smart_quotes: false,
+            special_strings: true,
+            statistics_cookies: true,
+            sub_superscript: UseSubSuperscript::True,
+            tables: true,
+            tags: TagsOption::True,
+            timestamps: true,
+            title: true,
+            toc: DepthOption::True,
+            todo_keywords: true,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Updates options with the value of `#+OPTIONS` keyword
+    ///
+    /// Unknown options or invalid values are ignored.
+    ///
+    /// ```rust
+    /// use orgize::export::{DepthOption, ExportOptions, TagsOption};
+    ///
+    /// let mut options = ExportOptions::default();
+    /// options.apply("toc:2 num:nil H:4 ^:{} tags:not-in-toc \\n:t todo:nil unknown:t");
+    /// assert_eq!(options.toc, DepthOption::Level(2));
+    /// assert_eq!(options.section_numbers, DepthOption::Nil);
+    /// assert_eq!(options.headline_levels, 4);
+    /// assert!(options.sub_superscript.is_brace());
+    /// assert_eq!(options.tags, TagsOption::NotInToc);
+    /// assert!(options.line_breaks);
+    /// assert!(!options.todo_keywords);
+    /// ```
+    pub fn apply(&mut self, value: &str) {
+        for item in value.split_whitespace() {
+            // option key can be colon itself, e.g. `::t`
+            let Some((key, value)) = item.rsplit_once(':') else {
+                continue;
+            };
+
+            let flag = value != "nil";
+
+            let depth = match value {
+                "nil" => DepthOption::Nil,
+                _ => value.parse().map_or(DepthOption::True, DepthOption::Level),
+            };
+
+            match key {
+                "author" => self.author = flag,
+                "c" => self.clocks = flag,
+                "creator" => self.creator = flag,
+                "date" => self.date = flag,
+                "email" => self.email = flag,
+                "*" => self.emphasis = flag,
+                "e" => self.entities = flag,
+                ":" => self.fixed_width = flag,
+                "f" => self.footnotes = flag,
+                "H" => {
+                    if let Ok(level) = value.parse() {
+                        self.headline_levels = level;
+                    }
+                }
+                "inline" => self.inlinetasks = flag,
+                "\\n" => self.line_breaks = flag,
+                "p" => self.planning = flag,
+                "pri" => self.priority = flag,
+                "prop" => self.properties = flag,
+                "num" => self.section_numbers = depth,
+                "'" => self.smart_quotes = flag,
+                "-" => self.special_strings = flag,
+                "stat" => self.statistics_cookies = flag,
+                "^" => {
+                    self.sub_superscript = match value {
+                        "nil" => UseSubSuperscript::Nil,
+                        "{}" => UseSubSuperscript::Brace,
+                        _ => UseSubSuperscript::True,
+                    }
+                }
+                "|" => self.tables = flag,
+                "tags" => {
+                    self.tags = match value {
+                        "nil" => TagsOption::Nil,
+                        "not-in-toc" => TagsOption::NotInToc,
+                        _ => TagsOption::True,
+                    }
+                }
+                "<" => self.timestamps = flag,
+                "title" => self.title = flag,
+                "toc" => self.toc = depth,
+                "todo" => self.todo_keywords = flag,
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns `true` if headline is deeper than `H:` option, and should be
+    /// exported as a list item
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+OPTIONS: H:1\n* a\n** b");
+    /// let options = org.document().export_options();
+    /// let headlines: Vec<_> = org.document().headlines().collect();
+    /// assert!(!options.is_list_headline(&headlines[0]));
+    /// assert!(options.is_list_headline(&headlines[0].headlines().next().unwrap()));
+    /// ```
+    pub fn is_list_headline(&self, headline: &Headline) -> bool {
+        headline.level() > self.headline_levels
+    }
+
+    /// Returns `true` if given subscript or superscript should be interpreted,
+    /// according to `^:` option
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Superscript, export::ExportOptions, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse("a^b c^{d}");
+    /// let sup: Vec<_> = org.document().syntax().descendants().filter_map(Superscript::cast).collect();
+    ///
+    /// let mut options = ExportOptions::default();
+    /// assert!(options.use_sub_superscript(sup[0].syntax()));
+    /// options.apply("^:{}");
+    /// assert!(!options.use_sub_superscript(sup[0].syntax()));
+    /// assert!(options.use_sub_superscript(sup[1].syntax()));
+    /// options.apply("^:nil");
+    /// assert!(!options.use_sub_superscript(sup[1].syntax()));
+    /// ```
+    pub fn use_sub_superscript(&self, node: &SyntaxNode) -> bool {
+        match self.sub_superscript {
+            UseSubSuperscript::Nil => false,
+            UseSubSuperscript::True => true,
+            UseSubSuperscript::Brace => node
+                .children_with_tokens()
+                .any(|e| e.kind() == SyntaxKind::L_CURLY),
+        }
+    }
+}
+
+/// Citation processor, parsed from `#+CITE_EXPORT` keyword
+///
+/// `#+CITE_EXPORT: PROCESSOR BIBLIOGRAPHY-STYLE CITATION-STYLE`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CiteExport {
+    /// Processor name, e.g. `basic` or `csl`
+    pub processor: String,
+    pub bibliography_style: Option<String>,
+    pub citation_style: Option<String>,
+}
+
+impl CiteExport {
+    /// Parses the value of `#+CITE_EXPORT` keyword, returns `None` if it's empty
+    pub fn parse(value: &str) -> Option<CiteExport> {
+        let mut words = value.split_whitespace();
+        Some(CiteExport {
+            processor: words.next()?.to_string(),
+            bibliography_style: words.next().map(String::from),
+            citation_style: words.next().map(String::from),
+        })
+    }
+}
+
+/// Returns `true` if the node is a headline exported as a list item, used to
+/// open and close lists around sibling headlines
+pub(crate) fn is_list_headline_node(options: &ExportOptions, node: Option<SyntaxNode>) -> bool {
+    node.and_then(Headline::cast)
+        .is_some_and(|headline| options.is_list_headline(&headline))
+}
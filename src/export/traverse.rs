@@ -152,6 +152,7 @@ pub trait Traverser {
                 match node.kind() {
                     DOCUMENT => walk!(Document),
                     HEADLINE => walk!(Headline),
+                    INLINETASK => walk!(Inlinetask),
                     SECTION => walk!(Section),
                     PARAGRAPH => walk!(Paragraph),
                     BOLD => walk!(Bold),
@@ -177,10 +178,12 @@ pub trait Traverser {
                     CLOCK => walk!(@Clock),
                     COOKIE => walk!(@Cookie),
                     RADIO_TARGET => walk!(RadioTarget),
+                    RADIO_LINK => walk!(RadioLink),
                     DRAWER => walk!(Drawer),
                     DYN_BLOCK => walk!(DynBlock),
                     FN_DEF => walk!(FnDef),
                     FN_REF => walk!(FnRef),
+                    CITATION => walk!(@Citation),
                     MACROS => walk!(@Macros),
                     SNIPPET => walk!(@Snippet),
                     TIMESTAMP_ACTIVE | TIMESTAMP_INACTIVE | TIMESTAMP_DIARY => walk!(@Timestamp),
@@ -190,6 +193,15 @@ pub trait Traverser {
                     ORG_TABLE => walk!(OrgTable),
                     ORG_TABLE_RULE_ROW | ORG_TABLE_STANDARD_ROW => walk!(OrgTableRow),
                     ORG_TABLE_CELL => walk!(OrgTableCell),
+                    TABLE_EL => {
+                        // table.el content is plain text, which is read with
+                        // `TableEl::grid`, so children aren't traversed
+                        let node = TableEl { syntax: node };
+                        self.event(Event::Enter(Container::TableEl(node.clone())), ctx);
+                        take_control!();
+                        self.event(Event::Leave(Container::TableEl(node)), ctx);
+                        take_control!();
+                    }
                     LINK => walk!(Link),
                     LATEX_FRAGMENT => walk!(@LatexFragment),
                     LATEX_ENVIRONMENT => walk!(@LatexEnvironment),
@@ -197,6 +209,16 @@ pub trait Traverser {
                     LINE_BREAK => walk!(@LineBreak),
                     SUPERSCRIPT => walk!(Superscript),
                     SUBSCRIPT => walk!(Subscript),
+                    KEYWORD
+                        if (Keyword {
+                            syntax: node.clone(),
+                        })
+                        .key()
+                        .eq_ignore_ascii_case("PRINT_BIBLIOGRAPHY") =>
+                    {
+                        self.event(Event::PrintBibliography(Keyword { syntax: node }), ctx);
+                        take_control!();
+                    }
                     KEYWORD => walk!(Keyword),
                     PROPERTY_DRAWER => walk!(PropertyDrawer),
                     #[cfg(feature = "syntax-org-fc")]
@@ -0,0 +1,611 @@
+//! `#+INCLUDE` and `#+SETUPFILE` resolution
+//!
+//! See [`Org::resolve_includes`] for details.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use rowan::ast::AstNode;
+
+use crate::ast::{Headline, Keyword};
+use crate::config::TodoSequence;
+use crate::{Org, ParseConfig, SyntaxKind, SyntaxNode};
+
+/// Loads content of included files
+pub trait Loader {
+    /// Returns content of given file
+    ///
+    /// Relative paths in `#+INCLUDE` and `#+SETUPFILE` are resolved against
+    /// the directory of the file containing them, so `path` is either an
+    /// absolute path or a path relative to the root document.
+    fn load(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Loads files from filesystem
+#[derive(Debug, Clone)]
+pub struct FileLoader {
+    base: PathBuf,
+}
+
+impl FileLoader {
+    /// Creates a loader which resolves relative paths against `base`,
+    /// usually the directory of the root document
+    pub fn new(base: impl Into<PathBuf>) -> FileLoader {
+        FileLoader { base: base.into() }
+    }
+}
+
+impl Loader for FileLoader {
+    fn load(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(self.base.join(path))
+    }
+}
+
+/// Loads files from memory
+///
+/// ```rust
+/// use orgize::include::{Loader, MemoryLoader};
+/// use std::path::Path;
+///
+/// let mut loader = MemoryLoader::default();
+/// loader.insert("chapter.org", "* Chapter");
+/// assert_eq!(loader.load(Path::new("chapter.org")).unwrap(), "* Chapter");
+/// assert!(loader.load(Path::new("missing.org")).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLoader {
+    files: HashMap<PathBuf, String>,
+}
+
+impl MemoryLoader {
+    /// Adds a file, replacing the previous content if it already exists
+    pub fn insert(&mut self, path: impl AsRef<Path>, content: impl Into<String>) {
+        self.files.insert(normalize(path.as_ref()), content.into());
+    }
+}
+
+impl Loader for MemoryLoader {
+    fn load(&self, path: &Path) -> io::Result<String> {
+        self.files.get(&normalize(path)).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} not found", path.display()),
+            )
+        })
+    }
+}
+
+/// Error returned by [`Org::resolve_includes`]
+#[derive(Debug)]
+pub enum IncludeError {
+    /// Loader failed to load given file
+    Load { path: PathBuf, source: io::Error },
+    /// Given file includes itself, directly or indirectly
+    Cycle { path: PathBuf },
+}
+
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncludeError::Load { path, source } => {
+                write!(f, "failed to load {}: {}", path.display(), source)
+            }
+            IncludeError::Cycle { path } => {
+                write!(f, "{} is included recursively", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for IncludeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IncludeError::Load { source, .. } => Some(source),
+            IncludeError::Cycle { .. } => None,
+        }
+    }
+}
+
+impl Org {
+    /// Resolves `#+INCLUDE` and `#+SETUPFILE` keywords using given loader
+    ///
+    /// `#+INCLUDE` keywords are replaced with the content of included files,
+    /// recursively. Following parameters are supported:
+    ///
+    /// - `"file.org::*headline"` and `"file.org::#custom-id"`: only includes given subtree
+    /// - `:lines "5-10"`: only includes lines 5 to 10, 10 excluded
+    /// - `:minlevel 2`: shifts included headlines so that the topmost level becomes 2,
+    ///   defaults to the level of the headline containing this keyword plus one
+    /// - `src rust`, `example`, `export html`: wraps content in given block
+    ///
+    /// Todo keywords and link abbreviations declared in `#+SETUPFILE` are
+    /// merged into parse config of returned org. Like in emacs, setup files
+    /// are treated as if their content were inlined, so their todo keywords
+    /// are merged with `#+TODO` in document in order.
+    ///
+    /// ```rust
+    /// use orgize::{Org, include::MemoryLoader};
+    ///
+    /// let mut loader = MemoryLoader::default();
+    /// loader.insert("setup.org", "#+TODO: NEXT | DONE");
+    /// loader.insert("chapter.org", "* NEXT Chapter\n#+INCLUDE: \"code.rs\" src rust\n");
+    /// loader.insert("code.rs", "fn main() {}\n");
+    ///
+    /// let org = Org::parse("#+SETUPFILE: setup.org\n* Book\n#+INCLUDE: \"chapter.org\"\n");
+    /// let org = org.resolve_includes(&loader).unwrap();
+    ///
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "#+SETUPFILE: setup.org\n* Book\n** NEXT Chapter\n#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n"
+    /// );
+    /// assert_eq!(org.config().todo_keywords.0, ["NEXT"]);
+    ///
+    /// let org = Org::parse("#+SETUPFILE: setup.org\n#+TODO: WAIT | CANCELLED\n* NEXT a");
+    /// let org = org.resolve_includes(&loader).unwrap();
+    /// assert_eq!(org.config().todo_keywords.0, ["NEXT", "WAIT"]);
+    /// assert_eq!(org.config().todo_keywords.1, ["DONE", "CANCELLED"]);
+    /// ```
+    ///
+    /// Returns an error if any file failed to load or is included recursively.
+    ///
+    /// ```rust
+    /// use orgize::{Org, include::{IncludeError, MemoryLoader}};
+    ///
+    /// let mut loader = MemoryLoader::default();
+    /// loader.insert("a.org", "#+INCLUDE: b.org");
+    /// loader.insert("b.org", "#+INCLUDE: a.org");
+    ///
+    /// let org = Org::parse("#+INCLUDE: a.org");
+    /// assert!(matches!(org.resolve_includes(&loader), Err(IncludeError::Cycle { .. })));
+    /// ```
+    pub fn resolve_includes<L: Loader + ?Sized>(&self, loader: &L) -> Result<Org, IncludeError> {
+        let mut resolver = Resolver {
+            loader,
+            config: &self.config,
+            stack: vec![],
+            sequences: vec![],
+            link_abbreviations: vec![],
+        };
+
+        let text = resolver.splice(&self.document().syntax, Path::new(""))?;
+
+        let mut config = self.config.clone();
+        config
+            .link_abbreviations
+            .extend(resolver.link_abbreviations);
+
+        // sequences collected in document order, setup files included, replace the default ones
+        let sequences = resolver.sequences;
+        Ok(config.parse_with(&text, |_| sequences.clone()))
+    }
+}
+
+struct Resolver<'a, L: ?Sized> {
+    loader: &'a L,
+    config: &'a ParseConfig,
+    /// Files being resolved, used for detecting cycles
+    stack: Vec<PathBuf>,
+    /// Todo keywords sequences in document order, including setup files
+    sequences: Vec<TodoSequence>,
+    /// Link abbreviations declared in setup files
+    link_abbreviations: Vec<(String, String)>,
+}
+
+impl<'a, L: Loader + ?Sized> Resolver<'a, L> {
+    fn load(&self, path: &Path) -> Result<String, IncludeError> {
+        self.loader.load(path).map_err(|source| IncludeError::Load {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    fn enter(&mut self, path: &Path) -> Result<(), IncludeError> {
+        if self.stack.iter().any(|p| p == path) {
+            return Err(IncludeError::Cycle {
+                path: path.to_path_buf(),
+            });
+        }
+        self.stack.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Returns content of given document, with `#+INCLUDE` keywords replaced
+    ///
+    /// `dir` is the directory of this document
+    fn splice(&mut self, document: &SyntaxNode, dir: &Path) -> Result<String, IncludeError> {
+        let text = document.to_string();
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+
+        for keyword in document.descendants().filter_map(Keyword::cast) {
+            // in-buffer settings, only top-level ones are honored
+            if keyword.syntax.parent().and_then(|p| p.parent()).as_ref() == Some(document) {
+                if keyword.key().eq_ignore_ascii_case("SETUPFILE") {
+                    let path = normalize(&dir.join(unquote(keyword.value().trim())));
+                    self.setup_file(path)?;
+                    continue;
+                }
+                if let Some(sequence) = TodoSequence::from_keyword(&keyword) {
+                    self.sequences.push(sequence);
+                    continue;
+                }
+            }
+
+            if !keyword.key().eq_ignore_ascii_case("INCLUDE") {
+                continue;
+            }
+            let Some(include) = Include::parse(&keyword.value()) else {
+                continue;
+            };
+
+            let start: usize = keyword.syntax.text_range().start().into();
+            // blank lines following the keyword are preserved
+            let end: usize = keyword
+                .syntax
+                .children_with_tokens()
+                .filter(|e| e.kind() != SyntaxKind::BLANK_LINE)
+                .last()
+                .map(|e| e.text_range().end().into())
+                .unwrap_or(start);
+
+            let level = keyword
+                .syntax
+                .ancestors()
+                .find_map(Headline::cast)
+                .map(|h| h.level());
+
+            output += &text[last..start];
+            output += &self.include(&include, dir, level)?;
+            last = end;
+        }
+
+        output += &text[last..];
+        Ok(output)
+    }
+
+    fn include(
+        &mut self,
+        include: &Include,
+        dir: &Path,
+        level: Option<usize>,
+    ) -> Result<String, IncludeError> {
+        let path = normalize(&dir.join(&include.file));
+        let mut content = self.load(&path)?;
+
+        if let Some(selector) = &include.selector {
+            let org = self.config.clone().parse(&content);
+            content = select(&org.document().syntax, selector).unwrap_or_default();
+        }
+
+        if let Some((start, end)) = include.lines {
+            content = content
+                .split_inclusive('\n')
+                .enumerate()
+                .filter(|(idx, _)| {
+                    (start.unwrap_or(0)..end.unwrap_or(usize::MAX)).contains(&(idx + 1))
+                })
+                .map(|(_, line)| line)
+                .collect();
+        }
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+
+        if let Some((block, parameters)) = &include.block {
+            let mut output = format!("#+BEGIN_{}", block.to_ascii_uppercase());
+            if !parameters.is_empty() {
+                output.push(' ');
+                output += parameters;
+            }
+            output.push('\n');
+            output += &escape_code(&content);
+            output += &format!("#+END_{}\n", block.to_ascii_uppercase());
+            return Ok(output);
+        }
+
+        self.enter(&path)?;
+        let org = self.config.clone().parse(&content);
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let content = self.splice(&org.document().syntax, dir)?;
+        self.stack.pop();
+
+        match include.minlevel.or(level.map(|l| l + 1)) {
+            Some(minlevel) => Ok(shift_headlines(&content, minlevel, self.config)),
+            None => Ok(content),
+        }
+    }
+
+    fn setup_file(&mut self, path: PathBuf) -> Result<(), IncludeError> {
+        self.enter(&path)?;
+
+        let content = self.load(&path)?;
+        let document = self.config.clone().parse(&content).document();
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        for keyword in document.keywords() {
+            if keyword.key().eq_ignore_ascii_case("SETUPFILE") {
+                let path = normalize(&dir.join(unquote(keyword.value().trim())));
+                self.setup_file(path)?;
+            } else if let Some(sequence) = TodoSequence::from_keyword(&keyword) {
+                self.sequences.push(sequence);
+            }
+        }
+
+        self.link_abbreviations
+            .extend(document.link_abbreviations());
+
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+/// Parameters of `#+INCLUDE` keyword
+#[derive(Debug, Default, PartialEq)]
+struct Include {
+    file: String,
+    /// Search option after `::`, e.g. `*headline`
+    selector: Option<String>,
+    /// Block type and its parameters, e.g. `src` and `rust`
+    block: Option<(String, String)>,
+    /// Start and end line, end line excluded
+    lines: Option<(Option<usize>, Option<usize>)>,
+    minlevel: Option<usize>,
+}
+
+impl Include {
+    fn parse(value: &str) -> Option<Include> {
+        let mut words = split_words(value).into_iter().peekable();
+
+        let file = words.next()?;
+        let (file, selector) = match file.split_once("::") {
+            Some((file, selector)) => (file.to_string(), Some(selector.to_string())),
+            None => (file, None),
+        };
+
+        let mut include = Include {
+            file,
+            selector,
+            ..Default::default()
+        };
+
+        if let Some(block) = words.next_if(|w| !w.starts_with(':')) {
+            let mut parameters = vec![];
+            while let Some(word) = words.next_if(|w| !w.starts_with(':')) {
+                parameters.push(word);
+            }
+            include.block = Some((block, parameters.join(" ")));
+        }
+
+        while let Some(key) = words.next() {
+            let value = words.next_if(|w| !w.starts_with(':'));
+            match (key.as_str(), value) {
+                (":lines", Some(value)) => {
+                    let (start, end) = value.split_once('-').unwrap_or((&value, ""));
+                    include.lines = Some((start.trim().parse().ok(), end.trim().parse().ok()));
+                }
+                (":minlevel", Some(value)) => include.minlevel = value.parse().ok(),
+                _ => {}
+            }
+        }
+
+        Some(include)
+    }
+}
+
+/// Splits on whitespace, keeping double-quoted strings as one word
+fn split_words(s: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut chars = s.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut word = String::new();
+        if c == '"' {
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' if chars.peek() == Some(&'"') => {
+                        word.push('"');
+                        chars.next();
+                    }
+                    _ => word.push(c),
+                }
+            }
+        } else {
+            word.push(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                word.push(c);
+            }
+        }
+        words.push(word);
+    }
+
+    words
+}
+
+fn unquote(s: &str) -> String {
+    split_words(s).into_iter().next().unwrap_or_default()
+}
+
+/// Returns the subtree matching given search option
+fn select(document: &SyntaxNode, selector: &str) -> Option<String> {
+    let headline = if let Some(title) = selector.strip_prefix('*') {
+        document
+            .descendants()
+            .filter_map(Headline::cast)
+            .find(|h| h.title_raw().trim() == title.trim())
+    } else if let Some(id) = selector.strip_prefix('#') {
+        document.descendants().filter_map(Headline::cast).find(|h| {
+            h.properties()
+                .and_then(|p| p.get("CUSTOM_ID"))
+                .is_some_and(|v| v.trim() == id.trim())
+        })
+    } else {
+        None
+    };
+
+    Some(headline?.syntax.to_string())
+}
+
+/// Shifts levels of all headlines, so the topmost level becomes `minlevel`
+fn shift_headlines(content: &str, minlevel: usize, config: &ParseConfig) -> String {
+    let document = config.clone().parse(content).document();
+
+    let stars: Vec<_> = document
+        .syntax
+        .descendants()
+        .filter_map(Headline::cast)
+        .filter_map(|h| {
+            h.syntax
+                .children_with_tokens()
+                .find(|e| e.kind() == SyntaxKind::HEADLINE_STARS)
+        })
+        .map(|e| e.text_range())
+        .collect();
+
+    let Some(min) = stars.iter().map(|r| usize::from(r.len())).min() else {
+        return content.to_string();
+    };
+
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    for range in stars {
+        let level = usize::from(range.len()) + minlevel;
+        output += &content[last..range.start().into()];
+        output += &"*".repeat(level.saturating_sub(min).max(1));
+        last = range.end().into();
+    }
+    output += &content[last..];
+    output
+}
+
+/// Escapes lines starting with `*` or `#+` inside blocks, by prepending a comma
+fn escape_code(s: &str) -> String {
+    s.split_inclusive('\n')
+        .map(|line| {
+            let trimmed = line.trim_start_matches([' ', '\t']).trim_start_matches(',');
+            if trimmed.starts_with('*') || trimmed.starts_with("#+") {
+                let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+                format!("{},{}", &line[..indent], &line[indent..])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Removes `.` and `..` components, so the same file always has the same path
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Include::parse(r#" "a b.org::*Intro" src rust :lines "5-10" :minlevel 2"#),
+        Some(Include {
+            file: "a b.org".into(),
+            selector: Some("*Intro".into()),
+            block: Some(("src".into(), "rust".into())),
+            lines: Some((Some(5), Some(10))),
+            minlevel: Some(2),
+        })
+    );
+    assert_eq!(
+        Include::parse(" a.org :lines \"-3\"").unwrap().lines,
+        Some((None, Some(3)))
+    );
+    assert!(Include::parse(" ").is_none());
+}
+
+#[test]
+fn include() {
+    let resolve = |input: &str, files: &[(&str, &str)]| -> String {
+        let mut loader = MemoryLoader::default();
+        for (path, content) in files {
+            loader.insert(path, *content);
+        }
+        Org::parse(input)
+            .resolve_includes(&loader)
+            .unwrap()
+            .to_org()
+    };
+
+    let files = [
+        ("doc/a.org", "* A\n** A1\n#+INCLUDE: \"./b.org::#b2\"\n"),
+        (
+            "doc/b.org",
+            "* B1\n* B2\n:PROPERTIES:\n:CUSTOM_ID: b2\n:END:\n",
+        ),
+        ("lines.txt", "1\n2\n3\n4\n* 5"),
+    ];
+
+    assert_eq!(
+        resolve("#+INCLUDE: doc/a.org :minlevel 2\ntext", &files),
+        "** A\n*** A1\n**** B2\n:PROPERTIES:\n:CUSTOM_ID: b2\n:END:\ntext"
+    );
+    assert_eq!(
+        resolve("#+INCLUDE: \"doc/b.org::*B1\"\n\n* c", &files),
+        "* B1\n\n* c"
+    );
+    assert_eq!(
+        resolve("#+INCLUDE: lines.txt :lines \"2-4\"", &files),
+        "2\n3\n"
+    );
+    assert_eq!(
+        resolve("#+INCLUDE: lines.txt example :lines \"4-\"", &files),
+        "#+BEGIN_EXAMPLE\n4\n,* 5\n#+END_EXAMPLE\n"
+    );
+}
+
+#[test]
+fn setup_file() {
+    let mut loader = MemoryLoader::default();
+    loader.insert(
+        "setup/a.org",
+        "#+SETUPFILE: b.org\n#+LINK: gh https://github.com/%s",
+    );
+    loader.insert(
+        "setup/b.org",
+        "#+TODO: A B | C\n#+SETUPFILE: ../setup/a.org",
+    );
+
+    let org = Org::parse("#+SETUPFILE: \"setup/a.org\"");
+    assert!(matches!(
+        org.resolve_includes(&loader),
+        Err(IncludeError::Cycle { .. })
+    ));
+
+    loader.insert("setup/b.org", "#+TODO: A B | C");
+    let org = org.resolve_includes(&loader).unwrap();
+    assert_eq!(org.config().todo_keywords.0, ["A", "B"]);
+    assert_eq!(org.config().todo_keywords.1, ["C"]);
+    assert_eq!(org.link_abbreviations().len(), 1);
+
+    // setup files in included files are resolved against their directory
+    loader.insert("sub/c.org", "#+SETUPFILE: s2.org\n");
+    loader.insert("sub/s2.org", "#+TODO: NEXT | DONE");
+    let org = Org::parse("#+INCLUDE: sub/c.org\n#+TODO: WAIT | CANCELLED\n* NEXT a\n* WAIT b");
+    let org = org.resolve_includes(&loader).unwrap();
+    assert_eq!(org.config().todo_keywords.0, ["NEXT", "WAIT"]);
+    let headlines: Vec<_> = org.document().headlines().collect();
+    assert_eq!(headlines[0].todo_keyword().unwrap(), "NEXT");
+    assert_eq!(headlines[1].todo_keyword().unwrap(), "WAIT");
+}
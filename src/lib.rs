@@ -1,12 +1,15 @@
 #![doc = include_str!("../README.md")]
 
+pub mod agenda;
 pub mod ast;
 pub mod config;
 mod entities;
 pub mod export;
+pub mod include;
 mod org;
 mod replace;
 mod syntax;
+pub mod table;
 #[cfg(test)]
 mod tests;
 
@@ -42,6 +42,8 @@ impl Org {
     /// Convert org element tree to html-format using default html handler
     pub fn to_html(&self) -> String {
         let mut handler = HtmlExport::default();
+        handler.extend_link_abbreviations(self.config.link_abbreviations.iter().cloned());
+        handler.set_parse_config(self.config.clone());
         self.traverse(&mut handler);
         handler.finish()
     }
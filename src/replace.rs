@@ -7,7 +7,7 @@ use crate::ast::Headline;
 use crate::syntax::{
     combinator::line_starts_iter, document::document_node, headline::headline_node, OrgLanguage,
 };
-use crate::Org;
+use crate::{Org, ParseConfig};
 
 #[derive(Debug)]
 enum RangeShape {
@@ -54,17 +54,18 @@ enum ReplaceWithShape {
 }
 
 impl ReplaceWithShape {
-    fn new(text: &str) -> Self {
+    fn new(text: &str, config: &ParseConfig) -> Self {
         let mut result = ReplaceWithShape::Other;
 
         for start in line_starts_iter(text) {
             let level = text[start..].bytes().take_while(|&c| c == b'*').count();
 
-            if level == 0 {
+            // inlinetask isn't headline
+            if level == 0 || level >= config.inlinetask_min_level {
                 continue;
             }
 
-            if !matches!(text[start..].as_bytes().get(level), Some(b' ')) {
+            if !matches!(text.as_bytes()[start..].get(level), Some(b' ')) {
                 continue;
             }
 
@@ -116,7 +117,7 @@ impl Org {
         let replace_with = replace_with.as_ref();
         match (
             RangeShape::new(self.document().syntax, range),
-            ReplaceWithShape::new(replace_with),
+            ReplaceWithShape::new(replace_with, &self.config),
         ) {
             (
                 RangeShape::ExactHeadline { headline, level },
@@ -133,7 +134,7 @@ impl Org {
             ) if level <= new_level
             // non-last headline must ends with a newline
                 && (headline.end() == self.document().end()
-                    || replace_with.ends_with(&['\n', '\r'])) =>
+                    || replace_with.ends_with(['\n', '\r'])) =>
             {
                 self.replace_headline(headline, range, replace_with)
             }
@@ -221,28 +222,36 @@ fn replace() {
         TextSize::new(1)
     ));
 
-    assert_eq!(ReplaceWithShape::new(""), ReplaceWithShape::Other);
-    assert_eq!(ReplaceWithShape::new(" ** a"), ReplaceWithShape::Other);
+    let config = &ParseConfig::default();
+    assert_eq!(ReplaceWithShape::new("", config), ReplaceWithShape::Other);
     assert_eq!(
-        ReplaceWithShape::new("\n** a"),
+        ReplaceWithShape::new(" ** a", config),
+        ReplaceWithShape::Other
+    );
+    assert_eq!(
+        ReplaceWithShape::new("\n** a", config),
         ReplaceWithShape::IncludeHeadline { level: 2 }
     );
     assert_eq!(
-        ReplaceWithShape::new("** a"),
+        ReplaceWithShape::new("** a", config),
         ReplaceWithShape::ExactHeadline { level: 2 }
     );
     assert_eq!(
-        ReplaceWithShape::new("** a\n* 1"),
+        ReplaceWithShape::new("** a\n* 1", config),
         ReplaceWithShape::IncludeHeadline { level: 1 }
     );
     assert_eq!(
-        ReplaceWithShape::new("* a\n** 1"),
+        ReplaceWithShape::new("* a\n** 1", config),
         ReplaceWithShape::ExactHeadline { level: 1 }
     );
     assert_eq!(
-        ReplaceWithShape::new("** a\n** 1"),
+        ReplaceWithShape::new("** a\n** 1", config),
         ReplaceWithShape::IncludeHeadline { level: 2 }
     );
+    assert_eq!(
+        ReplaceWithShape::new("*************** a", config),
+        ReplaceWithShape::Other
+    );
 
     assert!(matches!(
         RangeShape::new(
@@ -1,8 +1,8 @@
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take_while, take_while1},
-    character::complete::{alpha1, space0, space1},
-    combinator::{cond, opt},
+    bytes::complete::{tag, tag_no_case, take_until, take_while, take_while1},
+    character::complete::{alpha1, digit1, space0, space1},
+    combinator::{cond, not, opt, recognize},
     sequence::{separated_pair, tuple},
     IResult, InputTake,
 };
@@ -56,7 +56,7 @@ fn block_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
     Err(nom::Err::Error(()))
 }
 
-fn block_begin_node(input: Input) -> IResult<Input, (GreenElement, &str), ()> {
+fn block_begin_node(input: Input<'_>) -> IResult<Input<'_>, (GreenElement, &str), ()> {
     let (input, (ws1, begin, name)) = tuple((space0, tag_no_case("#+BEGIN_"), alpha1))(input)?;
 
     let mut b = NodeBuilder::new();
@@ -120,13 +120,22 @@ fn source_block_switches(input: Input) -> IResult<Input, Input, ()> {
         match tuple::<_, _, (), _>((
             cond(i.len() != input.len(), space1),
             alt((
-                separated_pair(
-                    alt((tag("-l"), tag("-n"))),
+                recognize(separated_pair(
+                    tag("-l"),
                     space1,
-                    take_while1(|c: char| c != ' ' && c != '\t' && c != '\n' && c != '\r'),
-                ),
-                tuple((tag("+"), alpha1)),
-                tuple((tag("-"), alpha1)),
+                    alt((
+                        recognize(tuple((tag("\""), take_until("\""), tag("\"")))),
+                        take_while1(|c: char| c != ' ' && c != '\t' && c != '\n' && c != '\r'),
+                    )),
+                )),
+                // -n and +n takes an optional number
+                recognize(tuple((
+                    alt((tag("-n"), tag("+n"))),
+                    not(alpha1),
+                    opt(tuple((space1, digit1, not(alpha1)))),
+                ))),
+                recognize(tuple((tag("+"), alpha1))),
+                recognize(tuple((tag("-"), alpha1))),
             )),
         ))(i)
         {
@@ -301,5 +310,26 @@ alert('Hello World!');
     "###
     );
 
+    insta::assert_debug_snapshot!(
+        to_src_block("#+begin_src c +n 10 -l \"(ref: %s)\" -r :tangle yes\n#+end_src").syntax,
+        @r###"
+    SOURCE_BLOCK@0..59
+      BLOCK_BEGIN@0..50
+        TEXT@0..8 "#+begin_"
+        TEXT@8..11 "src"
+        WHITESPACE@11..12 " "
+        SRC_BLOCK_LANGUAGE@12..13 "c"
+        WHITESPACE@13..14 " "
+        SRC_BLOCK_SWITCHES@14..37 "+n 10 -l \"(ref: %s)\" -r"
+        WHITESPACE@37..38 " "
+        SRC_BLOCK_PARAMETERS@38..49 ":tangle yes"
+        NEW_LINE@49..50 "\n"
+      BLOCK_CONTENT@50..50
+      BLOCK_END@50..59
+        TEXT@50..56 "#+end_"
+        TEXT@56..59 "src"
+    "###
+    );
+
     // TODO: more testing
 }
@@ -0,0 +1,237 @@
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::opt,
+    sequence::{pair, tuple},
+    Err, IResult, InputTake,
+};
+
+use super::{
+    combinator::{colon_token, l_bracket_token, node, GreenElement, NodeBuilder},
+    input::Input,
+    object::minimal_object_nodes,
+    SyntaxKind::{self, *},
+};
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn citation_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    crate::lossless_parser!(citation_node_base, input)
+}
+
+fn citation_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    let (input, (l_bracket, cite, style, colon)) = tuple((
+        l_bracket_token,
+        tag("cite"),
+        opt(pair(
+            tag("/"),
+            take_while1(|c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/')),
+        )),
+        colon_token,
+    ))(input)?;
+
+    let (input, contents) = balanced_brackets(input)?;
+    let (input, r_bracket) = input.take_split(1);
+
+    let segments = split_segments(contents);
+
+    let has_key = |i: &Input| key_position(i.s).is_some();
+    let last = segments.len() - 1;
+    let prefix = (last > 0 && !has_key(&segments[0])).then_some(segments[0]);
+    let suffix = (last > 0 && !has_key(&segments[last])).then_some(segments[last]);
+
+    let references = &segments[prefix.map_or(0, |_| 1)..segments.len() - suffix.map_or(0, |_| 1)];
+    if references.is_empty() || !references.iter().all(has_key) {
+        return Err(Err::Error(()));
+    }
+
+    let mut b = NodeBuilder::new();
+    b.push(l_bracket);
+    b.push(cite.text_token());
+    if let Some((slash, style)) = style {
+        b.token(SLASH, slash);
+        b.text(style);
+    }
+    b.push(colon);
+
+    // semicolons between segments
+    let mut semicolons = contents
+        .s
+        .match_indices(';')
+        .map(|(i, _)| contents.of(&contents.s[i..i + 1]));
+
+    if let Some(prefix) = prefix {
+        affix(&mut b, prefix, CITATION_PREFIX);
+        b.token(SEMICOLON, semicolons.next().unwrap());
+    }
+    for (idx, reference) in references.iter().enumerate() {
+        if idx != 0 {
+            b.token(SEMICOLON, semicolons.next().unwrap());
+        }
+        b.push(reference_node(*reference));
+    }
+    if let Some(suffix) = suffix {
+        b.token(SEMICOLON, semicolons.next().unwrap());
+        affix(&mut b, suffix, CITATION_SUFFIX);
+    }
+
+    b.push(r_bracket.token(R_BRACKET));
+
+    Ok((input, b.finish(CITATION)))
+}
+
+/// `KEYPREFIX @KEY KEYSUFFIX`
+fn reference_node(input: Input) -> GreenElement {
+    let (start, end) = trimmed(input.s);
+    let (at, key_end) = key_position(input.s).expect("reference must contains key");
+
+    let mut b = NodeBuilder::new();
+    b.ws(input.of(&input.s[..start]));
+    if at > start {
+        b.push(node(
+            CITATION_PREFIX,
+            minimal_object_nodes(input.of(&input.s[start..at])),
+        ));
+    }
+    b.token(AT, input.of(&input.s[at..at + 1]));
+    b.text(input.of(&input.s[at + 1..key_end]));
+    if end > key_end {
+        b.push(node(
+            CITATION_SUFFIX,
+            minimal_object_nodes(input.of(&input.s[key_end..end])),
+        ));
+    }
+    b.ws(input.of(&input.s[end..]));
+    b.finish(CITATION_REFERENCE)
+}
+
+/// Global prefix or suffix, surrounding whitespaces are kept outside of the node
+fn affix(b: &mut NodeBuilder, input: Input, kind: SyntaxKind) {
+    let (start, end) = trimmed(input.s);
+    b.ws(input.of(&input.s[..start]));
+    if start < end {
+        b.push(node(
+            kind,
+            minimal_object_nodes(input.of(&input.s[start..end])),
+        ));
+    }
+    b.ws(input.of(&input.s[end..]));
+}
+
+/// Returns the start and end of `s` without surrounding whitespaces
+fn trimmed(s: &str) -> (usize, usize) {
+    let start = s.len() - s.trim_start().len();
+    let end = s.trim_end().len().max(start);
+    (start, end)
+}
+
+fn split_segments(contents: Input) -> Vec<Input> {
+    let mut segments = vec![];
+    let mut start = 0;
+    for (i, _) in contents.s.match_indices(';') {
+        segments.push(contents.of(&contents.s[start..i]));
+        start = i + 1;
+    }
+    segments.push(contents.of(&contents.s[start..]));
+    segments
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || "!#$%&'()*+./:<>?@^_`{|}~-".contains(c)
+}
+
+/// Returns the position of `@` and the end of the first key in `s`
+fn key_position(s: &str) -> Option<(usize, usize)> {
+    s.match_indices('@').find_map(|(at, _)| {
+        let len = s[at + 1..]
+            .find(|c: char| !is_key_char(c))
+            .unwrap_or(s.len() - at - 1);
+        (len > 0).then_some((at, at + 1 + len))
+    })
+}
+
+/// Takes contents until the unbalanced closing bracket
+fn balanced_brackets(input: Input) -> IResult<Input, Input, ()> {
+    let mut pairs = 0;
+    for (i, b) in input.bytes().enumerate() {
+        match b {
+            b'[' => pairs += 1,
+            b']' if pairs == 0 => {
+                let (input, contents) = input.take_split(i);
+                return Ok((input, contents));
+            }
+            b']' => pairs -= 1,
+            _ => {}
+        }
+    }
+    Err(Err::Error(()))
+}
+
+#[test]
+fn parse() {
+    use crate::{ast::Citation, tests::to_ast, ParseConfig};
+
+    let to_citation = to_ast::<Citation>(citation_node);
+
+    insta::assert_debug_snapshot!(
+        to_citation("[cite:@key]").syntax,
+        @r###"
+    CITATION@0..11
+      L_BRACKET@0..1 "["
+      TEXT@1..5 "cite"
+      COLON@5..6 ":"
+      CITATION_REFERENCE@6..10
+        AT@6..7 "@"
+        TEXT@7..10 "key"
+      R_BRACKET@10..11 "]"
+    "###
+    );
+
+    insta::assert_debug_snapshot!(
+        to_citation("[cite/t/b:see; @doe2020; see *also* @smith p. 3 ; and more]").syntax,
+        @r###"
+    CITATION@0..59
+      L_BRACKET@0..1 "["
+      TEXT@1..5 "cite"
+      SLASH@5..6 "/"
+      TEXT@6..9 "t/b"
+      COLON@9..10 ":"
+      CITATION_PREFIX@10..13
+        TEXT@10..13 "see"
+      SEMICOLON@13..14 ";"
+      CITATION_REFERENCE@14..23
+        WHITESPACE@14..15 " "
+        AT@15..16 "@"
+        TEXT@16..23 "doe2020"
+      SEMICOLON@23..24 ";"
+      CITATION_REFERENCE@24..48
+        WHITESPACE@24..25 " "
+        CITATION_PREFIX@25..36
+          TEXT@25..29 "see "
+          BOLD@29..35
+            STAR@29..30 "*"
+            TEXT@30..34 "also"
+            STAR@34..35 "*"
+          TEXT@35..36 " "
+        AT@36..37 "@"
+        TEXT@37..42 "smith"
+        CITATION_SUFFIX@42..47
+          TEXT@42..47 " p. 3"
+        WHITESPACE@47..48 " "
+      SEMICOLON@48..49 ";"
+      WHITESPACE@49..50 " "
+      CITATION_SUFFIX@50..58
+        TEXT@50..58 "and more"
+      R_BRACKET@58..59 "]"
+    "###
+    );
+
+    let config = &ParseConfig::default();
+
+    assert!(citation_node(("[cite:key]", config).into()).is_err());
+    assert!(citation_node(("[cite:@key", config).into()).is_err());
+    assert!(citation_node(("[cite:@a;b;@c]", config).into()).is_err());
+    assert!(citation_node(("[cite:a;b]", config).into()).is_err());
+    assert!(citation_node(("[cite/:@a]", config).into()).is_err());
+}
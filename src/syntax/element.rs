@@ -12,6 +12,7 @@ use super::{
     dyn_block::dyn_block_node,
     fixed_width::fixed_width_node,
     fn_def::fn_def_node,
+    headline::inlinetask_node,
     input::Input,
     keyword::{affiliated_keyword_nodes, keyword_node},
     latex_environment::latex_environment_node,
@@ -87,9 +88,14 @@ pub fn element_node(input: Input) -> IResult<Input, GreenElement, ()> {
 
     let result = match byte {
         Some(b'[') => fn_def_node(input),
-        Some(b'0'..=b'9') | Some(b'*') => list_node(input),
+        Some(b'0'..=b'9') => list_node(input),
+        // inlinetask doesn't have affiliated keywords
+        Some(b'*') if !has_affiliated_keyword => {
+            inlinetask_node(input).or_else(|_| list_node(input))
+        }
+        Some(b'*') => list_node(input),
         // clock doesn't have affiliated keywords
-        Some(b'C') if !has_affiliated_keyword => clock_node(input),
+        Some(b'C') if !has_affiliated_keyword => clock_node(input).or_else(|_| list_node(input)),
         Some(b'-') => rule_node(input).or_else(|_| list_node(input)),
         Some(b':') => drawer_node(input).or_else(|_| fixed_width_node(input)),
         Some(b'|') => org_table_node(input),
@@ -99,6 +105,7 @@ pub fn element_node(input: Input) -> IResult<Input, GreenElement, ()> {
             .or_else(|_| dyn_block_node(input))
             .or_else(|_| comment_node(input)),
         Some(b'\\') => latex_environment_node(input),
+        Some(b) if b.is_ascii_alphabetic() && input.c.list_allow_alphabetical => list_node(input),
         _ => Err(nom::Err::Error(())),
     };
 
@@ -138,7 +145,8 @@ impl<'a> Iterator for ElementPositions<'a> {
             if matches!(
                 b,
                 b'[' | b'0'..=b'9' | b'*' | b'C' | b'-' | b':' | b'|' | b'+' | b'#' | b'\\'
-            ) {
+            ) || (b.is_ascii_alphabetic() && self.input.c.list_allow_alphabetical)
+            {
                 let previous = self.pos;
                 self.pos = iter
                     .next()
@@ -9,8 +9,8 @@ use nom::{
 
 use super::{
     combinator::{
-        hash_token, l_bracket_token, line_starts_iter, node, r_bracket_token, token, trim_line_end,
-        GreenElement, NodeBuilder,
+        blank_lines, hash_token, l_bracket_token, line_starts_iter, node, r_bracket_token, token,
+        trim_line_end, GreenElement, NodeBuilder,
     },
     drawer::property_drawer_node,
     element::element_nodes,
@@ -36,6 +36,49 @@ fn headline_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
 
     b.token(HEADLINE_STARS, stars);
 
+    let input = title_line(input, &mut b)?;
+
+    if input.is_empty() {
+        return Ok((input, b.finish(HEADLINE)));
+    }
+
+    let (input, planning) = opt(planning_node)(input)?;
+    b.push_opt(planning);
+
+    if input.is_empty() {
+        return Ok((input, b.finish(HEADLINE)));
+    }
+
+    let (input, property_drawer) = opt(property_drawer_node)(input)?;
+    b.push_opt(property_drawer);
+
+    if input.is_empty() {
+        return Ok((input, b.finish(HEADLINE)));
+    }
+
+    let (input, section) = opt(section_node)(input)?;
+    b.push_opt(section);
+
+    let mut i = input;
+    let current_level = stars.len();
+    while !i.is_empty() {
+        let next_level = i.bytes().take_while(|&c| c == b'*').count();
+
+        if next_level <= current_level {
+            break;
+        }
+
+        let (input, headline) = headline_node(i)?;
+        b.push(headline);
+        debug_assert!(i.len() > input.len(), "{} > {}", i.len(), input.len());
+        i = input;
+    }
+
+    Ok((i, b.finish(HEADLINE)))
+}
+
+/// Recognizes keyword, priority, title and tags following the headline stars
+fn title_line<'a>(input: Input<'a>, b: &mut NodeBuilder) -> Result<Input<'a>, nom::Err<()>> {
     let (input, ws) = space0(input)?;
     b.ws(ws);
 
@@ -63,43 +106,99 @@ fn headline_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
     b.ws(ws_);
     b.nl(nl);
 
-    if input.is_empty() {
-        return Ok((input, b.finish(HEADLINE)));
-    }
+    Ok(input)
+}
 
-    let (input, planning) = opt(planning_node)(input)?;
-    b.push_opt(planning);
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn inlinetask_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    debug_assert!(!input.is_empty());
+    crate::lossless_parser!(inlinetask_node_base, input)
+}
 
-    if input.is_empty() {
-        return Ok((input, b.finish(HEADLINE)));
+fn inlinetask_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    let (input, stars) = inlinetask_stars(input)?;
+
+    let mut b = NodeBuilder::new();
+
+    b.token(HEADLINE_STARS, stars);
+
+    let input = title_line(input, &mut b)?;
+
+    // inlinetask without an ending line only contains its title
+    let Some((contents, end, input)) = inlinetask_end(input) else {
+        let (input, post_blank) = blank_lines(input)?;
+        b.children.extend(post_blank);
+        return Ok((input, b.finish(INLINETASK)));
+    };
+
+    let mut contents = contents;
+
+    if !contents.is_empty() {
+        let (input, planning) = opt(planning_node)(contents)?;
+        b.push_opt(planning);
+        contents = input;
     }
 
-    let (input, property_drawer) = opt(property_drawer_node)(input)?;
-    b.push_opt(property_drawer);
+    if !contents.is_empty() {
+        let (input, property_drawer) = opt(property_drawer_node)(contents)?;
+        b.push_opt(property_drawer);
+        contents = input;
+    }
 
-    if input.is_empty() {
-        return Ok((input, b.finish(HEADLINE)));
+    if !contents.is_empty() {
+        b.children.extend(element_nodes(contents)?);
     }
 
-    let (input, section) = opt(section_node)(input)?;
-    b.push_opt(section);
+    let (end, (end_stars, ws)) = tuple((take_while1(|c: char| c == '*'), space0))(end)?;
+    let (_, (end_keyword, ws_, nl)) = trim_line_end(end)?;
 
-    let mut i = input;
-    let current_level = stars.len();
-    while !i.is_empty() {
-        let next_level = i.bytes().take_while(|&c| c == b'*').count();
+    let mut b_ = NodeBuilder::new();
+    b_.token(HEADLINE_STARS, end_stars);
+    b_.ws(ws);
+    b_.text(end_keyword);
+    b_.ws(ws_);
+    b_.nl(nl);
+    b.push(b_.finish(INLINETASK_END));
 
-        if next_level <= current_level {
-            break;
+    let (input, post_blank) = blank_lines(input)?;
+    b.children.extend(post_blank);
+
+    Ok((input, b.finish(INLINETASK)))
+}
+
+/// Searches for the ending line of inlinetask
+///
+/// Returns inlinetask contents, the ending line and the rest input
+fn inlinetask_end(input: Input) -> Option<(Input, Input, Input)> {
+    for start in line_starts_iter(input.as_str()) {
+        let line = input.slice(start..);
+        let bytes = line.as_bytes();
+        let level = bytes.iter().take_while(|&&c| c == b'*').count();
+
+        if level == 0 || !matches!(bytes.get(level), Some(b' ' | b'\t')) {
+            continue;
         }
 
-        let (input, headline) = headline_node(i)?;
-        b.push(headline);
-        debug_assert!(i.len() > input.len(), "{} > {}", i.len(), input.len());
-        i = input;
+        // only the first headline-like line is considered, lines with fewer
+        // stars are headlines even if their title is `END`
+        let (rest, (contents, _, _)) = trim_line_end(line).ok()?;
+        if level < input.c.inlinetask_min_level || contents.as_str()[level..].trim_start() != "END"
+        {
+            return None;
+        }
+
+        let end_len = line.len() - rest.len();
+        return Some((
+            input.slice(0..start),
+            line.slice(0..end_len),
+            line.slice(end_len..),
+        ));
     }
 
-    Ok((i, b.finish(HEADLINE)))
+    None
 }
 
 #[cfg_attr(
@@ -134,7 +233,8 @@ fn headline_stars(input: Input) -> IResult<Input, Input, ()> {
     let bytes = input.as_bytes();
     let level = bytes.iter().take_while(|&&c| c == b'*').count();
 
-    if level == 0 {
+    // headlines with too many stars are parsed as inlinetask
+    if level == 0 || level >= input.c.inlinetask_min_level {
         Err(nom::Err::Error(()))
     }
     // headline stars must be followed by space
@@ -145,6 +245,17 @@ fn headline_stars(input: Input) -> IResult<Input, Input, ()> {
     }
 }
 
+fn inlinetask_stars(input: Input) -> IResult<Input, Input, ()> {
+    let bytes = input.as_bytes();
+    let level = bytes.iter().take_while(|&&c| c == b'*').count();
+
+    if level >= input.c.inlinetask_min_level && matches!(bytes.get(level), Some(b' ')) {
+        Ok(input.take_split(level))
+    } else {
+        Err(nom::Err::Error(()))
+    }
+}
+
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "debug", skip(input), fields(input = input.s))
@@ -367,3 +478,89 @@ fn issue_15_16() {
         tags.map(|x| x.to_string()).collect::<Vec<_>>(),
     );
 }
+
+#[test]
+fn inlinetask() {
+    use crate::{ast::Inlinetask, tests::to_ast, ParseConfig};
+
+    let to_inlinetask = to_ast::<Inlinetask>(inlinetask_node);
+
+    insta::assert_debug_snapshot!(
+        to_inlinetask("*************** TODO [#A] task :tag:\nSCHEDULED: <2019-04-08 Mon>\ncontent\n*************** END\n\n").syntax,
+        @r###"
+    INLINETASK@0..94
+      HEADLINE_STARS@0..15 "***************"
+      WHITESPACE@15..16 " "
+      HEADLINE_KEYWORD_TODO@16..20 "TODO"
+      WHITESPACE@20..21 " "
+      HEADLINE_PRIORITY@21..25
+        L_BRACKET@21..22 "["
+        HASH@22..23 "#"
+        TEXT@23..24 "A"
+        R_BRACKET@24..25 "]"
+      WHITESPACE@25..26 " "
+      HEADLINE_TITLE@26..31
+        TEXT@26..31 "task "
+      HEADLINE_TAGS@31..36
+        COLON@31..32 ":"
+        TEXT@32..35 "tag"
+        COLON@35..36 ":"
+      NEW_LINE@36..37 "\n"
+      PLANNING@37..65
+        PLANNING_SCHEDULED@37..64
+          TEXT@37..47 "SCHEDULED:"
+          WHITESPACE@47..48 " "
+          TIMESTAMP_ACTIVE@48..64
+            L_ANGLE@48..49 "<"
+            TIMESTAMP_YEAR@49..53 "2019"
+            MINUS@53..54 "-"
+            TIMESTAMP_MONTH@54..56 "04"
+            MINUS@56..57 "-"
+            TIMESTAMP_DAY@57..59 "08"
+            WHITESPACE@59..60 " "
+            TIMESTAMP_DAYNAME@60..63 "Mon"
+            R_ANGLE@63..64 ">"
+        NEW_LINE@64..65 "\n"
+      PARAGRAPH@65..73
+        TEXT@65..73 "content\n"
+      INLINETASK_END@73..93
+        HEADLINE_STARS@73..88 "***************"
+        WHITESPACE@88..89 " "
+        TEXT@89..92 "END"
+        NEW_LINE@92..93 "\n"
+      BLANK_LINE@93..94 "\n"
+    "###
+    );
+
+    insta::assert_debug_snapshot!(
+        to_inlinetask("*************** task\n\n").syntax,
+        @r###"
+    INLINETASK@0..22
+      HEADLINE_STARS@0..15 "***************"
+      WHITESPACE@15..16 " "
+      HEADLINE_TITLE@16..20
+        TEXT@16..20 "task"
+      NEW_LINE@20..21 "\n"
+      BLANK_LINE@21..22 "\n"
+    "###
+    );
+
+    let task = to_inlinetask("*************** task\ncontent\n");
+    assert!(!task.has_end());
+    assert_eq!(task.syntax.to_string(), "*************** task\n");
+
+    let task = to_inlinetask("*************** task\ncontent\n** END\n");
+    assert!(!task.has_end());
+    assert_eq!(task.syntax.to_string(), "*************** task\n");
+
+    let config = &ParseConfig::default();
+    assert!(inlinetask_node(("************** task", config).into()).is_err());
+    assert!(inlinetask_node(("***************", config).into()).is_err());
+
+    let config = &ParseConfig {
+        inlinetask_min_level: 3,
+        ..Default::default()
+    };
+    assert!(inlinetask_node(("*** task", config).into()).is_ok());
+    assert!(headline_node(("*** task", config).into()).is_err());
+}
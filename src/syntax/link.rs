@@ -1,13 +1,14 @@
 use nom::{
-    bytes::complete::take_while,
-    combinator::{map, opt},
+    bytes::complete::{take_while, take_while1},
+    combinator::{map, opt, verify},
     sequence::tuple,
-    IResult,
+    IResult, InputTake,
 };
 
 use super::{
     combinator::{
-        l_bracket2_token, l_bracket_token, node, r_bracket2_token, r_bracket_token, GreenElement,
+        l_angle_token, l_bracket2_token, l_bracket_token, node, r_angle_token, r_bracket2_token,
+        r_bracket_token, GreenElement,
     },
     input::Input,
     object::link_description_object_nodes,
@@ -46,6 +47,99 @@ pub fn link_node(input: Input) -> IResult<Input, GreenElement, ()> {
     crate::lossless_parser!(parser, input)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn angle_link_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    let mut parser = map(
+        tuple((
+            l_angle_token,
+            verify(
+                take_while1(|c: char| c != '<' && c != '>' && c != '\n'),
+                |path: &Input| link_type(path).is_some(),
+            ),
+            r_angle_token,
+        )),
+        |(l_angle, path, r_angle)| node(LINK, [l_angle, path.token(LINK_PATH), r_angle]),
+    );
+    crate::lossless_parser!(parser, input)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn plain_link_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    crate::lossless_parser!(plain_link_node_base, input)
+}
+
+fn plain_link_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    let ty = link_type(&input).ok_or(nom::Err::Error(()))?;
+
+    let len = plain_link_path_len(&input.s[ty.len() + 1..]);
+
+    if len == 0 {
+        return Err(nom::Err::Error(()));
+    }
+
+    let (input, path) = input.take_split(ty.len() + 1 + len);
+
+    Ok((input, node(LINK, [path.token(LINK_PATH)])))
+}
+
+/// Returns `true` if a plain link can start right after `pre`
+///
+/// Plain link must start at the beginning of a word.
+pub fn verify_plain_link_pre(pre: &Input) -> bool {
+    !pre.s.ends_with(|c: char| c.is_alphanumeric())
+}
+
+/// Returns link type if input starts with `TYPE:` and `TYPE` is one of `link_types`
+fn link_type<'a>(input: &Input<'a>) -> Option<&'a str> {
+    let len = input
+        .bytes()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'+' | b'_'))
+        .count();
+
+    let (ty, rest) = input.s.split_at(len);
+
+    if rest.starts_with(':') && input.c.link_types.iter().any(|t| t == ty) {
+        Some(ty)
+    } else {
+        None
+    }
+}
+
+/// Returns the length of plain link path, following the rules of `org-link-plain-re`:
+///
+/// 1. whitespaces, brackets and angle brackets are not allowed
+/// 2. parentheses are allowed if they're balanced (up to two levels)
+/// 3. path cannot end with punctuation, except `/` and `)`
+fn plain_link_path_len(s: &str) -> usize {
+    let mut depth = 0;
+    let mut end = 0;
+
+    for (idx, c) in s.char_indices() {
+        match c {
+            ' ' | '\t' | '\r' | '\n' | '[' | ']' | '<' | '>' => break,
+            '(' if depth < 2 => depth += 1,
+            '(' => break,
+            ')' if depth > 0 => depth -= 1,
+            ')' => break,
+            _ => {}
+        }
+
+        if depth == 0 {
+            end = idx + c.len_utf8();
+        }
+    }
+
+    s[..end]
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() && c != '/' && c != ')')
+        .len()
+}
+
 #[test]
 fn parse() {
     use crate::{ast::Link, tests::to_ast, ParseConfig};
@@ -109,4 +203,47 @@ fn parse() {
     let config = &ParseConfig::default();
 
     assert!(link_node(("[[#id][desc]", config).into()).is_err());
+
+    let to_angle_link = to_ast::<Link>(angle_link_node);
+
+    insta::assert_debug_snapshot!(
+        to_angle_link("<mailto:poiscript@gmail.com>").syntax,
+        @r###"
+    LINK@0..28
+      L_ANGLE@0..1 "<"
+      LINK_PATH@1..27 "mailto:poiscript@gmai ..."
+      R_ANGLE@27..28 ">"
+    "###
+    );
+
+    assert!(angle_link_node(("<unknown:path>", config).into()).is_err());
+    assert!(angle_link_node(("<https://orgmode.org\n>", config).into()).is_err());
+
+    let to_plain_link = to_ast::<Link>(plain_link_node);
+
+    insta::assert_debug_snapshot!(
+        to_plain_link("https://orgmode.org/worg/").syntax,
+        @r###"
+    LINK@0..25
+      LINK_PATH@0..25 "https://orgmode.org/w ..."
+    "###
+    );
+
+    let link = |s| plain_link_node((s, config).into()).map(|(_, n)| n.to_string());
+    assert_eq!(link("https://orgmode.org.").unwrap(), "https://orgmode.org");
+    assert_eq!(
+        link("https://orgmode.org), ").unwrap(),
+        "https://orgmode.org"
+    );
+    assert_eq!(
+        link("https://en.wikipedia.org/wiki/Org-mode_(disambiguation)!").unwrap(),
+        "https://en.wikipedia.org/wiki/Org-mode_(disambiguation)"
+    );
+    assert_eq!(
+        link("file:a.org::*heading").unwrap(),
+        "file:a.org::*heading"
+    );
+    assert!(link("https:").is_err());
+    assert!(link("https:.").is_err());
+    assert!(link("unknown:path").is_err());
 }
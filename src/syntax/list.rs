@@ -80,6 +80,7 @@ fn list_item_node<'a>(
     indent: Input<'a>,
     input: Input<'a>,
 ) -> IResult<Input<'a>, (bool, GreenElement), ()> {
+    let alphabetical = input.c.list_allow_alphabetical;
     let (input, bullet) = recognize(tuple((
         alt((
             tag("+"),
@@ -87,6 +88,12 @@ fn list_item_node<'a>(
             tag("-"),
             preceded(digit1, tag(".")),
             preceded(digit1, tag(")")),
+            preceded(
+                verify(take(1usize), |i: &Input| {
+                    alphabetical && i.as_bytes()[0].is_ascii_alphabetic()
+                }),
+                alt((tag("."), tag(")"))),
+            ),
         )),
         alt((space1, eol_or_eof)),
     )))(input)?;
@@ -112,7 +119,7 @@ fn list_item_node<'a>(
         ));
     }
 
-    let is_ordered = bullet.s.starts_with(|c: char| c.is_ascii_digit());
+    let is_ordered = bullet.s.starts_with(|c: char| c.is_ascii_alphanumeric());
     let (input, counter) = opt(list_item_counter)(input)?;
     let (input, checkbox) = opt(list_item_checkbox)(input)?;
     let (input, tag) = cond(!is_ordered, opt(list_item_tag))(input)?;
@@ -614,4 +621,14 @@ fn parse() {
     assert!(list_node(("-a", config).into()).is_err());
     assert!(list_node(("*\r\n", config).into()).is_err());
     assert!(list_node(("* ", config).into()).is_err());
+    assert!(list_node(("a. item", config).into()).is_err());
+
+    let config = &ParseConfig {
+        list_allow_alphabetical: true,
+        ..Default::default()
+    };
+
+    assert!(list_node(("a. item", config).into()).is_ok());
+    assert!(list_node(("B) item", config).into()).is_ok());
+    assert!(list_node(("ab. item", config).into()).is_err());
 }
@@ -1,6 +1,7 @@
 //! Org-mode elements
 
 pub mod block;
+pub mod citation;
 pub mod clock;
 #[cfg(feature = "syntax-org-fc")]
 pub mod cloze;
@@ -110,6 +111,7 @@ pub enum SyntaxKind {
     DOUBLE_ARROW, // '=>'
     PIPE,         // '|'
     COMMA,        // ','
+    SEMICOLON,    // ';'
     CARET,        // '^'
     NEW_LINE,     // '\n' or '\r\n' or '\r'
     WHITESPACE,   // ' ' or '\t'
@@ -133,6 +135,8 @@ pub enum SyntaxKind {
     PLANNING_DEADLINE,
     PLANNING_SCHEDULED,
     PLANNING_CLOSED,
+    INLINETASK,
+    INLINETASK_END,
 
     //
     // elements
@@ -198,7 +202,12 @@ pub enum SyntaxKind {
     LINE_BREAK,
     COOKIE,
     RADIO_TARGET,
+    RADIO_LINK,
     FN_REF,
+    CITATION,
+    CITATION_PREFIX,
+    CITATION_SUFFIX,
+    CITATION_REFERENCE,
     LATEX_FRAGMENT,
     MACROS,
     SNIPPET,
@@ -249,12 +258,15 @@ impl SyntaxKind {
                 | SyntaxKind::LATEX_FRAGMENT
                 | SyntaxKind::SNIPPET
                 | SyntaxKind::FN_REF
+                | SyntaxKind::CITATION
+                | SyntaxKind::CITATION_REFERENCE
                 | SyntaxKind::INLINE_CALL
                 | SyntaxKind::INLINE_SRC
                 | SyntaxKind::LINE_BREAK
                 | SyntaxKind::LINK
                 | SyntaxKind::MACROS
                 | SyntaxKind::RADIO_TARGET
+                | SyntaxKind::RADIO_LINK
                 | SyntaxKind::COOKIE
                 | SyntaxKind::SUPERSCRIPT
                 | SyntaxKind::SUBSCRIPT
@@ -313,6 +325,7 @@ impl SyntaxKind {
                 | SyntaxKind::DRAWER
                 | SyntaxKind::DYN_BLOCK
                 | SyntaxKind::FN_DEF
+                | SyntaxKind::INLINETASK
                 | SyntaxKind::LIST_ITEM
                 | SyntaxKind::LIST
                 | SyntaxKind::PROPERTY_DRAWER
@@ -1,6 +1,7 @@
 use nom::{IResult, InputTake};
 
 use super::{
+    citation::citation_node,
     combinator::GreenElement,
     cookie::cookie_node,
     emphasis::{
@@ -13,9 +14,9 @@ use super::{
     input::Input,
     latex_fragment::latex_fragment_node,
     line_break::line_break_node,
-    link::link_node,
+    link::{self, angle_link_node, link_node, plain_link_node},
     macros::macros_node,
-    radio_target::radio_target_node,
+    radio_target::{self, radio_link_node, radio_target_node},
     snippet::snippet_node,
     subscript_superscript::{self, subscript_node, superscript_node},
     target::target_node,
@@ -26,6 +27,33 @@ struct ObjectPositions<'a> {
     input: Input<'a>,
     pos: usize,
     finder: jetscii::BytesConst,
+    /// next possible plain link, which can't be located by `finder`
+    plain_link: Lookahead,
+    /// next possible radio link, which can't be located by `finder`
+    radio_link: Lookahead,
+}
+
+#[derive(Clone, Copy)]
+enum Lookahead {
+    /// disabled, or no more candidates in input
+    Done,
+    /// needs to search again from current position
+    Pending,
+    /// found candidate at given position
+    At(usize),
+}
+
+impl Lookahead {
+    fn next(&mut self, pos: usize, search: impl FnOnce() -> Option<usize>) -> Option<usize> {
+        match *self {
+            Lookahead::Done => return None,
+            Lookahead::At(p) if p >= pos => return Some(p),
+            _ => {}
+        }
+        let next = search();
+        *self = next.map_or(Lookahead::Done, Lookahead::At);
+        next
+    }
 }
 
 impl ObjectPositions<'_> {
@@ -37,7 +65,7 @@ impl ObjectPositions<'_> {
                 b'*', b'+', b'/', b'_', b'=', b'~', /* text markup */
                 b'@', /* snippet */
                 b'<', /* timestamp, target, radio target */
-                b'[', /* link, cookie, fn_ref, timestamp */
+                b'[', /* link, cookie, fn_ref, citation, timestamp */
                 b'c', /* inline call */
                 b's', /* inline source */
                 b'\\', b'$', /* latex & entity */
@@ -45,6 +73,12 @@ impl ObjectPositions<'_> {
                 b'^', /* superscript */
                 b'_'  /* subscript */
             ),
+            plain_link: Lookahead::Pending,
+            radio_link: if input.c.radio_targets.is_empty() {
+                Lookahead::Done
+            } else {
+                Lookahead::Pending
+            },
         }
     }
 
@@ -58,6 +92,8 @@ impl ObjectPositions<'_> {
                 b'^', /* superscript */
                 b'_'  /* subscript */
             ),
+            plain_link: Lookahead::Done,
+            radio_link: Lookahead::Done,
         }
     }
 
@@ -76,10 +112,55 @@ impl ObjectPositions<'_> {
                 b'^', /* superscript */
                 b'_'  /* subscript */
             ),
+            plain_link: Lookahead::Done,
+            radio_link: Lookahead::Done,
         }
     }
 }
 
+impl ObjectPositions<'_> {
+    /// Finds the start of next word followed by a colon, which might be the beginning of plain link
+    fn next_plain_link(&mut self) -> Option<usize> {
+        let (bytes, pos) = (self.input.as_bytes(), self.pos);
+
+        self.plain_link.next(pos, || {
+            let mut from = pos;
+            loop {
+                let colon = from + memchr::memchr(b':', &bytes[from..])?;
+
+                let start = bytes[..colon]
+                    .iter()
+                    .rposition(|b| !b.is_ascii_alphanumeric() && !matches!(b, b'-' | b'+' | b'_'))
+                    .map_or(0, |i| i + 1);
+
+                if start < colon && start >= pos {
+                    return Some(start);
+                }
+
+                from = colon + 1;
+            }
+        })
+    }
+
+    /// Finds the start of next text matching one of radio targets
+    fn next_radio_link(&mut self) -> Option<usize> {
+        let (input, pos) = (self.input, self.pos);
+
+        self.radio_link.next(pos, || {
+            let mut pre = input.s[..pos].chars().next_back();
+            for (i, c) in input.s[pos..].char_indices() {
+                if !pre.is_some_and(char::is_alphanumeric)
+                    && radio_target::radio_link_len(&input.s[pos + i..], input.c).is_some()
+                {
+                    return Some(pos + i);
+                }
+                pre = Some(c);
+            }
+            None
+        })
+    }
+}
+
 impl<'a> Iterator for ObjectPositions<'a> {
     type Item = (Input<'a>, Input<'a>);
 
@@ -89,8 +170,15 @@ impl<'a> Iterator for ObjectPositions<'a> {
         }
 
         let previous = self.pos;
-        let i = self.finder.find(&self.input.as_bytes()[self.pos..])?;
-        let p = self.pos + i;
+        let i = self.finder.find(&self.input.as_bytes()[self.pos..]);
+        let p = [
+            i.map(|i| self.pos + i),
+            self.next_plain_link(),
+            self.next_radio_link(),
+        ]
+        .into_iter()
+        .flatten()
+        .min()?;
 
         self.pos = p + 1;
 
@@ -142,9 +230,10 @@ pub fn minimal_object_nodes(input: Input) -> Vec<GreenElement> {
 /// - LaTeX Fragments
 /// - Export Snippets
 /// - Footnote References
+/// - Citations
 /// - Inline Babel Calls
 /// - Inline Source Blocks
-/// - Links
+/// - Links (regular, angle, plain and radio links)
 /// - Macros
 /// - Targets and Radio Targets
 /// - Statistics Cookies
@@ -153,13 +242,15 @@ pub fn minimal_object_nodes(input: Input) -> Vec<GreenElement> {
 /// - Line Breaks
 /// - Subscript and Superscript
 /// - Cloze (if `syntax-org-fc` is enabled)
-///
-/// // todo:
-/// - Citations
 pub fn standard_object_nodes(input: Input) -> Vec<GreenElement> {
     object_nodes(
         ObjectPositions::standard,
         |i: Input, pre: Input| match &i.as_bytes()[0] {
+            _ if radio_target::verify_radio_link_pre(&pre)
+                && radio_target::radio_link_len(i.s, i.c).is_some() =>
+            {
+                radio_link_node(i)
+            }
             b'*' if emphasis::verify_pre(pre.s) => bold_node(i),
             b'+' if emphasis::verify_pre(pre.s) => strike_node(i),
             b'/' if emphasis::verify_pre(pre.s) => italic_node(i),
@@ -179,20 +270,29 @@ pub fn standard_object_nodes(input: Input) -> Vec<GreenElement> {
             b'<' => radio_target_node(i)
                 .or_else(|_| target_node(i))
                 .or_else(|_| timestamp_diary_node(i))
-                .or_else(|_| timestamp_active_node(i)),
+                .or_else(|_| timestamp_active_node(i))
+                .or_else(|_| angle_link_node(i)),
             b'[' => cookie_node(i)
                 .or_else(|_| link_node(i))
                 .or_else(|_| fn_ref_node(i))
+                .or_else(|_| citation_node(i))
                 .or_else(|_| timestamp_inactive_node(i)),
             // NOTE: although not specified in document, inline call and inline src follows the
             // same pre tokens rule as text markup
-            b'c' if emphasis::verify_pre(pre.s) => inline_call_node(i),
-            b's' if emphasis::verify_pre(pre.s) => inline_src_node(i),
+            b'c' if emphasis::verify_pre(pre.s) => {
+                inline_call_node(i).or_else(|_| plain_link_node(i))
+            }
+            b's' if emphasis::verify_pre(pre.s) => {
+                inline_src_node(i).or_else(|_| plain_link_node(i))
+            }
             b'$' => latex_fragment_node(i),
             b'\\' if !pre.s.ends_with('\\') && i.as_bytes()[1] == b'\\' => line_break_node(i),
             b'\\' => entity_node(i).or_else(|_| latex_fragment_node(i)),
             b'^' if subscript_superscript::verify_pre(&pre) => superscript_node(i),
             b'_' if subscript_superscript::verify_pre(&pre) => subscript_node(i),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' if link::verify_plain_link_pre(&pre) => {
+                plain_link_node(i)
+            }
             _ => Err(nom::Err::Error(())),
         },
         input,
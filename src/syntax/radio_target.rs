@@ -2,7 +2,7 @@ use nom::{
     bytes::complete::take_while,
     combinator::{map, verify},
     sequence::tuple,
-    IResult,
+    IResult, InputTake,
 };
 
 use super::{
@@ -11,6 +11,7 @@ use super::{
     object::minimal_object_nodes,
     SyntaxKind::*,
 };
+use crate::ParseConfig;
 
 pub fn radio_target_node(input: Input) -> IResult<Input, GreenElement, ()> {
     let mut parser = map(
@@ -34,6 +35,66 @@ pub fn radio_target_node(input: Input) -> IResult<Input, GreenElement, ()> {
     crate::lossless_parser!(parser, input)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn radio_link_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    crate::lossless_parser!(radio_link_node_base, input)
+}
+
+fn radio_link_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    let len = radio_link_len(input.s, input.c).ok_or(nom::Err::Error(()))?;
+    let (input, contents) = input.take_split(len);
+    Ok((input, node(RADIO_LINK, minimal_object_nodes(contents))))
+}
+
+/// Returns `true` if a radio link can start right after `pre`
+pub fn verify_radio_link_pre(pre: &Input) -> bool {
+    !pre.c.radio_targets.is_empty() && !pre.s.ends_with(|c: char| c.is_alphanumeric())
+}
+
+/// Returns the length of the longest radio target matching the beginning of `s`
+pub fn radio_link_len(s: &str, config: &ParseConfig) -> Option<usize> {
+    config
+        .radio_targets
+        .iter()
+        .filter_map(|target| match_radio_target(s, target))
+        .max()
+}
+
+/// Matches `target` at the beginning of `s`
+///
+/// Matching is case-insensitive and any whitespaces in target matches any
+/// non-empty whitespaces in `s`, and the match must end at word boundary.
+fn match_radio_target(s: &str, target: &str) -> Option<usize> {
+    let mut s_chars = s.char_indices().peekable();
+    let mut t_chars = target.trim().chars().peekable();
+
+    t_chars.peek()?;
+
+    while let Some(t) = t_chars.next() {
+        if t.is_whitespace() {
+            while t_chars.next_if(|c| c.is_whitespace()).is_some() {}
+            s_chars.next_if(|(_, c)| c.is_whitespace())?;
+            while s_chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        } else {
+            let (_, c) = s_chars.next()?;
+            if !c.to_lowercase().eq(t.to_lowercase()) {
+                return None;
+            }
+        }
+    }
+
+    let end = s_chars.peek().map_or(s.len(), |(i, _)| *i);
+
+    if s[end..].starts_with(|c: char| c.is_alphanumeric()) {
+        None
+    } else {
+        Some(end)
+    }
+}
+
 #[test]
 fn parse() {
     use crate::{ast::RadioTarget, tests::to_ast, ParseConfig};
@@ -81,3 +142,42 @@ fn parse() {
     assert!(radio_target_node(("<<<ta\nget>>>", config).into()).is_err());
     assert!(radio_target_node(("<<<target>>", config).into()).is_err());
 }
+
+#[test]
+fn radio_link() {
+    use crate::{ast::RadioLink, syntax::SyntaxNode};
+    use rowan::ast::AstNode;
+
+    let config = &ParseConfig {
+        radio_targets: vec!["radio target".into(), "radio".into(), "\\alpha".into()],
+        ..Default::default()
+    };
+
+    let to_radio_link = |input: &str| {
+        let node = radio_link_node((input, config).into()).unwrap().1;
+        RadioLink::cast(SyntaxNode::new_root(node.into_node().unwrap())).unwrap()
+    };
+
+    insta::assert_debug_snapshot!(
+        to_radio_link("Radio\n  Target.").syntax,
+        @r###"
+    RADIO_LINK@0..14
+      TEXT@0..14 "Radio\n  Target"
+    "###
+    );
+
+    insta::assert_debug_snapshot!(
+        to_radio_link("\\alpha").syntax,
+        @r###"
+    RADIO_LINK@0..6
+      ENTITY@0..6
+        BACKSLASH@0..1 "\\"
+        TEXT@1..6 "alpha"
+    "###
+    );
+
+    assert_eq!(to_radio_link("radio targets").syntax.to_string(), "radio");
+    assert!(radio_link_node(("radiotarget", config).into()).is_err());
+    assert!(radio_link_node(("radios", config).into()).is_err());
+    assert!(radio_link_node(("target", config).into()).is_err());
+}
@@ -9,13 +9,15 @@ use nom::{
 use super::{
     combinator::{blank_lines, line_ends_iter, node, pipe_token, GreenElement, NodeBuilder},
     input::Input,
-    keyword::tblfm_keyword_nodes,
+    keyword::{affiliated_keyword_nodes, tblfm_keyword_nodes},
     object::standard_object_nodes,
     SyntaxKind::*,
 };
 
 fn org_table_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
-    let mut children = vec![];
+    let (input, affiliated_keywords) = affiliated_keyword_nodes(input)?;
+    let mut children = affiliated_keywords;
+    let rows_start = children.len();
 
     let mut start = 0;
     for i in line_ends_iter(input.as_str()) {
@@ -36,7 +38,7 @@ fn org_table_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
         start = i;
     }
 
-    if start == 0 {
+    if children.len() == rows_start {
         return Err(nom::Err::Error(()));
     }
 
@@ -234,6 +236,26 @@ r#"|
         TEXT@29..35 " test2"
     "###
     );
+
+    insta::assert_debug_snapshot!(
+        to_org_table("#+NAME: tbl\n| a |").syntax,
+        @r###"
+    ORG_TABLE@0..17
+      AFFILIATED_KEYWORD@0..12
+        HASH_PLUS@0..2 "#+"
+        TEXT@2..6 "NAME"
+        COLON@6..7 ":"
+        TEXT@7..11 " tbl"
+        NEW_LINE@11..12 "\n"
+      ORG_TABLE_STANDARD_ROW@12..17
+        PIPE@12..13 "|"
+        WHITESPACE@13..14 " "
+        ORG_TABLE_CELL@14..15
+          TEXT@14..15 "a"
+        WHITESPACE@15..16 " "
+        PIPE@16..17 "|"
+    "###
+    );
 }
 
 #[test]
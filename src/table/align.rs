@@ -0,0 +1,204 @@
+use rowan::{ast::AstNode, NodeOrToken};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    ast::{ColumnAlignment, ColumnCookie, OrgTable, OrgTableRow},
+    Org,
+};
+
+/// Indicator appended to cells truncated by a width cookie, same as
+/// `org-table-shrunk-column-indicator`
+const TRUNCATED: char = '…';
+
+impl OrgTable {
+    /// Returns the table text aligned like `org-table-align`
+    ///
+    /// - every column is as wide as its widest cell, measured in display width
+    /// - columns are aligned as [`OrgTable::column_alignments`] says, so
+    ///   numeric columns are right-aligned
+    /// - rules are regenerated as `|---+---|`
+    /// - cells wider than the width cookie of their column, e.g. `<10>`, are
+    ///   truncated and end with `…`
+    /// - all rows use the indentation of the first row
+    ///
+    /// `#+TBLFM` lines and affiliated keywords are kept as-is.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let org = Org::parse(r#"|name|qty|
+    /// |-
+    /// |苹果|3|
+    /// | pear | 12 | extra |
+    /// "#);
+    /// let table = org.first_node::<OrgTable>().unwrap();
+    /// assert_eq!(table.aligned(), r#"| name | qty |       |
+    /// |------+-----+-------|
+    /// | 苹果 |   3 |       |
+    /// | pear |  12 | extra |
+    /// "#);
+    ///
+    /// let org = Org::parse("| <6>       |\n| a long cell |\n");
+    /// let table = org.first_node::<OrgTable>().unwrap();
+    /// assert_eq!(table.aligned(), "| <6>    |\n| a lon… |\n");
+    /// ```
+    pub fn aligned(&self) -> String {
+        let rows: Vec<_> = self
+            .syntax
+            .children()
+            .filter_map(OrgTableRow::cast)
+            .map(|row| (row.is_rule(), row.cells()))
+            .collect();
+
+        let cookies = self.column_cookies();
+        let alignments = self.column_alignments();
+        let columns = cookies.len().max(1);
+
+        let mut widths = vec![1; columns];
+        for (_, cells) in &rows {
+            for (idx, cell) in cells.iter().enumerate() {
+                widths[idx] = widths[idx].max(cell.width());
+            }
+        }
+        for (width, cookie) in widths.iter_mut().zip(&cookies) {
+            if let Some(limit) = cookie.width {
+                *width = (*width).min(limit.max(1));
+            }
+        }
+
+        let indent = self
+            .syntax
+            .children()
+            .find_map(OrgTableRow::cast)
+            .map(|row| {
+                let text = row.syntax.to_string();
+                text[..text.len() - text.trim_start_matches([' ', '\t']).len()].to_string()
+            })
+            .unwrap_or_default();
+
+        let mut output = String::new();
+        let mut rows = rows.into_iter();
+
+        for element in self.syntax.children_with_tokens() {
+            let node = match element {
+                NodeOrToken::Node(node) if OrgTableRow::can_cast(node.kind()) => node,
+                element => {
+                    output += &element.to_string();
+                    continue;
+                }
+            };
+            let Some((is_rule, cells)) = rows.next() else {
+                continue;
+            };
+
+            output += &indent;
+            output += "|";
+            if is_rule {
+                for (idx, width) in widths.iter().enumerate() {
+                    if idx != 0 {
+                        output += "+";
+                    }
+                    output += &"-".repeat(width + 2);
+                }
+                output += "|";
+            } else {
+                for (idx, width) in widths.iter().enumerate() {
+                    let cell = cells.get(idx).map(String::as_str).unwrap_or_default();
+                    let alignment = alignments
+                        .get(idx)
+                        .copied()
+                        .unwrap_or(ColumnAlignment::Left);
+                    output += " ";
+                    output += &pad(cell, *width, alignment);
+                    output += " |";
+                }
+            }
+
+            let text = node.to_string();
+            output += &text[text.trim_end_matches(['\r', '\n']).len()..];
+        }
+
+        output
+    }
+}
+
+impl Org {
+    /// Aligns every table in the document, see [`OrgTable::aligned`]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("* Stock\n|a|bb|\n|-|\n|1|22|\n#+TBLFM: $2=$1*22\n\ntext\n");
+    /// org.align_tables();
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* Stock\n| a | bb |\n|---+----|\n| 1 | 22 |\n#+TBLFM: $2=$1*22\n\ntext\n"
+    /// );
+    /// ```
+    pub fn align_tables(&mut self) {
+        let mut index = 0;
+
+        while let Some(table) = self
+            .document()
+            .syntax
+            .descendants()
+            .filter_map(OrgTable::cast)
+            .nth(index)
+        {
+            index += 1;
+            let aligned = table.aligned();
+            if aligned != table.raw() {
+                self.replace_range(table.text_range(), aligned);
+            }
+        }
+    }
+}
+
+/// Pads or truncates cell to given display width
+fn pad(cell: &str, width: usize, alignment: ColumnAlignment) -> String {
+    if ColumnCookie::parse(cell).is_none() && cell.width() > width {
+        let mut truncated = String::new();
+        let mut used = 0;
+        for c in cell.chars() {
+            let w = c.width().unwrap_or_default();
+            if used + w >= width {
+                break;
+            }
+            truncated.push(c);
+            used += w;
+        }
+        truncated.push(TRUNCATED);
+        return truncated + &" ".repeat(width.saturating_sub(used + 1));
+    }
+
+    let fill = width.saturating_sub(cell.width());
+    match alignment {
+        ColumnAlignment::Left => format!("{cell}{}", " ".repeat(fill)),
+        ColumnAlignment::Right => format!("{}{cell}", " ".repeat(fill)),
+        ColumnAlignment::Center => {
+            let left = fill / 2;
+            format!("{}{cell}{}", " ".repeat(left), " ".repeat(fill - left))
+        }
+    }
+}
+
+#[test]
+fn aligned() {
+    let align = |s: &str| -> String { Org::parse(s).first_node::<OrgTable>().unwrap().aligned() };
+
+    assert_eq!(align("|"), "|   |");
+    assert_eq!(
+        align("  |a|\n  |-+-|\n    | b |"),
+        "  | a |\n  |---|\n  | b |"
+    );
+    assert_eq!(
+        align("| <c> | <r> |\n| a | bcd |\n| 12345 | e |\n"),
+        "|  <c>  | <r> |\n|   a   | bcd |\n| 12345 |   e |\n"
+    );
+    assert_eq!(
+        align("#+NAME: t\n| 1 |   2.5 |\n| 10 | -3e2 |\n\n"),
+        "#+NAME: t\n|  1 |  2.5 |\n| 10 | -3e2 |\n\n"
+    );
+    // wide characters aren't split
+    assert_eq!(align("| <4> |\n| 日本語 |\n"), "| <4>  |\n| 日…  |\n");
+}
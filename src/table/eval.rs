@@ -0,0 +1,1048 @@
+use rowan::{ast::AstNode, TextRange};
+use std::fmt;
+
+use super::{
+    BinaryOp, ColumnRef, FieldRef, FormulaExpr, FormulaFormat, FormulaTarget, FormulaValue,
+    HlineRef, RowRef, TableFormula,
+};
+use crate::{
+    ast::{OrgTable, OrgTableRow, RowMark},
+    Org, SyntaxNode,
+};
+
+/// Maximum number of recalculations before giving up, same as `org-table-iterate`
+const MAX_ITERATIONS: usize = 10;
+
+/// Cell rewrite produced by [`OrgTable::evaluate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellEdit {
+    /// Range to replace, either the cell content or the space between two
+    /// vertical bars if the cell is empty
+    pub range: TextRange,
+    /// Replacement text
+    pub value: String,
+}
+
+/// Error returned by [`OrgTable::evaluate`] and [`Org::recalculate_tables`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaError {
+    /// Formula in `#+TBLFM` can't be parsed
+    Malformed(String),
+    /// Reference points outside of the table, or to a hline
+    InvalidReference,
+    /// `$name` is neither a column name, a field name nor a parameter
+    UnknownName(String),
+    UnknownFunction(String),
+    /// No table is named by `#+NAME` in `remote(name, ...)`
+    UnknownTable(String),
+    /// Function is called with wrong number or kind of arguments
+    InvalidArgument(String),
+    /// Arithmetic on a non-numeric value
+    NotANumber(String),
+    DivisionByZero,
+    /// Values keep changing after recalculating ten times
+    NotConverged,
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormulaError::Malformed(formula) => write!(f, "malformed formula {formula:?}"),
+            FormulaError::InvalidReference => write!(f, "invalid field reference"),
+            FormulaError::UnknownName(name) => write!(f, "unknown name ${name}"),
+            FormulaError::UnknownFunction(name) => write!(f, "unknown function {name}"),
+            FormulaError::UnknownTable(name) => write!(f, "no table named {name}"),
+            FormulaError::InvalidArgument(name) => write!(f, "invalid arguments to {name}"),
+            FormulaError::NotANumber(value) => write!(f, "{value:?} is not a number"),
+            FormulaError::DivisionByZero => write!(f, "division by zero"),
+            FormulaError::NotConverged => {
+                write!(f, "no convergence after {MAX_ITERATIONS} iterations")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+impl OrgTable {
+    /// Evaluates `#+TBLFM` formulas, returns edits for the cells whose value changed
+    ///
+    /// Column formulas are applied to every row below the first hline, or
+    /// every row if the table has no header, except rows marked with `!`, `^`,
+    /// `_`, `$` or `/`. Field formulas take precedence over column formulas.
+    /// The table is recalculated until no value changes, like `org-table-iterate`.
+    ///
+    /// Lisp formulas are left untouched, other formulas which can't be parsed
+    /// are reported as [`FormulaError::Malformed`].
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let org = Org::parse(r#"| item  | price | qty | total |
+    /// |-------+-------+-----+-------|
+    /// | apple |   1.5 |   4 |       |
+    /// | pear  |     2 |   3 |       |
+    /// |-------+-------+-----+-------|
+    /// |       |       |     |       |
+    /// #+TBLFM: $4=$2*$3;%.2f::@>$1=Total::@>$4=vsum(@I..@II)"#);
+    /// let table = org.first_node::<OrgTable>().unwrap();
+    /// let edits = table.evaluate().unwrap();
+    /// let values: Vec<_> = edits.iter().map(|e| e.value.as_str()).collect();
+    /// assert_eq!(values, [" 6.00 ", " 6.00 ", " Total ", " 12 "]);
+    /// ```
+    pub fn evaluate(&self) -> Result<Vec<CellEdit>, FormulaError> {
+        let mut formulas = vec![];
+        for tblfm in self.tblfm() {
+            for formula in tblfm.split("::").map(str::trim).filter(|f| !f.is_empty()) {
+                match TableFormula::parse(formula) {
+                    Some(f) if matches!(f.value, FormulaValue::Calc(_)) => formulas.push(f),
+                    Some(_) => {}
+                    None => return Err(FormulaError::Malformed(formula.to_string())),
+                }
+            }
+        }
+
+        let mut grid = Grid::new(self);
+        let original = grid.clone();
+
+        let mut converged = false;
+        for _ in 0..MAX_ITERATIONS {
+            let previous = grid.clone();
+            grid.recalculate(&formulas, &self.syntax)?;
+            if grid == previous {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(FormulaError::NotConverged);
+        }
+
+        let mut edits = vec![];
+        for (row, original) in grid.rows.iter().zip(&original.rows) {
+            for (cell, original) in row.cells.iter().zip(&original.cells) {
+                if cell.value == original.value {
+                    continue;
+                }
+                edits.push(if cell.empty {
+                    CellEdit {
+                        range: cell.range,
+                        value: format!(" {} ", cell.value),
+                    }
+                } else {
+                    CellEdit {
+                        range: cell.range,
+                        value: cell.value.clone(),
+                    }
+                });
+            }
+        }
+        Ok(edits)
+    }
+}
+
+impl Org {
+    /// Recalculates every table containing `#+TBLFM` formulas
+    ///
+    /// Tables are recalculated repeatedly until no table changes, so that
+    /// `remote` references see up-to-date values.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse(r#"#+NAME: prices
+    /// | apple | 3 |
+    /// | pear  | 4 |
+    /// | sum   |   |
+    /// #+TBLFM: @3$2=vsum(@1..@2)
+    ///
+    /// | total |   |
+    /// #+TBLFM: $2=remote(prices, @3$2) * 2
+    /// "#);
+    /// org.recalculate_tables().unwrap();
+    /// assert_eq!(org.to_org(), r#"#+NAME: prices
+    /// | apple | 3 |
+    /// | pear  | 4 |
+    /// | sum   | 7 |
+    /// #+TBLFM: @3$2=vsum(@1..@2)
+    ///
+    /// | total | 14 |
+    /// #+TBLFM: $2=remote(prices, @3$2) * 2
+    /// "#);
+    /// ```
+    ///
+    /// Recalculation stops at the first table failing to evaluate:
+    ///
+    /// ```rust
+    /// use orgize::{Org, table::FormulaError};
+    ///
+    /// let mut org = Org::parse("| 1 | |\n#+TBLFM: @3$2=1");
+    /// assert_eq!(org.recalculate_tables(), Err(FormulaError::InvalidReference));
+    /// ```
+    pub fn recalculate_tables(&mut self) -> Result<(), FormulaError> {
+        for _ in 0..MAX_ITERATIONS {
+            let mut changed = false;
+            let mut index = 0;
+
+            while let Some(table) = self
+                .document()
+                .syntax
+                .descendants()
+                .filter_map(OrgTable::cast)
+                .nth(index)
+            {
+                index += 1;
+                if table.tblfm().next().is_none() {
+                    continue;
+                }
+
+                let mut edits = table.evaluate()?;
+                edits.sort_by_key(|edit| edit.range.start());
+                for edit in edits.into_iter().rev() {
+                    self.replace_range(edit.range, edit.value);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return Ok(());
+            }
+        }
+
+        Err(FormulaError::NotConverged)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Grid {
+    /// `None` for hlines, or index of `rows`
+    lines: Vec<Option<usize>>,
+    rows: Vec<GridRow>,
+    columns: usize,
+    /// mark of each row, if table has a marking column
+    marks: Vec<Option<RowMark>>,
+    /// first row below the first hline, `0` if table has no header
+    body_start: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GridRow {
+    line: usize,
+    cells: Vec<GridCell>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GridCell {
+    value: String,
+    range: TextRange,
+    /// `true` if this cell has no content in the source
+    empty: bool,
+}
+
+/// Position of a row reference
+#[derive(Debug, Clone, Copy)]
+enum RowPos {
+    Row(usize),
+    Hline(usize),
+}
+
+impl Grid {
+    fn new(table: &OrgTable) -> Self {
+        let mut grid = Grid {
+            lines: vec![],
+            rows: vec![],
+            columns: 0,
+            marks: vec![],
+            body_start: 0,
+        };
+        let has_marking_column = table.has_marking_column();
+
+        for row in table.syntax.children().filter_map(OrgTableRow::cast) {
+            if row.is_rule() {
+                if grid.body_start == 0 && !grid.rows.is_empty() {
+                    grid.body_start = grid.rows.len();
+                }
+                grid.lines.push(None);
+            } else {
+                let cells: Vec<_> = row
+                    .slots()
+                    .into_iter()
+                    .map(|(slot, cell)| match cell {
+                        Some(cell) => GridCell {
+                            value: cell.syntax.to_string(),
+                            range: cell.text_range(),
+                            empty: false,
+                        },
+                        None => GridCell {
+                            value: String::new(),
+                            range: slot,
+                            empty: true,
+                        },
+                    })
+                    .collect();
+                grid.columns = grid.columns.max(cells.len());
+                grid.marks.push(if has_marking_column {
+                    cells.first().and_then(|c| RowMark::parse(&c.value))
+                } else {
+                    None
+                });
+                grid.lines.push(Some(grid.rows.len()));
+                grid.rows.push(GridRow {
+                    line: grid.lines.len() - 1,
+                    cells,
+                });
+            }
+        }
+
+        // header is only used if there're rows below the first hline
+        if grid.body_start == grid.rows.len() {
+            grid.body_start = 0;
+        }
+
+        grid
+    }
+
+    fn value(&self, row: usize, column: usize) -> &str {
+        self.rows[row]
+            .cells
+            .get(column)
+            .map(|c| c.value.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Names defined in `!`, `^` and `_` rows
+    fn lookup(&self, name: &str) -> Option<Name> {
+        for (idx, row) in self.rows.iter().enumerate() {
+            let target = match self.marks[idx] {
+                Some(RowMark::ColumnNames) => None,
+                Some(RowMark::NamesAbove) if idx > 0 => Some(idx - 1),
+                Some(RowMark::NamesBelow) if idx + 1 < self.rows.len() => Some(idx + 1),
+                Some(RowMark::Parameters) => {
+                    let parameter = row.cells[1..].iter().find_map(|cell| {
+                        let (key, value) = cell.value.split_once('=')?;
+                        (key.trim() == name).then(|| value.trim().to_string())
+                    });
+                    if let Some(value) = parameter {
+                        return Some(Name::Parameter(value));
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            if let Some(column) = row.cells.iter().skip(1).position(|c| c.value == name) {
+                return Some(match target {
+                    Some(target) => Name::Field(target, column + 1),
+                    None => Name::Column(column + 1),
+                });
+            }
+        }
+        None
+    }
+
+    fn recalculate(
+        &mut self,
+        formulas: &[TableFormula],
+        root: &SyntaxNode,
+    ) -> Result<(), FormulaError> {
+        let mut fields = vec![];
+        let mut columns = vec![];
+
+        for formula in formulas {
+            match &formula.target {
+                FormulaTarget::Column(column) => {
+                    columns.push((self.column(column, 0)?, formula));
+                }
+                FormulaTarget::Named(name) => match self.lookup(name) {
+                    Some(Name::Field(row, column)) => fields.push((row, column, formula)),
+                    Some(Name::Column(column)) => columns.push((column, formula)),
+                    _ => return Err(FormulaError::UnknownName(name.clone())),
+                },
+                FormulaTarget::Field(field) => {
+                    let (row, column) = self.field(field, 0, 0)?;
+                    fields.push((row, column, formula));
+                }
+                FormulaTarget::Range(start, end) => {
+                    for (row, column) in self.range(start, end, 0, 0)? {
+                        fields.push((row, column, formula));
+                    }
+                }
+            }
+        }
+
+        for row in self.body_start..self.rows.len() {
+            if self.marks[row].is_some_and(RowMark::is_special) {
+                continue;
+            }
+            for &(column, formula) in &columns {
+                if fields.iter().any(|&(r, c, _)| r == row && c == column) {
+                    continue;
+                }
+                self.apply(row, column, formula, root)?;
+            }
+        }
+
+        for &(row, column, formula) in &fields {
+            self.apply(row, column, formula, root)?;
+        }
+
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        row: usize,
+        column: usize,
+        formula: &TableFormula,
+        root: &SyntaxNode,
+    ) -> Result<(), FormulaError> {
+        let FormulaValue::Calc(expr) = &formula.value else {
+            return Ok(());
+        };
+        let format = formula.format.clone().unwrap_or_default();
+        let ctx = Context {
+            grid: self,
+            row,
+            column,
+            format: &format,
+            root,
+        };
+        let value = ctx.eval(expr)?.format(&format);
+        if let Some(cell) = self.rows[row].cells.get_mut(column) {
+            cell.value = value;
+        }
+        Ok(())
+    }
+
+    fn row(&self, row: &RowRef, current: usize) -> Result<RowPos, FormulaError> {
+        let index = match *row {
+            RowRef::Absolute(n) | RowRef::First(n) => n as i64 - 1,
+            RowRef::Relative(n) => current as i64 + n as i64,
+            RowRef::Last(n) => self.rows.len() as i64 - n as i64,
+            RowRef::Hline { hline, offset } => {
+                let hlines: Vec<_> = (0..self.lines.len())
+                    .filter(|&l| self.lines[l].is_none())
+                    .collect();
+                let line = self.rows.get(current).map_or(0, |r| r.line);
+                let hline = match hline {
+                    HlineRef::Absolute(n) => hlines.get(n as usize - 1),
+                    HlineRef::Above(n) => hlines
+                        .iter()
+                        .rev()
+                        .filter(|&&l| l < line)
+                        .nth(n as usize - 1),
+                    HlineRef::Below(n) => hlines.iter().filter(|&&l| l > line).nth(n as usize - 1),
+                };
+                let hline = *hline.ok_or(FormulaError::InvalidReference)?;
+                if offset == 0 {
+                    return Ok(RowPos::Hline(hline));
+                }
+                // rows after the hline
+                let after = self
+                    .rows
+                    .iter()
+                    .position(|r| r.line > hline)
+                    .unwrap_or(self.rows.len());
+                if offset > 0 {
+                    after as i64 + offset as i64 - 1
+                } else {
+                    after as i64 + offset as i64
+                }
+            }
+        };
+        if index < 0 || index as usize >= self.rows.len() {
+            return Err(FormulaError::InvalidReference);
+        }
+        Ok(RowPos::Row(index as usize))
+    }
+
+    fn column(&self, column: &ColumnRef, current: usize) -> Result<usize, FormulaError> {
+        let index = match *column {
+            ColumnRef::Absolute(n) | ColumnRef::First(n) => n as i64 - 1,
+            ColumnRef::Relative(n) => current as i64 + n as i64,
+            ColumnRef::Last(n) => self.columns as i64 - n as i64,
+        };
+        if index < 0 || index as usize >= self.columns {
+            return Err(FormulaError::InvalidReference);
+        }
+        Ok(index as usize)
+    }
+
+    fn field(
+        &self,
+        field: &FieldRef,
+        row: usize,
+        column: usize,
+    ) -> Result<(usize, usize), FormulaError> {
+        let row = match &field.row {
+            Some(r) => match self.row(r, row)? {
+                RowPos::Row(row) => row,
+                RowPos::Hline(_) => return Err(FormulaError::InvalidReference),
+            },
+            None => row,
+        };
+        let column = match &field.column {
+            Some(c) => self.column(c, column)?,
+            None => column,
+        };
+        Ok((row, column))
+    }
+
+    /// Returns fields in range, row by row
+    fn range(
+        &self,
+        start: &FieldRef,
+        end: &FieldRef,
+        row: usize,
+        column: usize,
+    ) -> Result<Vec<(usize, usize)>, FormulaError> {
+        let line = |r: &Option<RowRef>| -> Result<usize, FormulaError> {
+            Ok(match r {
+                Some(r) => match self.row(r, row)? {
+                    RowPos::Row(row) => self.rows[row].line,
+                    RowPos::Hline(line) => line,
+                },
+                None => {
+                    self.rows
+                        .get(row)
+                        .ok_or(FormulaError::InvalidReference)?
+                        .line
+                }
+            })
+        };
+        let column_of = |c: &Option<ColumnRef>| match c {
+            Some(c) => self.column(c, column),
+            None => Ok(column),
+        };
+
+        let (l1, l2) = (line(&start.row)?, line(&end.row)?);
+        let (c1, c2) = (column_of(&start.column)?, column_of(&end.column)?);
+
+        let mut fields = vec![];
+        for line in l1.min(l2)..=l1.max(l2) {
+            if let Some(row) = self.lines[line] {
+                for column in c1.min(c2)..=c1.max(c2) {
+                    fields.push((row, column));
+                }
+            }
+        }
+        Ok(fields)
+    }
+}
+
+enum Name {
+    Column(usize),
+    Field(usize, usize),
+    Parameter(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(f64),
+    Text(String),
+    Vector(Vec<Value>),
+}
+
+struct Context<'a> {
+    grid: &'a Grid,
+    row: usize,
+    column: usize,
+    format: &'a FormulaFormat,
+    root: &'a SyntaxNode,
+}
+
+impl Context<'_> {
+    fn field_value(&self, s: &str) -> Value {
+        if s.is_empty() {
+            return if self.format.has_flag("E") {
+                Value::Number(f64::NAN)
+            } else {
+                Value::Number(0.)
+            };
+        }
+        match parse_number(s) {
+            Some(n) => Value::Number(n),
+            None if self.format.has_flag("N") => Value::Number(0.),
+            None => Value::Text(s.to_string()),
+        }
+    }
+
+    fn eval(&self, expr: &FormulaExpr) -> Result<Value, FormulaError> {
+        match expr {
+            FormulaExpr::Number(n) => Ok(Value::Number(*n)),
+            FormulaExpr::String(s) => Ok(Value::Text(s.clone())),
+            FormulaExpr::Symbol(s) => Ok(match s.as_str() {
+                "pi" => Value::Number(std::f64::consts::PI),
+                "e" => Value::Number(std::f64::consts::E),
+                _ => Value::Text(s.clone()),
+            }),
+            FormulaExpr::Name(name) => match self.grid.lookup(name) {
+                Some(Name::Column(column)) => {
+                    Ok(self.field_value(self.grid.value(self.row, column)))
+                }
+                Some(Name::Field(row, column)) => {
+                    Ok(self.field_value(self.grid.value(row, column)))
+                }
+                Some(Name::Parameter(value)) => Ok(self.field_value(&value)),
+                None => Err(FormulaError::UnknownName(name.clone())),
+            },
+            FormulaExpr::Field(field) => {
+                let (row, column) = self.grid.field(field, self.row, self.column)?;
+                Ok(self.field_value(self.grid.value(row, column)))
+            }
+            FormulaExpr::Range(start, end) => {
+                let keep_empty = self.format.has_flag("E");
+                Ok(Value::Vector(
+                    self.grid
+                        .range(start, end, self.row, self.column)?
+                        .into_iter()
+                        .map(|(row, column)| self.grid.value(row, column))
+                        .filter(|value| keep_empty || !value.is_empty())
+                        .map(|value| self.field_value(value))
+                        .collect(),
+                ))
+            }
+            FormulaExpr::RowNumber => Ok(Value::Number(self.row as f64 + 1.)),
+            FormulaExpr::ColumnNumber => Ok(Value::Number(self.column as f64 + 1.)),
+            FormulaExpr::Neg(expr) => map_numbers(self.eval(expr)?, &|n| Ok(-n)),
+            FormulaExpr::Binary(lhs, op, rhs) => binary(self.eval(lhs)?, *op, self.eval(rhs)?),
+            FormulaExpr::Call(name, args) if name == "if" => {
+                let [cond, then, otherwise] = &args[..] else {
+                    return Err(FormulaError::InvalidArgument(name.clone()));
+                };
+                match self.eval(cond)? {
+                    Value::Number(n) if n != 0. => self.eval(then),
+                    Value::Number(_) => self.eval(otherwise),
+                    _ => Err(FormulaError::InvalidArgument(name.clone())),
+                }
+            }
+            FormulaExpr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(name, args)
+            }
+            FormulaExpr::Remote(name, reference) => {
+                let table = self
+                    .root
+                    .ancestors()
+                    .last()
+                    .into_iter()
+                    .flat_map(|root| root.descendants())
+                    .filter_map(OrgTable::cast)
+                    .find(|table| {
+                        table
+                            .name()
+                            .and_then(|keyword| keyword.value())
+                            .is_some_and(|value| value.trim() == name)
+                    })
+                    .ok_or_else(|| FormulaError::UnknownTable(name.clone()))?;
+                let grid = Grid::new(&table);
+                Context {
+                    grid: &grid,
+                    row: 0,
+                    column: 0,
+                    format: self.format,
+                    root: self.root,
+                }
+                .eval(reference)
+            }
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Option<f64> {
+    if s.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.')) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn number(value: &Value) -> Result<f64, FormulaError> {
+    match value {
+        Value::Number(n) => Ok(*n),
+        Value::Text(text) => Err(FormulaError::NotANumber(text.clone())),
+        Value::Vector(_) => Err(FormulaError::NotANumber(value.to_string())),
+    }
+}
+
+fn map_numbers(
+    value: Value,
+    f: &dyn Fn(f64) -> Result<f64, FormulaError>,
+) -> Result<Value, FormulaError> {
+    match value {
+        Value::Vector(values) => Ok(Value::Vector(
+            values
+                .into_iter()
+                .map(|v| map_numbers(v, f))
+                .collect::<Result<_, _>>()?,
+        )),
+        value => Ok(Value::Number(f(number(&value)?)?)),
+    }
+}
+
+fn binary(lhs: Value, op: BinaryOp, rhs: Value) -> Result<Value, FormulaError> {
+    // comparisons between texts
+    if let (Value::Text(l), Value::Text(r)) = (&lhs, &rhs) {
+        let result = match op {
+            BinaryOp::Eq => l == r,
+            BinaryOp::Ne => l != r,
+            _ => return Err(FormulaError::NotANumber(l.clone())),
+        };
+        return Ok(Value::Number(result as u8 as f64));
+    }
+
+    // vector arithmetic is applied element-wise
+    match (lhs, rhs) {
+        (Value::Vector(l), Value::Vector(r)) if l.len() == r.len() => Ok(Value::Vector(
+            l.into_iter()
+                .zip(r)
+                .map(|(l, r)| binary(l, op, r))
+                .collect::<Result<_, _>>()?,
+        )),
+        (Value::Vector(_), Value::Vector(_)) => Err(FormulaError::InvalidArgument("vector".into())),
+        (Value::Vector(l), r) => Ok(Value::Vector(
+            l.into_iter()
+                .map(|l| binary(l, op, r.clone()))
+                .collect::<Result<_, _>>()?,
+        )),
+        (l, Value::Vector(r)) => Ok(Value::Vector(
+            r.into_iter()
+                .map(|r| binary(l.clone(), op, r))
+                .collect::<Result<_, _>>()?,
+        )),
+        (l, r) => {
+            let (l, r) = (number(&l)?, number(&r)?);
+            Ok(Value::Number(match op {
+                BinaryOp::Add => l + r,
+                BinaryOp::Sub => l - r,
+                BinaryOp::Mul => l * r,
+                BinaryOp::Div if r == 0. => return Err(FormulaError::DivisionByZero),
+                BinaryOp::Div => l / r,
+                BinaryOp::Mod if r == 0. => return Err(FormulaError::DivisionByZero),
+                BinaryOp::Mod => l.rem_euclid(r),
+                BinaryOp::Pow => l.powf(r),
+                BinaryOp::Eq => (l == r) as u8 as f64,
+                BinaryOp::Ne => (l != r) as u8 as f64,
+                BinaryOp::Lt => (l < r) as u8 as f64,
+                BinaryOp::Le => (l <= r) as u8 as f64,
+                BinaryOp::Gt => (l > r) as u8 as f64,
+                BinaryOp::Ge => (l >= r) as u8 as f64,
+            }))
+        }
+    }
+}
+
+/// Flattens arguments into a list of numbers
+fn numbers(args: Vec<Value>) -> Result<Vec<f64>, FormulaError> {
+    let mut numbers = vec![];
+    for arg in args {
+        match arg {
+            Value::Vector(values) => numbers.extend(self::numbers(values)?),
+            value => numbers.push(number(&value)?),
+        }
+    }
+    Ok(numbers)
+}
+
+fn call(name: &str, args: Vec<Value>) -> Result<Value, FormulaError> {
+    let invalid = || FormulaError::InvalidArgument(name.to_string());
+
+    let unary = |f: fn(f64) -> f64| -> Result<Value, FormulaError> {
+        match &args[..] {
+            [value] => map_numbers(value.clone(), &|n| Ok(f(n))),
+            _ => Err(invalid()),
+        }
+    };
+
+    let value = match name {
+        "vsum" => numbers(args)?.iter().sum(),
+        "vprod" => numbers(args)?.iter().product(),
+        "vcount" => numbers(args)?.len() as f64,
+        "vmean" => {
+            let numbers = numbers(args)?;
+            if numbers.is_empty() {
+                return Err(invalid());
+            }
+            numbers.iter().sum::<f64>() / numbers.len() as f64
+        }
+        "vmedian" => {
+            let mut numbers = numbers(args)?;
+            if numbers.is_empty() {
+                return Err(invalid());
+            }
+            numbers.sort_by(f64::total_cmp);
+            let mid = numbers.len() / 2;
+            if numbers.len() % 2 == 0 {
+                (numbers[mid - 1] + numbers[mid]) / 2.
+            } else {
+                numbers[mid]
+            }
+        }
+        "vmax" | "max" => numbers(args)?
+            .into_iter()
+            .reduce(f64::max)
+            .ok_or_else(invalid)?,
+        "vmin" | "min" => numbers(args)?
+            .into_iter()
+            .reduce(f64::min)
+            .ok_or_else(invalid)?,
+        "abs" => return unary(f64::abs),
+        "sqrt" => return unary(f64::sqrt),
+        "exp" => return unary(f64::exp),
+        "ln" => return unary(f64::ln),
+        "log10" => return unary(f64::log10),
+        "floor" => return unary(f64::floor),
+        "ceil" => return unary(f64::ceil),
+        "round" => match &args[..] {
+            [value] => return map_numbers(value.clone(), &|n| Ok(n.round())),
+            [value, digits] => {
+                let scale = 10f64.powi(number(digits)? as i32);
+                return map_numbers(value.clone(), &|n| Ok((n * scale).round() / scale));
+            }
+            _ => return Err(invalid()),
+        },
+        _ => return Err(FormulaError::UnknownFunction(name.to_string())),
+    };
+
+    Ok(Value::Number(value))
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", format_number(*n, 8)),
+            Value::Text(text) => write!(f, "{text}"),
+            Value::Vector(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Value {
+    fn format(&self, format: &FormulaFormat) -> String {
+        let Value::Number(n) = self else {
+            return self.to_string();
+        };
+
+        if let Some(printf) = &format.printf {
+            if let Some(s) = sprintf(printf, *n) {
+                return s;
+            }
+        }
+
+        for flag in &format.flags {
+            let digits = flag[1..].parse::<usize>();
+            match (&flag[..1], digits) {
+                ("f", Ok(digits)) => return format!("{n:.digits$}"),
+                ("n", Ok(digits)) => return format_number(*n, digits.max(1)),
+                ("s" | "e", Ok(digits)) => return c_exponent(&format!("{n:.digits$e}")),
+                _ => {}
+            }
+        }
+
+        self.to_string()
+    }
+}
+
+/// Formats number with given significant digits, like Calc's default
+/// float format
+fn format_number(n: f64, digits: usize) -> String {
+    if n.is_nan() {
+        return "nan".into();
+    }
+    if n.is_infinite() {
+        return if n > 0. { "inf".into() } else { "-inf".into() };
+    }
+    if n == 0. {
+        return "0".into();
+    }
+
+    let exponent = n.abs().log10().floor() as i32;
+    if !(-5..15).contains(&exponent) {
+        let s = format!("{:.*e}", digits - 1, n);
+        let (mantissa, exponent) = s.split_once('e').unwrap_or((&s, "0"));
+        return format!("{}e{}", trim_zeros(mantissa), exponent);
+    }
+
+    let decimals = (digits as i32 - 1 - exponent).max(0) as usize;
+    trim_zeros(&format!("{n:.decimals$}")).to_string()
+}
+
+fn trim_zeros(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
+/// Converts rust exponent format `1.5e3` into C format `1.5e+03`
+fn c_exponent(s: &str) -> String {
+    match s.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let (sign, digits) = match exponent.strip_prefix('-') {
+                Some(digits) => ('-', digits),
+                None => ('+', exponent),
+            };
+            format!("{mantissa}e{sign}{digits:0>2}")
+        }
+        None => s.to_string(),
+    }
+}
+
+/// Minimal printf for a single number, returns `None` if the conversion
+/// is unsupported
+fn sprintf(spec: &str, n: f64) -> Option<String> {
+    let spec = spec.strip_prefix('%')?;
+    let conversion = spec.chars().last()?;
+    let spec = &spec[..spec.len() - conversion.len_utf8()];
+
+    let flags_len = spec
+        .find(|c: char| !matches!(c, '-' | '+' | ' ' | '0' | '#'))
+        .unwrap_or(spec.len());
+    let (flags, spec) = spec.split_at(flags_len);
+    let (width, precision) = match spec.split_once('.') {
+        Some((width, precision)) => (width, Some(precision.parse::<usize>().unwrap_or(0))),
+        None => (spec, None),
+    };
+    let width = width.parse::<usize>().unwrap_or(0);
+
+    let mut s = match conversion {
+        'd' | 'i' => format!("{}", n.round() as i64),
+        'f' | 'F' => format!("{:.*}", precision.unwrap_or(6), n),
+        'e' | 'E' => c_exponent(&format!("{:.*e}", precision.unwrap_or(6), n)),
+        'g' | 'G' => format_number(n, precision.unwrap_or(6).max(1)),
+        'x' => format!("{:x}", n.round() as i64),
+        'X' => format!("{:X}", n.round() as i64),
+        'o' => format!("{:o}", n.round() as i64),
+        's' => format_number(n, 8),
+        _ => return None,
+    };
+    if conversion.is_ascii_uppercase() {
+        s = s.to_uppercase();
+    }
+
+    if flags.contains('+') && n >= 0. {
+        s.insert(0, '+');
+    } else if flags.contains(' ') && n >= 0. {
+        s.insert(0, ' ');
+    }
+
+    if s.len() < width {
+        let padding = width - s.len();
+        if flags.contains('-') {
+            s.push_str(&" ".repeat(padding));
+        } else if flags.contains('0') {
+            let sign = s.starts_with(['-', '+', ' ']) as usize;
+            s.insert_str(sign, &"0".repeat(padding));
+        } else {
+            s.insert_str(0, &" ".repeat(padding));
+        }
+    }
+
+    Some(s)
+}
+
+#[test]
+fn evaluate() {
+    let recalculate = |s: &str| -> String {
+        let mut org = Org::parse(s);
+        org.recalculate_tables().unwrap();
+        org.to_org()
+    };
+
+    assert_eq!(
+        recalculate(
+            r#"| ! | a | b | sum |
+|---+---+---+-----|
+| # | 1 | 2 |     |
+| # | 3 |   |     |
+| _ |   |   | s   |
+| # |   |   |     |
+#+TBLFM: $sum=$a+$b::$s=vmean(@2..@3);%.3f::@>$2=@# * $#"#
+        ),
+        r#"| ! | a | b | sum |
+|---+---+---+-----|
+| # | 1 | 2 | 3 |
+| # | 3 |   | 3 |
+| _ |   |   | s   |
+| # | 10 |   | 3.000 |
+#+TBLFM: $sum=$a+$b::$s=vmean(@2..@3);%.3f::@>$2=@# * $#"#
+    );
+
+    assert_eq!(
+        recalculate("| 1 | 2 |\n| 3 | 4 |\n#+TBLFM: $2=@-1$2+1::@1$2=10"),
+        "| 1 | 10 |\n| 3 | 11 |\n#+TBLFM: $2=@-1$2+1::@1$2=10"
+    );
+}
+
+#[test]
+fn format() {
+    let value = |n: f64, format: &str| Value::Number(n).format(&FormulaFormat::parse(format));
+
+    assert_eq!(value(1. / 3., ""), "0.33333333");
+    assert_eq!(value(2.5e20, ""), "2.5e20");
+    assert_eq!(value(12., "%.2f"), "12.00");
+    assert_eq!(value(-1.23456, "%08.3f"), "-001.235");
+    assert_eq!(value(1234.5, "%e"), "1.234500e+03");
+    assert_eq!(value(42., "%-5d"), "42   ");
+    assert_eq!(value(255., "%x"), "ff");
+    assert_eq!(value(1.23456, "f2"), "1.23");
+    assert_eq!(value(1.23456, "n3"), "1.23");
+    // unknown conversions are ignored
+    assert_eq!(value(6., "%é"), "6");
+
+    let mut org = Org::parse("| 3 | |\n#+TBLFM: $2=$1*2;%é\n");
+    org.recalculate_tables().unwrap();
+    assert_eq!(org.to_org(), "| 3 | 6 |\n#+TBLFM: $2=$1*2;%é\n");
+}
+
+#[test]
+fn errors() {
+    let evaluate = |s: &str| Org::parse(s).first_node::<OrgTable>().unwrap().evaluate();
+
+    assert_eq!(
+        evaluate("| a |\n#+TBLFM: $1=foo(1)"),
+        Err(FormulaError::UnknownFunction("foo".into()))
+    );
+    assert_eq!(
+        evaluate("| a | |\n#+TBLFM: $2=$1+1"),
+        Err(FormulaError::NotANumber("a".into()))
+    );
+    assert_eq!(
+        evaluate("| 1 | |\n#+TBLFM: $2=1/0"),
+        Err(FormulaError::DivisionByZero)
+    );
+    assert_eq!(
+        evaluate("| 1 | |\n#+TBLFM: $2=remote(nope, @1$1)"),
+        Err(FormulaError::UnknownTable("nope".into()))
+    );
+    assert_eq!(
+        evaluate("| 1 |\n#+TBLFM: $1=$1+1"),
+        Err(FormulaError::NotConverged)
+    );
+    assert_eq!(
+        evaluate("|---|\n#+TBLFM: $1=1"),
+        Err(FormulaError::InvalidReference)
+    );
+    assert_eq!(
+        evaluate("|---|\n#+TBLFM: $1..$2=1"),
+        Err(FormulaError::InvalidReference)
+    );
+    assert_eq!(
+        evaluate("| 1 |\n#+TBLFM: $1=1::@1$1=remote(x"),
+        Err(FormulaError::Malformed("@1$1=remote(x".into()))
+    );
+    // lisp formulas are ignored
+    assert_eq!(evaluate("| 1 |\n#+TBLFM: $1='(+ 1 2)"), Ok(vec![]));
+}
@@ -0,0 +1,748 @@
+/// A single formula from a `#+TBLFM` line
+///
+/// ```rust
+/// use orgize::table::{ColumnRef, FieldRef, FormulaExpr, FormulaTarget, FormulaValue, RowRef, TableFormula};
+///
+/// let formula = TableFormula::parse("@>$1=vsum(@I..@II);%.2f").unwrap();
+/// assert_eq!(
+///     formula.target,
+///     FormulaTarget::Field(FieldRef {
+///         row: Some(RowRef::Last(1)),
+///         column: Some(ColumnRef::Absolute(1)),
+///     })
+/// );
+/// assert!(matches!(formula.value, FormulaValue::Calc(FormulaExpr::Call(ref name, _)) if name == "vsum"));
+/// assert_eq!(formula.format.unwrap().printf.as_deref(), Some("%.2f"));
+///
+/// let formula = TableFormula::parse("$3='(concat $1 \";\" $2);L").unwrap();
+/// assert_eq!(formula.target, FormulaTarget::Column(ColumnRef::Absolute(3)));
+/// assert_eq!(formula.value, FormulaValue::Lisp("(concat $1 \";\" $2)".into()));
+/// assert_eq!(formula.format.unwrap().flags, ["L"]);
+///
+/// assert!(TableFormula::parse("$3").is_none());
+/// assert!(TableFormula::parse("$3=vsum(").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableFormula {
+    pub target: FormulaTarget,
+    pub value: FormulaValue,
+    /// Format specifier following the last `;`
+    pub format: Option<FormulaFormat>,
+}
+
+/// Left-hand side of a formula
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaTarget {
+    /// `$3=`, applies to every field in the column
+    Column(ColumnRef),
+    /// `@2$3=`, `@>$1=`
+    Field(FieldRef),
+    /// `@2$1..@4$3=`
+    Range(FieldRef, FieldRef),
+    /// `$name=`, field named in a `_` or `^` row
+    Named(String),
+}
+
+/// Right-hand side of a formula
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaValue {
+    /// Calc-style expression
+    Calc(FormulaExpr),
+    /// Emacs Lisp form, starting with `'(`, kept as-is without the quote
+    Lisp(String),
+}
+
+/// Format specifier, e.g. `%.2f`, `N` or `%.1f;NE`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormulaFormat {
+    /// printf-style format, e.g. `%.2f`
+    pub printf: Option<String>,
+    /// Mode flags, e.g. `N`, `E`, `L`, `p20`, `f2`
+    pub flags: Vec<String>,
+}
+
+/// Field reference, `@row$column`
+///
+/// A missing part refers to the row or column of the field being computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldRef {
+    pub row: Option<RowRef>,
+    pub column: Option<ColumnRef>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowRef {
+    /// `@3`, counted from the first row, excluding hlines
+    Absolute(u32),
+    /// `@-1`, `@+2`
+    Relative(i32),
+    /// `@<`, `@<<`: the n-th row from the top
+    First(u32),
+    /// `@>`, `@>>`: the n-th row from the bottom
+    Last(u32),
+    /// `@I`, `@-I`, `@II+1`
+    Hline { hline: HlineRef, offset: i32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HlineRef {
+    /// `@I`, `@II`: the n-th hline of the table
+    Absolute(u32),
+    /// `@-I`, `@-II`: the n-th hline above current row
+    Above(u32),
+    /// `@+I`, `@+II`: the n-th hline below current row
+    Below(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnRef {
+    /// `$3`
+    Absolute(u32),
+    /// `$-1`, `$+2`
+    Relative(i32),
+    /// `$<`, `$<<`: the n-th column from the left
+    First(u32),
+    /// `$>`, `$>>`: the n-th column from the right
+    Last(u32),
+}
+
+/// Calc-style expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaExpr {
+    Number(f64),
+    /// `"text"`
+    String(String),
+    /// Bare word, e.g. `pi`
+    Symbol(String),
+    /// `$name`, a column name, field name or constant
+    Name(String),
+    /// `@2$3`, `$3`, `@-1`
+    Field(FieldRef),
+    /// `@2$1..@>$1`, `$2..$3`
+    Range(FieldRef, FieldRef),
+    /// `@#`, current row number
+    RowNumber,
+    /// `$#`, current column number
+    ColumnNumber,
+    Neg(Box<FormulaExpr>),
+    Binary(Box<FormulaExpr>, BinaryOp, Box<FormulaExpr>),
+    /// `vsum($2..$3)`
+    Call(String, Vec<FormulaExpr>),
+    /// `remote(name, @2$3)`
+    Remote(String, Box<FormulaExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Pow,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl TableFormula {
+    /// Parses a single formula, returns `None` if it's malformed
+    pub fn parse(s: &str) -> Option<TableFormula> {
+        let (target, rest) = s.trim().split_once('=')?;
+
+        let (value, format) = match format_position(rest) {
+            Some(idx) => (&rest[..idx], Some(FormulaFormat::parse(&rest[idx + 1..]))),
+            None => (rest, None),
+        };
+
+        let value = value.trim();
+        let value = if let Some(lisp) = value.strip_prefix('\'') {
+            FormulaValue::Lisp(lisp.to_string())
+        } else {
+            let mut parser = Parser::new(value);
+            let expr = parser.expr()?;
+            parser.end()?;
+            FormulaValue::Calc(expr)
+        };
+
+        Some(TableFormula {
+            target: parse_target(target.trim())?,
+            value,
+            format,
+        })
+    }
+
+    /// Parses a `#+TBLFM` value, which contains formulas separated by `::`
+    ///
+    /// Malformed formulas are skipped.
+    ///
+    /// ```rust
+    /// use orgize::table::TableFormula;
+    ///
+    /// let formulas = TableFormula::parse_line(" $4=vsum($2..$3)::@>$1=Total::$5=");
+    /// assert_eq!(formulas.len(), 2);
+    /// ```
+    pub fn parse_line(s: &str) -> Vec<TableFormula> {
+        s.split("::").filter_map(TableFormula::parse).collect()
+    }
+}
+
+impl FormulaFormat {
+    /// ```rust
+    /// use orgize::table::FormulaFormat;
+    ///
+    /// let format = FormulaFormat::parse("%.2f");
+    /// assert_eq!(format.printf.as_deref(), Some("%.2f"));
+    /// assert!(format.flags.is_empty());
+    ///
+    /// let format = FormulaFormat::parse("NEf2");
+    /// assert_eq!(format.printf, None);
+    /// assert_eq!(format.flags, ["N", "E", "f2"]);
+    /// assert!(format.has_flag("N"));
+    /// ```
+    pub fn parse(s: &str) -> FormulaFormat {
+        let mut format = FormulaFormat::default();
+        let mut rest = s.trim();
+
+        while let Some(c) = rest.chars().next() {
+            let len = if c == '%' {
+                // ends after the conversion character, which may be multibyte
+                let len = rest[1..]
+                    .char_indices()
+                    .find(|(_, c)| !matches!(c, '-' | '+' | ' ' | '#' | '.' | '0'..='9'))
+                    .map_or(rest.len(), |(i, c)| i + 1 + c.len_utf8());
+                format.printf = Some(rest[..len].to_string());
+                len
+            } else if c.is_ascii_alphabetic() {
+                let len = rest[1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .map(|i| i + 1)
+                    .unwrap_or(rest.len());
+                format.flags.push(rest[..len].to_string());
+                len
+            } else {
+                c.len_utf8()
+            };
+            rest = &rest[len..];
+        }
+
+        format
+    }
+
+    /// Returns `true` if the format contains this mode flag
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+}
+
+/// Returns the position of last `;` outside of strings and parentheses
+fn format_position(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut position = None;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ';' if depth == 0 => position = Some(i),
+            _ => {}
+        }
+    }
+
+    position
+}
+
+fn parse_target(s: &str) -> Option<FormulaTarget> {
+    let mut parser = Parser::new(s);
+    let target = match parser.reference()? {
+        FormulaExpr::Name(name) => FormulaTarget::Named(name),
+        FormulaExpr::Range(start, end) => FormulaTarget::Range(start, end),
+        FormulaExpr::Field(FieldRef {
+            row: None,
+            column: Some(column),
+        }) => FormulaTarget::Column(column),
+        FormulaExpr::Field(field) => FormulaTarget::Field(field),
+        _ => return None,
+    };
+    parser.end()?;
+    Some(target)
+}
+
+/// Recursive descent parser of Calc-style expressions
+///
+/// Precedence from lowest to highest: comparison, `+ -`, `* / %`,
+/// unary `-`, `^`.
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser { s, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn end(&mut self) -> Option<()> {
+        self.skip_ws();
+        self.rest().is_empty().then_some(())
+    }
+
+    /// Consumes `c` repeatedly, returns how many times it was consumed
+    fn repeated(&mut self, c: char) -> u32 {
+        let mut count = 0;
+        while self.eat(c.encode_utf8(&mut [0; 4])) {
+            count += 1;
+        }
+        count
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn unsigned(&mut self) -> Option<u32> {
+        self.take_while(|c| c.is_ascii_digit()).parse().ok()
+    }
+
+    fn signed(&mut self) -> Option<i32> {
+        let start = self.pos;
+        if !self.eat("+") && !self.eat("-") {
+            return None;
+        }
+        match self.unsigned() {
+            Some(n) if self.s[start..].starts_with('-') => i32::try_from(n).ok()?.checked_neg(),
+            Some(n) => i32::try_from(n).ok(),
+            None => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    fn expr(&mut self) -> Option<FormulaExpr> {
+        let lhs = self.additive()?;
+        self.skip_ws();
+        let op = if self.eat("==") || self.eat("=") {
+            BinaryOp::Eq
+        } else if self.eat("!=") || self.eat("<>") {
+            BinaryOp::Ne
+        } else if self.eat("<=") {
+            BinaryOp::Le
+        } else if self.eat(">=") {
+            BinaryOp::Ge
+        } else if self.eat("<") {
+            BinaryOp::Lt
+        } else if self.eat(">") {
+            BinaryOp::Gt
+        } else {
+            return Some(lhs);
+        };
+        let rhs = self.additive()?;
+        Some(FormulaExpr::Binary(Box::new(lhs), op, Box::new(rhs)))
+    }
+
+    fn additive(&mut self) -> Option<FormulaExpr> {
+        let mut lhs = self.term()?;
+        loop {
+            self.skip_ws();
+            let op = if self.eat("+") {
+                BinaryOp::Add
+            } else if self.eat("-") {
+                BinaryOp::Sub
+            } else {
+                return Some(lhs);
+            };
+            let rhs = self.term()?;
+            lhs = FormulaExpr::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+    }
+
+    fn term(&mut self) -> Option<FormulaExpr> {
+        let mut lhs = self.unary()?;
+        loop {
+            self.skip_ws();
+            let op = if self.eat("*") {
+                BinaryOp::Mul
+            } else if self.eat("/") {
+                BinaryOp::Div
+            } else if self.eat("%") {
+                BinaryOp::Mod
+            } else {
+                return Some(lhs);
+            };
+            let rhs = self.unary()?;
+            lhs = FormulaExpr::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+    }
+
+    fn unary(&mut self) -> Option<FormulaExpr> {
+        self.skip_ws();
+        if self.eat("-") {
+            Some(FormulaExpr::Neg(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Option<FormulaExpr> {
+        let base = self.primary()?;
+        self.skip_ws();
+        if self.eat("^") {
+            let exponent = self.unary()?;
+            Some(FormulaExpr::Binary(
+                Box::new(base),
+                BinaryOp::Pow,
+                Box::new(exponent),
+            ))
+        } else {
+            Some(base)
+        }
+    }
+
+    fn primary(&mut self) -> Option<FormulaExpr> {
+        self.skip_ws();
+        match self.peek()? {
+            '(' => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.skip_ws();
+                self.eat(")").then_some(expr)
+            }
+            '"' => {
+                self.pos += 1;
+                let mut value = String::new();
+                loop {
+                    let c = self.peek()?;
+                    self.pos += c.len_utf8();
+                    match c {
+                        '"' => return Some(FormulaExpr::String(value)),
+                        '\\' => {
+                            let c = self.peek()?;
+                            self.pos += c.len_utf8();
+                            value.push(c);
+                        }
+                        _ => value.push(c),
+                    }
+                }
+            }
+            '@' | '$' => self.reference(),
+            c if c.is_ascii_digit() || c == '.' => self.number(),
+            c if c.is_alphabetic() => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                self.skip_ws();
+                if !self.eat("(") {
+                    return Some(FormulaExpr::Symbol(name.to_string()));
+                }
+                if name == "remote" {
+                    let table = self.take_while(|c| c != ',' && c != ')').trim();
+                    if !self.eat(",") || table.is_empty() {
+                        return None;
+                    }
+                    let reference = self.expr()?;
+                    self.skip_ws();
+                    return self
+                        .eat(")")
+                        .then(|| FormulaExpr::Remote(table.to_string(), Box::new(reference)));
+                }
+                let mut args = vec![];
+                self.skip_ws();
+                if !self.eat(")") {
+                    loop {
+                        args.push(self.expr()?);
+                        self.skip_ws();
+                        if self.eat(")") {
+                            break;
+                        }
+                        if !self.eat(",") {
+                            return None;
+                        }
+                    }
+                }
+                Some(FormulaExpr::Call(name.to_string(), args))
+            }
+            _ => None,
+        }
+    }
+
+    fn number(&mut self) -> Option<FormulaExpr> {
+        let start = self.pos;
+        self.take_while(|c| c.is_ascii_digit() || c == '.');
+        let mantissa = self.pos;
+        if self.eat("e") || self.eat("E") {
+            if !self.eat("+") {
+                self.eat("-");
+            }
+            if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+                self.pos = mantissa;
+            }
+        }
+        self.s[start..self.pos]
+            .parse()
+            .ok()
+            .map(FormulaExpr::Number)
+    }
+
+    /// Parses field reference, named reference or range
+    fn reference(&mut self) -> Option<FormulaExpr> {
+        if self.eat("@#") {
+            return Some(FormulaExpr::RowNumber);
+        }
+        if self.eat("$#") {
+            return Some(FormulaExpr::ColumnNumber);
+        }
+        if self.rest().starts_with('$') && self.rest()[1..].starts_with(|c: char| c.is_alphabetic())
+        {
+            self.pos += 1;
+            let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+            return Some(FormulaExpr::Name(name.to_string()));
+        }
+
+        let start = self.field()?;
+        if self.eat("..") {
+            let end = self.field()?;
+            Some(FormulaExpr::Range(start, end))
+        } else {
+            Some(FormulaExpr::Field(start))
+        }
+    }
+
+    fn field(&mut self) -> Option<FieldRef> {
+        let row = if self.eat("@") {
+            Some(self.row()?)
+        } else {
+            None
+        };
+        let column = if self.eat("$") {
+            Some(self.column()?)
+        } else {
+            None
+        };
+        (row.is_some() || column.is_some()).then_some(FieldRef { row, column })
+    }
+
+    fn row(&mut self) -> Option<RowRef> {
+        match self.peek()? {
+            '<' => Some(RowRef::First(self.repeated('<'))),
+            '>' => Some(RowRef::Last(self.repeated('>'))),
+            'I' => {
+                let hline = HlineRef::Absolute(self.repeated('I'));
+                let offset = self.signed().unwrap_or_default();
+                Some(RowRef::Hline { hline, offset })
+            }
+            '+' | '-' if self.rest()[1..].starts_with('I') => {
+                let above = self.eat("-");
+                self.eat("+");
+                let count = self.repeated('I');
+                let hline = if above {
+                    HlineRef::Above(count)
+                } else {
+                    HlineRef::Below(count)
+                };
+                let offset = self.signed().unwrap_or_default();
+                Some(RowRef::Hline { hline, offset })
+            }
+            '+' | '-' => self.signed().map(RowRef::Relative),
+            _ => self.unsigned().map(RowRef::Absolute),
+        }
+    }
+
+    fn column(&mut self) -> Option<ColumnRef> {
+        match self.peek()? {
+            '<' => Some(ColumnRef::First(self.repeated('<'))),
+            '>' => Some(ColumnRef::Last(self.repeated('>'))),
+            '+' | '-' => self.signed().map(ColumnRef::Relative),
+            _ => self.unsigned().map(ColumnRef::Absolute),
+        }
+    }
+}
+
+#[test]
+fn references() {
+    let calc = |s: &str| -> FormulaExpr {
+        match TableFormula::parse(&format!("$1={s}")).unwrap().value {
+            FormulaValue::Calc(expr) => expr,
+            FormulaValue::Lisp(_) => panic!("expected calc formula"),
+        }
+    };
+
+    let field =
+        |row: Option<RowRef>, column: Option<ColumnRef>| -> FieldRef { FieldRef { row, column } };
+
+    use ColumnRef as C;
+    use RowRef as R;
+
+    assert_eq!(
+        calc("@3$2"),
+        FormulaExpr::Field(field(Some(R::Absolute(3)), Some(C::Absolute(2))))
+    );
+    assert_eq!(
+        calc("@-1$+2"),
+        FormulaExpr::Field(field(Some(R::Relative(-1)), Some(C::Relative(2))))
+    );
+    assert_eq!(
+        calc("@<<$>"),
+        FormulaExpr::Field(field(Some(R::First(2)), Some(C::Last(1))))
+    );
+    assert_eq!(
+        calc("@II+1..@-I"),
+        FormulaExpr::Range(
+            field(
+                Some(R::Hline {
+                    hline: HlineRef::Absolute(2),
+                    offset: 1
+                }),
+                None
+            ),
+            field(
+                Some(R::Hline {
+                    hline: HlineRef::Above(1),
+                    offset: 0
+                }),
+                None
+            ),
+        )
+    );
+    assert_eq!(calc("$total"), FormulaExpr::Name("total".into()));
+    assert_eq!(
+        calc("@# + $#"),
+        FormulaExpr::Binary(
+            Box::new(FormulaExpr::RowNumber),
+            BinaryOp::Add,
+            Box::new(FormulaExpr::ColumnNumber)
+        )
+    );
+}
+
+#[test]
+fn expressions() {
+    let calc = |s: &str| -> FormulaExpr {
+        match TableFormula::parse(&format!("$1={s}")).unwrap().value {
+            FormulaValue::Calc(expr) => expr,
+            FormulaValue::Lisp(_) => panic!("expected calc formula"),
+        }
+    };
+
+    let field =
+        |row: Option<RowRef>, column: Option<ColumnRef>| -> FieldRef { FieldRef { row, column } };
+
+    use FormulaExpr::*;
+
+    let col = |n| Field(field(None, Some(ColumnRef::Absolute(n))));
+
+    assert_eq!(
+        calc("$1-$2*3^2"),
+        Binary(
+            Box::new(col(1)),
+            BinaryOp::Sub,
+            Box::new(Binary(
+                Box::new(col(2)),
+                BinaryOp::Mul,
+                Box::new(Binary(
+                    Box::new(Number(3.)),
+                    BinaryOp::Pow,
+                    Box::new(Number(2.))
+                ))
+            ))
+        )
+    );
+    assert_eq!(
+        calc("-(1.5e2 / $2)"),
+        Neg(Box::new(Binary(
+            Box::new(Number(150.)),
+            BinaryOp::Div,
+            Box::new(col(2))
+        )))
+    );
+    assert_eq!(
+        calc(r#"if($1 < 20, teen, "a\"b")"#),
+        Call(
+            "if".into(),
+            vec![
+                Binary(Box::new(col(1)), BinaryOp::Lt, Box::new(Number(20.))),
+                Symbol("teen".into()),
+                String("a\"b".into())
+            ]
+        )
+    );
+    assert_eq!(
+        calc("remote(my-table, @2$3)"),
+        Remote(
+            "my-table".into(),
+            Box::new(Field(field(
+                Some(RowRef::Absolute(2)),
+                Some(ColumnRef::Absolute(3))
+            )))
+        )
+    );
+}
+
+#[test]
+fn targets() {
+    let field =
+        |row: Option<RowRef>, column: Option<ColumnRef>| -> FieldRef { FieldRef { row, column } };
+
+    let target = |s: &str| TableFormula::parse(s).unwrap().target;
+
+    assert_eq!(
+        target("$2=1"),
+        FormulaTarget::Column(ColumnRef::Absolute(2))
+    );
+    assert_eq!(target("$max=1"), FormulaTarget::Named("max".into()));
+    assert_eq!(
+        target("@2$1..@4$3=1"),
+        FormulaTarget::Range(
+            field(Some(RowRef::Absolute(2)), Some(ColumnRef::Absolute(1))),
+            field(Some(RowRef::Absolute(4)), Some(ColumnRef::Absolute(3)))
+        )
+    );
+    assert!(TableFormula::parse("1=1").is_none());
+    assert!(TableFormula::parse("@#=1").is_none());
+}
+
+#[test]
+fn malformed() {
+    // out of range references are rejected instead of overflowing
+    assert!(TableFormula::parse("$1=@-2147483648$1").is_none());
+    assert!(TableFormula::parse("$1=@+4294967295$1").is_none());
+
+    // multibyte conversion characters
+    assert_eq!(FormulaFormat::parse("%é").printf.as_deref(), Some("%é"));
+    assert_eq!(
+        FormulaFormat::parse("%.2日N").printf.as_deref(),
+        Some("%.2日")
+    );
+}
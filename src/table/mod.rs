@@ -0,0 +1,11 @@
+//! Org table alignment, formulas and spreadsheet evaluation
+
+mod align;
+mod eval;
+mod formula;
+
+pub use eval::{CellEdit, FormulaError};
+pub use formula::{
+    BinaryOp, ColumnRef, FieldRef, FormulaExpr, FormulaFormat, FormulaTarget, FormulaValue,
+    HlineRef, RowRef, TableFormula,
+};
@@ -1,4 +1,8 @@
-use orgize::Org;
+use orgize::{
+    config::UseSubSuperscript,
+    export::{Bibliography, HtmlExport},
+    Org, ParseConfig,
+};
 
 #[test]
 fn emphasis() {
@@ -17,6 +21,30 @@ fn link() {
         Org::parse("Visit[[http://example.com][link1]]or[[http://example.com][link1]].").to_html(),
         @r###"<main><section><p>Visit<a href="http://example.com">link1</a>or<a href="http://example.com">link1</a>.</p></section></main>"###
     );
+
+    insta::assert_snapshot!(
+        Org::parse("Visit https://example.com, or <mailto:a@example.com>.").to_html(),
+        @r###"<main><section><p>Visit <a href="https://example.com">https://example.com</a>, or <a href="mailto:a@example.com">mailto:a@example.com</a>.</p></section></main>"###
+    );
+}
+
+#[test]
+fn link_abbreviation() {
+    insta::assert_snapshot!(
+        Org::parse("#+LINK: gh https://github.com/%s\n[[gh:PoiScript/orgize][orgize]]").to_html(),
+        @r###"<main><section><p><a href="https://github.com/PoiScript/orgize">orgize</a></p></section></main>"###
+    );
+}
+
+#[test]
+fn radio_link() {
+    insta::assert_snapshot!(
+        Org::parse("<<<Radio Target>>> is linked from radio\ntarget.").to_html(),
+        @r###"
+    <main><section><p><a id="radio-radio-target">Radio Target</a> is linked from <a href="#radio-radio-target">radio
+    target</a>.</p></section></main>
+    "###
+    );
 }
 
 #[test]
@@ -67,6 +95,28 @@ fn list() {
     );
 }
 
+#[test]
+fn ordered_list() {
+    insta::assert_snapshot!(
+        Org::parse("3. a\n4. [@7] b\n5. c\n").to_html(),
+        @r###"
+    <main><section><ol start="3"><li><p>a
+    </p></li><li value="7"><p>b
+    </p></li><li><p>c
+    </p></li></ol></section></main>
+    "###
+    );
+
+    insta::assert_snapshot!(
+        Org::parse("1. [@4] a\n2. b\n").to_html(),
+        @r###"
+    <main><section><ol start="4"><li><p>a
+    </p></li><li><p>b
+    </p></li></ol></section></main>
+    "###
+    );
+}
+
 #[test]
 fn snippet() {
     insta::assert_snapshot!(
@@ -109,7 +159,7 @@ fn table() {
 |   4 |   5 |   6 |
 |-----+-----+-----|
 "#).to_html(),
-        @"<main><section><table><tbody><tr><td>0</td><td>1</td><td>2</td></tr><tr><td>4</td><td>5</td><td>6</td></tr></tbody></table></section></main>"
+        @r###"<main><section><table><tbody><tr><td class="org-right">0</td><td class="org-right">1</td><td class="org-right">2</td></tr><tr><td class="org-right">4</td><td class="org-right">5</td><td class="org-right">6</td></tr></tbody></table></section></main>"###
     );
 
     // has table header
@@ -120,7 +170,7 @@ fn table() {
 |   4 |   5 |   6 |
 |-----+-----+-----|
 "#).to_html(),
-        @"<main><section><table><thead><tr><td>0</td><td>1</td><td>2</td></tr></thead><tbody><tr><td>4</td><td>5</td><td>6</td></tr></tbody></table></section></main>"
+        @r###"<main><section><table><thead><tr><td class="org-right">0</td><td class="org-right">1</td><td class="org-right">2</td></tr></thead><tbody><tr><td class="org-right">4</td><td class="org-right">5</td><td class="org-right">6</td></tr></tbody></table></section></main>"###
     );
 
     // has two table body
@@ -132,7 +182,7 @@ fn table() {
 |-----+-----+-----|
 |   7 |   8 |   9 |
 "#).to_html(),
-        @"<main><section><table><thead><tr><td>0</td><td>1</td><td>2</td></tr></thead><tbody><tr><td>4</td><td>5</td><td>6</td></tr></tbody><tbody><tr><td>7</td><td>8</td><td>9</td></tr></tbody></table></section></main>"
+        @r###"<main><section><table><thead><tr><td class="org-right">0</td><td class="org-right">1</td><td class="org-right">2</td></tr></thead><tbody><tr><td class="org-right">4</td><td class="org-right">5</td><td class="org-right">6</td></tr></tbody><tbody><tr><td class="org-right">7</td><td class="org-right">8</td><td class="org-right">9</td></tr></tbody></table></section></main>"###
     );
 
     // multiple row rule
@@ -143,7 +193,7 @@ fn table() {
 |-----+-----+-----|
 |   4 |   5 |   6 |
 "#).to_html(),
-        @"<main><section><table><thead><tr><td>0</td><td>1</td><td>2</td></tr></thead><tbody><tr><td>4</td><td>5</td><td>6</td></tr></tbody></table></section></main>"
+        @r###"<main><section><table><thead><tr><td class="org-right">0</td><td class="org-right">1</td><td class="org-right">2</td></tr></thead><tbody><tr><td class="org-right">4</td><td class="org-right">5</td><td class="org-right">6</td></tr></tbody></table></section></main>"###
     );
 
     // empty
@@ -165,6 +215,53 @@ fn table() {
 "#).to_html(),
         @"<main><section><table><thead><tr></tr></thead><tbody><tr></tr></tbody><tbody><tr></tr></tbody></table></section></main>"
     );
+
+    // alignment cookies, marking column and column groups
+    insta::assert_snapshot!(
+        Org::parse(r#"
+| / | <   | >   | <>  |
+|   | <l> | <r> | <c> |
+| ! | a   | b   | c   |
+| # | 1   |     | x   |
+"#).to_html(),
+        @r###"<main><section><table><colgroup><col class="org-left"><col class="org-right"></colgroup><colgroup><col class="org-center"></colgroup><tbody><tr><td class="org-left">1</td><td class="org-right"></td><td class="org-center">x</td></tr></tbody></table></section></main>"###
+    );
+}
+
+#[test]
+fn table_el() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
++-----+-----+
+| a   | b   |
++=====+=====+
+| c         |
++-----+-----+
+| d   | e   |
++-----+ f   |
+| <g> |     |
++-----+-----+
+"#).to_html(),
+        @r###"
+    <main><section><table><thead><tr><th>a</th><th>b</th></tr></thead><tbody><tr><td colspan="2">c</td></tr><tr><td>d</td><td rowspan="2">e
+    f</td></tr><tr><td>&lt;g&gt;</td></tr></tbody></table></section></main>
+    "###
+    );
+
+    insta::assert_snapshot!(
+        Org::parse("+---+\n| a |\n+---+").to_html(),
+        @r###"<main><section><table><tbody><tr><td>a</td></tr></tbody></table></section></main>"###
+    );
+
+    // malformed borders
+    insta::assert_snapshot!(
+        Org::parse("+---+\n| a  \n+---+").to_html(),
+        @r###"
+    <main><section><pre>+---+
+    | a  
+    +---+</pre></section></main>
+    "###
+    );
 }
 
 #[test]
@@ -174,3 +271,168 @@ fn line_break() {
         @r###""<main><section><p>aa<br/>bb</p></section></main>""###
     );
 }
+
+#[test]
+fn inlinetask() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+* headline
+*************** TODO *task*
+content
+*************** END
+"#).to_html(),
+        @r###"
+    <main><h1>headline</h1><section><div class="inlinetask"><b><b>task</b></b><p>content
+    </p></div></section></main>
+    "###
+    );
+}
+
+#[test]
+fn export_options() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+OPTIONS: *:nil ^:{} e:nil <:nil |:nil
+*bold* a_b c_{d} \alpha <2003-09-16 Tue>
+| table |
+"#).to_html(),
+        @r###"
+    <main><section><p>*bold* a_b c<sub>d</sub> \alpha 
+    </p></section></main>
+    "###
+    );
+
+    insta::assert_snapshot!(
+        Org::parse(r#"* TODO [#A] Head :a:b:
+a[fn:1]
+
+[fn:1] note
+"#).to_html(),
+        @r###"
+    <main><h1><span class="todo TODO">TODO</span> Head <span class="tag"><span class="a">a</span>&#xa0;<span class="b">b</span></span></h1><section><p>afn1
+    </p>fn1 note</section></main>
+    "###
+    );
+
+    insta::assert_snapshot!(
+        Org::parse(r#"#+OPTIONS: f:nil todo:nil tags:nil pri:t H:1
+* TODO [#A] Head :a:b:
+a[fn:1]
+
+[fn:1] note
+** Sub :c:
+*** Deep
+** Sub 2
+"#).to_html(),
+        @r###"
+    <main><section></section><h1>[#A] Head</h1><section><p>a
+    </p></section><ul><li>Sub<ul><li>Deep</li></ul></li><li>Sub 2</li></ul></main>
+    "###
+    );
+}
+
+#[test]
+fn macros() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+TITLE: Orgize
+#+MACRO: poem Rose is $1, violet's $2. Life's ordered: Org assists you.
+#+MACRO: bold *$1*
+* Chapter {{{n}}}
+:PROPERTIES:
+:AUTHOR: poi
+:END:
+{{{title}}} by {{{property(AUTHOR)}}}: {{{poem(red,blue)}}} {{{bold({{{n}}})}}} {{{keyword(title)}}}
+"#).to_html(),
+        @r###"
+    <main><section></section><h1>Chapter 1</h1><section><p>Orgize by poi: Rose is red, violet&apos;s blue. Life&apos;s ordered: Org assists you. <b>2</b> Orgize
+    </p></section></main>
+    "###
+    );
+
+    // expansions are parsed with document's parse config
+    let config = ParseConfig {
+        use_sub_superscript: UseSubSuperscript::Nil,
+        ..Default::default()
+    };
+    insta::assert_snapshot!(
+        config.parse(r#"#+MACRO: all ($0) $1_{$2}
+{{{all(a, b)}}} a_{b}
+"#).to_html(),
+        @r###"
+    <main><section><p>(a, b) a_{ b} a_{b}
+    </p></section></main>
+    "###
+    );
+}
+
+#[test]
+fn citation() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+BIBLIOGRAPHY: refs.bib
+As shown [cite/t:see;@doe2020; @smith *p. 3*], [cite/n:@hidden]
+#+PRINT_BIBLIOGRAPHY:
+"#).to_html(),
+        @r###"
+    <main><section><p>As shown <span class="citation">see <a href="#cite-doe2020">doe2020</a>; <a href="#cite-smith">smith</a> <b>p. 3</b></span>, 
+    </p></section></main>
+    "###
+    );
+
+    let org = Org::parse(
+        r#"#+CITE_EXPORT: basic nil numeric
+See [cite:@smith; @doe2020, p. 3] and [cite:@doe2020].
+#+PRINT_BIBLIOGRAPHY:
+"#,
+    );
+    let mut html = HtmlExport::default();
+    html.set_bibliography(Bibliography::from_bibtex(
+        r#"@article{doe2020, author = {Doe, Jane}, title = {Plain Text}, journal = {Org Journal}, volume = 1, year = 2020}
+@book{smith, author = {Smith, Anne and Roe, Richard}, title = {Outlines}, publisher = {Org Press}, year = 2019}"#,
+    ));
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r###"
+    <main><section><p>See <span class="citation">[<a href="#cite-smith">1</a>, <a href="#cite-doe2020">2</a>, p. 3]</span> and <span class="citation">[<a href="#cite-doe2020">2</a>]</span>.
+    </p><ul class="bibliography"><li id="cite-smith">[1] Smith, Anne and Roe, Richard (2019). Outlines. Org Press.</li><li id="cite-doe2020">[2] Doe, Jane (2020). Plain Text. Org Journal, 1.</li></ul></section></main>
+    "###);
+}
+
+#[test]
+fn source_block_line_numbers() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+begin_src rust -n 10 -r
+    fn main() {
+        let x = 1; (ref:x)
+    }
+#+end_src
+#+begin_src rust +n -l "[%s]"
+    main(); [call]
+#+end_src
+Line [[(x)]] defines x, [[(call)][this line]] calls main.
+"#).to_html(),
+        @r###"
+    <main><section><pre><code class="language-rust"><span class="linenr">10: </span>fn main() {
+    <span id="coderef-x" class="coderef"><span class="linenr">11: </span>    let x = 1;</span>
+    <span class="linenr">12: </span>}
+    </code></pre><pre><code class="language-rust"><span id="coderef-call" class="coderef"><span class="linenr">13: </span>main(); [call]</span>
+    </code></pre><p>Line <a href="#coderef-x" class="coderef">11</a> defines x, <a href="#coderef-call" class="coderef">this line</a> calls main.
+    </p></section></main>
+    "###
+    );
+
+    // line numbers saturate instead of overflowing
+    insta::assert_snapshot!(
+        Org::parse(r#"#+begin_src c -n 4294967295
+a
+b
+#+end_src
+#+begin_src c +n 10
+c
+#+end_src
+"#).to_html(),
+        @r###"
+    <main><section><pre><code class="language-c"><span class="linenr">4294967295: </span>a
+    <span class="linenr">4294967295: </span>b
+    </code></pre><pre><code class="language-c"><span class="linenr">4294967295: </span>c
+    </code></pre></section></main>
+    "###
+    );
+}
@@ -47,6 +47,7 @@ impl Org {
                     Container::Section(x) => ("Section", x.text_range()),
                     Container::Paragraph(x) => ("Paragraph", x.text_range()),
                     Container::Headline(x) => ("Headline", x.text_range()),
+                    Container::Inlinetask(x) => ("Inlinetask", x.text_range()),
                     Container::OrgTable(x) => ("OrgTable", x.text_range()),
                     Container::OrgTableRow(x) => ("OrgTableRow", x.text_range()),
                     Container::OrgTableCell(x) => ("OrgTableCell", x.text_range()),
@@ -68,6 +69,7 @@ impl Org {
                     Container::SourceBlock(x) => ("SourceBlock", x.text_range()),
                     Container::Link(x) => ("Link", x.text_range()),
                     Container::RadioTarget(x) => ("RadioTarget", x.text_range()),
+                    Container::RadioLink(x) => ("RadioLink", x.text_range()),
                     Container::FnRef(x) => ("FnRef", x.text_range()),
                     Container::Target(x) => ("Target", x.text_range()),
                     Container::Bold(x) => ("Bold", x.text_range()),
@@ -101,6 +103,8 @@ impl Org {
                 Event::LatexFragment(x) => ("LatexFragment", x.text_range()),
                 Event::LatexEnvironment(x) => ("LatexEnvironment", x.text_range()),
                 Event::Entity(x) => ("Entity", x.text_range()),
+                Event::Citation(x) => ("Citation", x.text_range()),
+                Event::PrintBibliography(x) => ("PrintBibliography", x.text_range()),
                 _ => unreachable!(),
             };
 